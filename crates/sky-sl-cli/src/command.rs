@@ -11,6 +11,49 @@ pub enum Command {
         #[structopt(name = "OUTPUT")]
         output: PathBuf,
     },
+
+    #[structopt(about = "Check a file and the modules it uses")]
+    Build {
+        #[structopt(name = "FILE")]
+        file: PathBuf,
+
+        /// Sets an overridable constant by name or id, e.g. `--override SHADOW_QUALITY=2`
+        #[structopt(long = "override", name = "KEY=VALUE", number_of_values = 1)]
+        overrides: Vec<String>,
+    },
+
+    #[structopt(about = "Describe the layouts and entry points of a file and the modules below it")]
+    Reflect {
+        #[structopt(name = "FILE")]
+        file: PathBuf,
+
+        #[structopt(long = "format", default_value = "json", possible_values = &["json"])]
+        format: String,
+    },
+
+    #[structopt(about = "Check that entry points sharing a pipeline agree on their bindings")]
+    Pipeline {
+        #[structopt(name = "FILE")]
+        file: PathBuf,
+
+        /// The paths of the entry points below the module in FILE, e.g. `vertex_main lights::shade`
+        #[structopt(name = "ENTRY_POINT", required = true)]
+        entry_points: Vec<String>,
+    },
+
+    #[structopt(about = "Check that the outputs of a vertex entry point match the inputs of a fragment entry point")]
+    Interface {
+        #[structopt(name = "FILE")]
+        file: PathBuf,
+
+        /// The path of the vertex entry point below the module in FILE
+        #[structopt(name = "VERTEX")]
+        vertex: String,
+
+        /// The path of the fragment entry point below the module in FILE
+        #[structopt(name = "FRAGMENT")]
+        fragment: String,
+    },
 }
 
 impl Command {
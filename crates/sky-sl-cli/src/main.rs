@@ -1,6 +1,9 @@
 mod command;
 
+use camino::{Utf8Path, Utf8PathBuf};
 use command::*;
+use sky_sl::{hir::const_eval::ConstValue, workspace::{find_override, OverrideConstant, OverrideError, Workspace}};
+use std::path::PathBuf;
 
 fn main() {
     match Command::from_args() {
@@ -13,5 +16,161 @@ fn main() {
             dbg!(result.diagnostics());
             dbg!(result.tree());
         },
+        Command::Build { file, overrides } => {
+            let (mut workspace, file) = open_workspace(file);
+            let mut failed = report_errors(&mut workspace, &file);
+
+            // there is no backend to bake the values into yet, so the resolved values are listed instead
+            let constants = workspace.get_overrides(&file).expect("Could not reflect overrides");
+            let mut values: Vec<_> = constants.iter().map(|constant| constant.default).collect();
+            for assignment in &overrides {
+                match apply_override(&constants, &mut values, assignment) {
+                    Ok(()) => {},
+                    Err(error) => {
+                        eprintln!("error: {}", error);
+                        failed = true;
+                    },
+                }
+            }
+
+            for (constant, value) in constants.iter().zip(values) {
+                let value = value.map(|value| value.display()).unwrap_or_else(|| "?".to_string());
+                println!("override({}) {}: {} = {}", constant.id, constant.name, constant.ty.display(), value);
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        },
+        Command::Reflect { file, format } => {
+            let (mut workspace, file) = open_workspace(file);
+            if report_errors(&mut workspace, &file) {
+                std::process::exit(1);
+            }
+
+            let reflection = workspace.get_reflection(&file).expect("Could not reflect file");
+            let output = match format.as_str() {
+                "json" => serde_json::to_string_pretty(&reflection).expect("Could not serialize reflection"),
+                _ => unreachable!("unknown formats are rejected when parsing the arguments"),
+            };
+            println!("{}", output);
+        },
+        Command::Pipeline { file, entry_points } => {
+            let (mut workspace, file) = open_workspace(file);
+            let mut failed = report_errors(&mut workspace, &file);
+
+            let entry_points: Vec<&str> = entry_points.iter().map(String::as_str).collect();
+            match workspace.get_pipeline_layout(&file, &entry_points) {
+                Ok(pipeline) => {
+                    for binding in &pipeline.bindings {
+                        // printed like the declaration, e.g. `storage(read)`
+                        let kind = match binding.access {
+                            Some(access) => format!("{}({})", binding.kind, access),
+                            None => binding.kind.to_string(),
+                        };
+
+                        println!(
+                            "set({}) binding({}) {} {}::{}::{}: {}",
+                            binding.set, binding.binding, kind, binding.layout.module, binding.layout.name, binding.member, binding.ty,
+                        );
+                    }
+
+                    for error in &pipeline.errors {
+                        eprintln!("error: {}", error);
+                        failed = true;
+                    }
+                },
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    failed = true;
+                },
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        },
+        Command::Interface { file, vertex, fragment } => {
+            let (mut workspace, file) = open_workspace(file);
+            let mut failed = report_errors(&mut workspace, &file);
+
+            match workspace.get_stage_interface(&file, &vertex, &fragment) {
+                Ok(interface) => {
+                    for input in &interface.inputs {
+                        println!("location({}) interpolate({}) {}: {}", input.location, input.interpolation, input.name, input.ty);
+                    }
+
+                    for error in &interface.errors {
+                        let path = match &error.structure {
+                            Some(structure) => format!("{}::{}::{}", error.module, structure, error.member),
+                            None => format!("{}::{}::{}", error.module, fragment, error.member),
+                        };
+                        eprintln!("error: {} at {}:{}: {}", path, error.line, error.column, error.message);
+                        failed = true;
+                    }
+                },
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    failed = true;
+                },
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        },
     }
 }
+
+/// Creates a workspace containing all shader files next to and below `file`
+fn open_workspace(file: PathBuf) -> (Workspace, Utf8PathBuf) {
+    let file = file.canonicalize().expect("Could not find file");
+    let file = Utf8PathBuf::from_path_buf(file).expect("File path is not valid UTF-8");
+    let root = file.parent().expect("File has no parent directory").to_owned();
+
+    let workspace = Workspace::open(root).expect("Could not read shader files");
+    (workspace, file)
+}
+
+/// Prints the errors and warnings of a file, returns true if there are any errors
+fn report_errors(workspace: &mut Workspace, file: &Utf8Path) -> bool {
+    let mut failed = false;
+
+    let line_index = workspace.get_line_index(file).expect("Could not index file");
+    let hir = workspace.get_hir(file).expect("Could not lower file");
+    for error in hir.errors {
+        match error.span() {
+            Some(span) => {
+                let position = line_index.find_position(span.start());
+                eprintln!("error: {}:{}:{}: {}", file, position.line + 1, position.column + 1, error);
+            },
+            None => eprintln!("error: {}", error),
+        }
+        failed = true;
+    }
+
+    let typed_hir = workspace.get_typed_hir(file).expect("Could not type check file");
+    for error in typed_hir.errors {
+        let position = line_index.find_position(error.span().start());
+        eprintln!("error: {}:{}:{}: {}", file, position.line + 1, position.column + 1, error);
+        failed = true;
+    }
+
+    for warning in typed_hir.warnings {
+        let position = line_index.find_position(warning.span().start());
+        eprintln!("warning: {}:{}:{}: {}", file, position.line + 1, position.column + 1, warning);
+    }
+
+    failed
+}
+
+/// Applies a `KEY=VALUE` assignment, where the key is the name or id of an overridable constant
+fn apply_override(constants: &[OverrideConstant], values: &mut [Option<ConstValue>], assignment: &str) -> Result<(), OverrideError> {
+    let (key, value) = assignment.split_once('=').unwrap_or((assignment, ""));
+    let constant = find_override(constants, key.trim())?;
+    let value = constant.parse_value(value.trim())?;
+
+    let index = constants.iter().position(|other| other.id == constant.id).unwrap();
+    values[index] = Some(value);
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned, ToTokens};
+use sky_sl::{hir::memory_layout::LayoutRules, workspace::{RustField, RustStruct, Workspace}};
+use syn::{ext::IdentExt, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput, Expr, ExprLit, Field, Fields, Lit, LitStr, Token};
+
+/// Verifies that a Rust struct matches the layout of a struct in a sky-sl package, e.g.
+///
+/// ```ignore
+/// #[derive(SkyslLayout)]
+/// #[skysl(package = "shaders", path = "lights::Light", rules = "std430")]
+/// #[repr(C)]
+/// struct Light {
+///     color: [f32; 3],
+///     _pad0: [u8; 4],
+/// }
+/// ```
+///
+/// `package` is the package directory, relative paths are resolved against the manifest of the crate using the derive.
+/// It can also be built with `env!` and `concat!`, e.g. `concat!(env!("OUT_DIR"), "/shaders")`.
+/// `path` is the path of the struct below the package's `src/lib.skysl` and `rules` the layout rules, `std140` if omitted.
+/// Field names and types are compared with the types `sky-sl` generates for the struct, fields starting with `_`
+/// are padding and are skipped. Offsets and the size are checked by compile time assertions.
+#[proc_macro_derive(SkyslLayout, attributes(skysl))]
+pub fn derive_skysl_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match verify_layout(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct Attributes {
+    package: Utf8PathBuf,
+    package_span: Span,
+    path: LitStr,
+    rules: LayoutRules,
+}
+
+fn parse_attributes(input: &DeriveInput) -> syn::Result<Attributes> {
+    let mut package = None;
+    let mut path = None;
+    let mut rules = LayoutRules::Std140;
+
+    for attribute in input.attrs.iter().filter(|attribute| attribute.path().is_ident("skysl")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("package") {
+                let value: Expr = meta.value()?.parse()?;
+                package = Some((eval_package(&value)?, value.span()));
+                return Ok(());
+            }
+
+            let value: LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("path") {
+                path = Some(value);
+            } else if meta.path.is_ident("rules") {
+                rules = LayoutRules::from_name(&value.value())
+                    .ok_or_else(|| syn::Error::new(value.span(), "expected `std140`, `std430` or `scalar`"))?;
+            } else {
+                return Err(meta.error("expected `package`, `path` or `rules`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let missing = |name| syn::Error::new(input.ident.span(), format!("missing `#[skysl({} = \"...\")]`", name));
+
+    let (package, package_span) = package.ok_or_else(|| missing("package"))?;
+
+    Ok(Attributes {
+        package,
+        package_span,
+        path: path.ok_or_else(|| missing("path"))?,
+        rules,
+    })
+}
+
+/// Evaluates the package path, a string literal or `env!` and `concat!` of string literals
+fn eval_package(value: &Expr) -> syn::Result<Utf8PathBuf> {
+    let path = eval_string(value)?;
+    if Utf8Path::new(&path).is_absolute() {
+        return Ok(Utf8PathBuf::from(path));
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| syn::Error::new(value.span(), "CARGO_MANIFEST_DIR is not set"))?;
+    Ok(Utf8PathBuf::from(manifest_dir).join(path))
+}
+
+fn eval_string(value: &Expr) -> syn::Result<String> {
+    match value {
+        Expr::Lit(ExprLit { lit: Lit::Str(value), .. }) => Ok(value.value()),
+        Expr::Macro(expr) if expr.mac.path.is_ident("env") => {
+            let name: LitStr = expr.mac.parse_body()?;
+            std::env::var(name.value())
+                .map_err(|_| syn::Error::new(name.span(), format!("environment variable `{}` is not set", name.value())))
+        },
+        Expr::Macro(expr) if expr.mac.path.is_ident("concat") => {
+            let parts = expr.mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+            parts.iter().map(eval_string).collect()
+        },
+        _ => Err(syn::Error::new(value.span(), "expected a string literal, `env!` or `concat!`")),
+    }
+}
+
+fn verify_layout(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let attributes = parse_attributes(input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new(input.ident.span(), "SkyslLayout can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new(input.ident.span(), "SkyslLayout can only be derived for structs")),
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(input.generics.span(), "SkyslLayout can't be derived for generic structs"));
+    }
+
+    let (files, rust_struct) = load_struct(&attributes)?;
+
+    // padding fields like `_pad0` are only compared by the offsets of the other fields
+    let fields: Vec<&Field> = fields.iter()
+        .filter(|field| !field.ident.as_ref().unwrap().unraw().to_string().starts_with('_'))
+        .collect();
+
+    // runtime-sized arrays are not part of the Rust type
+    let expected: Vec<&RustField> = rust_struct.fields.iter().filter(|field| field.rust_ty.is_some()).collect();
+
+    let mut errors: Option<syn::Error> = None;
+    let mut report = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    for (index, expected) in expected.iter().enumerate() {
+        let expected_ty = expected.rust_ty.as_deref().unwrap();
+
+        let field = match fields.get(index) {
+            Some(field) => field,
+            None => {
+                let message = format!("missing field `{}: {}` of the sky-sl struct `{}`", expected.name, expected_ty, rust_struct.name);
+                report(syn::Error::new(input.ident.span(), message));
+                continue;
+            },
+        };
+
+        let name = field.ident.as_ref().unwrap().unraw().to_string();
+        if name != expected.name {
+            let message = format!("expected field `{}` of the sky-sl struct `{}`, found `{}`", expected.name, rust_struct.name, name);
+            report(syn::Error::new(field.ident.span(), message));
+            continue;
+        }
+
+        let ty = field.ty.to_token_stream().to_string();
+        if normalize_type(&ty) != normalize_type(expected_ty) {
+            let message = format!(
+                "`{}` is a `{}` in sky-sl and must be a `{}` in the {} layout, found `{}`",
+                name, expected.sky_sl_ty, expected_ty, attributes.rules.name(), normalize_type(&ty),
+            );
+            report(syn::Error::new(field.ty.span(), message));
+        }
+    }
+
+    for field in fields.iter().skip(expected.len()) {
+        let message = format!("`{}` is not a member of the sky-sl struct `{}`", field.ident.as_ref().unwrap().unraw(), rust_struct.name);
+        report(syn::Error::new(field.ident.span(), message));
+    }
+
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let name = &input.ident;
+    let rules = attributes.rules.name();
+
+    let offsets = fields.iter().zip(&expected).map(|(field, expected)| {
+        let ident = &field.ident;
+        let offset = expected.offset as usize;
+        let message = format!("`{}` must be at offset {} to match the {} layout of `{}`", expected.name, offset, rules, rust_struct.name);
+        quote_spanned!(field.span()=> assert!(::core::mem::offset_of!(#name, #ident) == #offset, #message);)
+    });
+
+    let size = rust_struct.size as usize;
+    let size_message = format!("`{}` must be {} bytes to match the {} layout of `{}`", name, size, rules, rust_struct.name);
+    let files = files.iter().map(|file| file.as_str());
+
+    Ok(quote! {
+        const _: () = {
+            // recompiles the checks when a shader changes
+            #(const _: &[u8] = include_bytes!(#files);)*
+
+            #(#offsets)*
+            assert!(::core::mem::size_of::<#name>() == #size, #size_message);
+        };
+    })
+}
+
+/// Loads the package and describes the struct, fails if the package has errors
+fn load_struct(attributes: &Attributes) -> syn::Result<(Vec<Utf8PathBuf>, RustStruct)> {
+    let package_error = |message: String| syn::Error::new(attributes.package_span, message);
+
+    let package = &attributes.package;
+    let root = package.join("src").join("lib.skysl");
+
+    let mut workspace = Workspace::create(package.clone());
+    let files = workspace.insert_directory(package).map_err(|error| package_error(error.to_string()))?;
+
+    for file in &files {
+        if let Some(error) = first_error(&mut workspace, file) {
+            return Err(package_error(error));
+        }
+    }
+
+    let path = attributes.path.value();
+    let rust_struct = workspace.get_rust_struct(&root, &path, attributes.rules)
+        .map_err(|error| package_error(format!("could not load `{}`: {}", root, error)))?
+        .ok_or_else(|| syn::Error::new(attributes.path.span(), format!("`{}` is not a struct in `{}`", path, package)))?;
+
+    if let Some(error) = rust_struct.errors.first() {
+        let message = format!("`{}` has no {} layout: {}", rust_struct.name, attributes.rules.name(), error);
+        return Err(syn::Error::new(attributes.path.span(), message));
+    }
+
+    Ok((files, rust_struct))
+}
+
+fn first_error(workspace: &mut Workspace, file: &Utf8Path) -> Option<String> {
+    let hir = workspace.get_hir(file).ok()?;
+    if let Some(error) = hir.errors.first() {
+        return Some(format!("{}: {}", file, error));
+    }
+
+    let line_index = workspace.get_line_index(file).ok()?;
+    let typed_hir = workspace.get_typed_hir(file).ok()?;
+    typed_hir.errors.first().map(|error| {
+        let position = line_index.find_position(error.span().start());
+        format!("{}:{}:{}: {}", file, position.line + 1, position.column + 1, error)
+    })
+}
+
+/// Removes whitespace and path prefixes, so `[ lights :: Light ; 4 ]` and `[Light; 4]` compare equal
+fn normalize_type(ty: &str) -> String {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    let segments: Vec<&str> = ty.split("::").collect();
+    let (last, prefixes) = segments.split_last().unwrap();
+
+    let mut normalized: String = prefixes.iter()
+        .map(|segment| segment.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_'))
+        .collect();
+    normalized.push_str(last);
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_type;
+
+    #[test]
+    fn it_normalizes_types() {
+        assert_eq!(normalize_type("[ lights :: Light ; 4 ]"), normalize_type("[Light; 4]"));
+        assert_eq!(normalize_type("crate :: shaders :: lights :: Light"), "Light");
+        assert_eq!(normalize_type("[ [ f32 ; 4 ] ; 4 ]"), "[[f32;4];4]");
+        assert_ne!(normalize_type("[f32; 3]"), normalize_type("[f32; 4]"));
+    }
+}
@@ -0,0 +1,9 @@
+/// The cases are compiled in a project trybuild generates, so they find the fixture package through an absolute path
+#[test]
+fn ui() {
+    std::env::set_var("SKYSL_DERIVE_TESTS", concat!(env!("CARGO_MANIFEST_DIR"), "/tests"));
+
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass.rs");
+    cases.compile_fail("tests/ui/fail_*.rs");
+}
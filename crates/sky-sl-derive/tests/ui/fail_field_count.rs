@@ -0,0 +1,19 @@
+use sky_sl_derive::SkyslLayout;
+
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "lights::Light")]
+#[repr(C)]
+struct Light {
+    color: [f32; 3],
+}
+
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "lights::Light")]
+#[repr(C)]
+struct ExtraLight {
+    color: [f32; 3],
+    intensity: f32,
+    range: f32,
+}
+
+fn main() {}
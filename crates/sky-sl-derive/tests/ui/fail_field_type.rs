@@ -0,0 +1,11 @@
+use sky_sl_derive::SkyslLayout;
+
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "lights::Light")]
+#[repr(C)]
+struct Light {
+    color: [f32; 4],
+    intensity: f64,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use sky_sl_derive::SkyslLayout;
+
+// `b` is aligned to 8 bytes in std430
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "Pair", rules = "std430")]
+#[repr(C)]
+struct Pair {
+    a: f32,
+    b: [f32; 2],
+    _pad0: [u8; 4],
+}
+
+fn main() {}
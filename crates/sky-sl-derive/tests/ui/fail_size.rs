@@ -0,0 +1,12 @@
+use sky_sl_derive::SkyslLayout;
+
+// std140 rounds the size of structs up to 16 bytes
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "Camera")]
+#[repr(C)]
+struct Camera {
+    view: [[f32; 4]; 4],
+    exposure: f32,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use sky_sl_derive::SkyslLayout;
+
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "lights::Light")]
+#[repr(C)]
+struct Light {
+    color: [f32; 3],
+    intensity: f32,
+}
+
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "Camera", rules = "std140")]
+#[repr(C)]
+struct Camera {
+    view: [[f32; 4]; 4],
+    exposure: f32,
+    _pad0: [u8; 12],
+}
+
+#[derive(SkyslLayout)]
+#[skysl(package = concat!(env!("SKYSL_DERIVE_TESTS"), "/shaders"), path = "Pair", rules = "std430")]
+#[repr(C)]
+struct Pair {
+    a: f32,
+    _pad0: [u8; 4],
+    b: [f32; 2],
+}
+
+fn main() {}
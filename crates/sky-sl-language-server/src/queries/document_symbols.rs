@@ -74,11 +74,15 @@ fn kind_to_lsp(kind: sky_sl::hir::symbol::SymbolKind) -> tower_lsp::lsp_types::S
         sky_sl::hir::symbol::SymbolKind::Struct => tower_lsp::lsp_types::SymbolKind::Struct,
         sky_sl::hir::symbol::SymbolKind::Value => tower_lsp::lsp_types::SymbolKind::Variable,
         sky_sl::hir::symbol::SymbolKind::Layout => tower_lsp::lsp_types::SymbolKind::Struct,
+        sky_sl::hir::symbol::SymbolKind::Enum => tower_lsp::lsp_types::SymbolKind::Enum,
+        sky_sl::hir::symbol::SymbolKind::TypeAlias => tower_lsp::lsp_types::SymbolKind::TypeParameter,
     }
 }
 
 fn member_kind_to_lsp(kind: sky_sl::hir::symbol::SymbolMemberKind) -> tower_lsp::lsp_types::SymbolKind {
     match kind {
         sky_sl::hir::symbol::SymbolMemberKind::Field => tower_lsp::lsp_types::SymbolKind::Field,
+        sky_sl::hir::symbol::SymbolMemberKind::Variant => tower_lsp::lsp_types::SymbolKind::EnumMember,
+        sky_sl::hir::symbol::SymbolMemberKind::Method => tower_lsp::lsp_types::SymbolKind::Method,
     }
 }
@@ -1,36 +1,88 @@
 use sky_sl::{
-    hir::{primitive::Primitive, symbol::Symbol, untyped::*},
+    hir::{intrinsic::Intrinsic, primitive::Primitive, symbol::Symbol, typed, untyped::*},
     syn::cst::LineIndex,
-    text::Locate,
+    text::{Locate, TextSize},
     workspace::Workspace,
 };
 use tower_lsp::lsp_types::*;
 
 pub fn hover(
     hir: Module,
+    typed_hir: typed::Module,
     position: Position,
     line_index: LineIndex,
     workspace: &Workspace,
 ) -> Option<Hover> {
     let offset = line_index.find_offset(position.line, position.character);
 
+    if let Some(hover) = hover_layout_member(&hir, &typed_hir, offset, workspace) {
+        return Some(hover);
+    }
+
     match hir.locate(offset)? {
         sky_sl::hir::named::NamedItemKind::Symbol(symbol) => Some(hover_symbol(&symbol, workspace)),
         sky_sl::hir::named::NamedItemKind::Primitive(primitive) => {
             Some(hover_primitive(&primitive, workspace))
         }
+        sky_sl::hir::named::NamedItemKind::Intrinsic(intrinsic) => {
+            Some(hover_intrinsic(&intrinsic, workspace))
+        }
     }
 }
 
+/// Shows the binding of a layout member, unless the offset is in its type
+fn hover_layout_member(hir: &Module, typed_hir: &typed::Module, offset: TextSize, workspace: &Workspace) -> Option<Hover> {
+    let member = hir.items.iter()
+        .filter_map(|item| match item {
+            ItemKind::Layout(layout) => Some(layout),
+            _ => None,
+        })
+        .flat_map(|layout| layout.members.iter())
+        .find(|member| member.span.contains(offset) && member.type_reference.locate(offset).is_none())?;
+
+    let member = typed_hir.items.iter()
+        .filter_map(|item| match item {
+            typed::ItemKind::Layout(layout) => Some(layout),
+            _ => None,
+        })
+        .flat_map(|layout| layout.members.iter())
+        .find(|typed_member| typed_member.span == member.span)?;
+
+    let mut binding = match (member.binding_index, member.automatic_binding) {
+        _ if member.binding_kind == BindingKind::PushConstant => "push constant".to_string(),
+        _ if member.binding_kind == BindingKind::Workgroup => "workgroup memory".to_string(),
+        (Some(index), true) => format!("{} binding {} (assigned automatically)", member.binding_kind.keyword(), index),
+        (Some(index), false) => format!("{} binding {}", member.binding_kind.keyword(), index),
+        (None, _) => format!("{} binding", member.binding_kind.keyword()),
+    };
+
+    if let Some(access) = member.access {
+        binding.push_str(&format!(", {}", access.name()));
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("## {}\r\n{}", workspace.interned_name(member.name), binding),
+        }),
+        range: None,
+    })
+}
+
 fn hover_symbol(symbol: &Symbol, workspace: &Workspace) -> Hover {
     let name = workspace.interned_name(symbol.name);
 
     let kind = match symbol.kind {
-        sky_sl::hir::symbol::SymbolKind::Module => "module",
-        sky_sl::hir::symbol::SymbolKind::Function => "function",
-        sky_sl::hir::symbol::SymbolKind::Struct => "struct",
-        sky_sl::hir::symbol::SymbolKind::Value => "value",
-        sky_sl::hir::symbol::SymbolKind::Layout => "layout",
+        sky_sl::hir::symbol::SymbolKind::Module => "module".to_string(),
+        sky_sl::hir::symbol::SymbolKind::Function => "function".to_string(),
+        sky_sl::hir::symbol::SymbolKind::Struct => "struct".to_string(),
+        sky_sl::hir::symbol::SymbolKind::Value => "value".to_string(),
+        sky_sl::hir::symbol::SymbolKind::Layout => "layout".to_string(),
+        sky_sl::hir::symbol::SymbolKind::Enum => "enum".to_string(),
+        sky_sl::hir::symbol::SymbolKind::TypeAlias => match workspace.display_type_alias(symbol) {
+            Some(ty) => format!("type alias for `{}`", ty),
+            None => "type alias".to_string(),
+        },
     };
 
     Hover {
@@ -46,9 +98,25 @@ fn hover_primitive(primitive: &Primitive, workspace: &Workspace) -> Hover {
     let name = workspace.interned_name(primitive.name);
 
     let kind = match primitive.kind {
-        sky_sl::hir::primitive::PrimitiveKind::Boolean => "bool",
-        sky_sl::hir::primitive::PrimitiveKind::Integer => "int",
-        sky_sl::hir::primitive::PrimitiveKind::FloatingPoint => "float",
+        sky_sl::hir::primitive::PrimitiveKind::Boolean => "bool".to_string(),
+        sky_sl::hir::primitive::PrimitiveKind::Integer { signed: true, width } => format!("{} bit signed integer", width),
+        sky_sl::hir::primitive::PrimitiveKind::Integer { signed: false, width } => format!("{} bit unsigned integer", width),
+        sky_sl::hir::primitive::PrimitiveKind::FloatingPoint { width } => format!("{} bit floating point", width),
+        sky_sl::hir::primitive::PrimitiveKind::Vector { components } => format!("vector with {} components", components),
+        sky_sl::hir::primitive::PrimitiveKind::Matrix { columns, rows } => format!("matrix with {} columns and {} rows", columns, rows),
+        sky_sl::hir::primitive::PrimitiveKind::Texture { dimension, arrayed, class } => {
+            let class = match class {
+                sky_sl::hir::primitive::TextureClass::Sampled => "sampled",
+                sky_sl::hir::primitive::TextureClass::Depth => "depth",
+                sky_sl::hir::primitive::TextureClass::Storage => "storage",
+            };
+            let array = if arrayed { " array" } else { "" };
+            format!("{} {} texture{}", class, dimension.name(), array)
+        }
+        sky_sl::hir::primitive::PrimitiveKind::TexelFormat(_) => "texel format".to_string(),
+        sky_sl::hir::primitive::PrimitiveKind::Sampler { comparison: false } => "sampler".to_string(),
+        sky_sl::hir::primitive::PrimitiveKind::Sampler { comparison: true } => "comparison sampler".to_string(),
+        sky_sl::hir::primitive::PrimitiveKind::Atomic => "atomic integer".to_string(),
     };
 
     Hover {
@@ -59,3 +127,16 @@ fn hover_primitive(primitive: &Primitive, workspace: &Workspace) -> Hover {
         range: None,
     }
 }
+
+fn hover_intrinsic(intrinsic: &Intrinsic, workspace: &Workspace) -> Hover {
+    let name = workspace.interned_name(intrinsic.name);
+    let signatures = intrinsic.signatures(&name).join("\r\n");
+
+    Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("## {}\r\nbuilt-in function\r\n```\r\n{}\r\n```", name, signatures),
+        }),
+        range: None,
+    }
+}
@@ -1,23 +1,38 @@
 use sky_sl::syn::cst::*;
+use std::collections::HashSet;
 use tower_lsp::lsp_types::*;
 
 pub struct SemanticTokensBuilder<'a> {
     line_index: &'a LineIndex,
+    primitives: &'a HashSet<String>,
+    intrinsics: &'a HashSet<String>,
     line: u32,
     offset: u32,
     tokens: Vec<SemanticToken>,
 }
 
 impl<'a> SemanticTokensBuilder<'a> {
-    pub fn new(line_index: &'a LineIndex) -> Self {
+    pub fn new(line_index: &'a LineIndex, primitives: &'a HashSet<String>, intrinsics: &'a HashSet<String>) -> Self {
         Self {
             line_index,
+            primitives,
+            intrinsics,
             line: 0,
             offset: 0,
             tokens: Vec::new(),
         }
     }
 
+    /// Returns true if the name refers to a built-in type like `f32` or `vec3`
+    pub fn is_primitive(&self, name: &str) -> bool {
+        self.primitives.contains(name)
+    }
+
+    /// Returns true if the name refers to a built-in function like `sample`
+    pub fn is_intrinsic(&self, name: &str) -> bool {
+        self.intrinsics.contains(name)
+    }
+
     pub fn build_token(&mut self, range: TextRange, token_type: u32, token_modifiers_bitset: u32) {
         let range = self.line_index.find_range(range);
         let delta_line = range.start.line - self.line;
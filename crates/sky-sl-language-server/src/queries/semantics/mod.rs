@@ -1,5 +1,6 @@
 use tower_lsp::lsp_types::*;
 use sky_sl::syn::{ast::*, cst::*};
+use std::collections::HashSet;
 
 mod builder;
 pub mod token;
@@ -16,8 +17,8 @@ pub fn get_legend() -> SemanticTokensLegend {
     }
 }
 
-pub fn get_semantic_tokens(root: Root, line_index: &LineIndex) -> SemanticTokens {
-    let mut builder = SemanticTokensBuilder::new(line_index);
+pub fn get_semantic_tokens(root: Root, line_index: &LineIndex, primitives: &HashSet<String>, intrinsics: &HashSet<String>) -> SemanticTokens {
+    let mut builder = SemanticTokensBuilder::new(line_index, primitives, intrinsics);
 
     visit_root(root, &mut builder);
 
@@ -45,6 +46,52 @@ fn visit_root(root: Root, builder: &mut SemanticTokensBuilder) {
             ModuleItemKind::LayoutDefinition(layout_definition) => {
                 visit_layout_definition(layout_definition, builder);
             },
+            ModuleItemKind::ConstDefinition(const_definition) => {
+                visit_const_definition(const_definition, builder);
+            },
+            ModuleItemKind::EnumDefinition(enum_definition) => {
+                visit_enum_definition(enum_definition, builder);
+            },
+            ModuleItemKind::ImplDefinition(impl_definition) => {
+                visit_impl_definition(impl_definition, builder);
+            },
+            ModuleItemKind::TypeAliasDefinition(type_alias_definition) => {
+                visit_type_alias_definition(type_alias_definition, builder);
+            },
+        }
+    }
+}
+
+/// Highlights the visibility of an item or member and the keyword it starts with, e.g. `pub(package) fn`,
+/// as well as the stage of an entry point, e.g. `stage(vertex) fn`, and the location and interpolation of a member
+fn visit_keywords(syntax: &SyntaxNode, builder: &mut SemanticTokensBuilder) {
+    for element in syntax.children_with_tokens() {
+        match element {
+            SyntaxElement::Node(node) if node.kind() == SyntaxKind::Visibility => {
+                for token in node.children_with_tokens().filter_map(|element| element.into_token()) {
+                    if matches!(token.kind(), SyntaxKind::PubKeyword | SyntaxKind::PackageKeyword) {
+                        builder.build_token(token.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+                    }
+                }
+            },
+            SyntaxElement::Node(node) if matches!(node.kind(), SyntaxKind::Stage | SyntaxKind::Interpolation) => {
+                for token in node.children_with_tokens().filter_map(|element| element.into_token()) {
+                    if token.kind().is_keyword() || token.kind() == SyntaxKind::Identifier {
+                        builder.build_token(token.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+                    }
+                }
+            },
+            SyntaxElement::Node(node) if node.kind() == SyntaxKind::Location => {
+                if let Some(keyword) = node.first_token() {
+                    builder.build_token(keyword.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+                }
+            },
+            SyntaxElement::Token(token) if token.kind().is_whitespace() => {},
+            SyntaxElement::Token(token) if token.kind().is_keyword() => {
+                builder.build_token(token.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+                break;
+            },
+            _ => break,
         }
     }
 }
@@ -63,9 +110,7 @@ fn visit_use_declaration(use_declaration: UseDeclaration, builder: &mut Semantic
 
 fn visit_function_definition(function_definition: FunctionDefinition, builder: &mut SemanticTokensBuilder) {
     if let Some(signature) = function_definition.signature() {
-        if let Some(keyword) = function_definition.syntax().first_token() {
-            builder.build_token(keyword.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
-        }
+        visit_keywords(function_definition.syntax(), builder);
     
         if let Some(name) = signature.name() {
             let syntax = name.syntax();
@@ -77,8 +122,8 @@ fn visit_function_definition(function_definition: FunctionDefinition, builder: &
         }
     
         if let Some(return_type) = signature.return_type() {
-            if let Some(type_path) = return_type.path() {
-                visit_type_path(type_path, builder);
+            if let Some(ty) = return_type.ty() {
+                visit_type(ty, builder);
             }
         }
     }
@@ -94,17 +139,47 @@ fn visit_argument_list(argument_list: ArgumentList, builder: &mut SemanticTokens
             builder.build_token(name.syntax().text_range(), *TokenIndex::PARAMETER, *ModifierIndex::DECLARATION);
         }
 
-        if let Some(type_path) = argument.path() {
-            visit_type_path(type_path, builder);
+        if let Some(ty) = argument.ty() {
+            visit_type(ty, builder);
         }
     }
 }
 
-fn visit_struct_definition(struct_definition: StructDefinition, builder: &mut SemanticTokensBuilder) {
-    if let Some(keyword) = struct_definition.syntax().first_token() {
-        builder.build_token(keyword.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+fn visit_const_definition(const_definition: ConstDefinition, builder: &mut SemanticTokensBuilder) {
+    visit_keywords(const_definition.syntax(), builder);
+
+    if let Some(expression) = const_definition.override_id().and_then(|override_id| override_id.expression()) {
+        visit_expression(expression, builder);
+    }
+
+    if let Some(name) = const_definition.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::VARIABLE, *ModifierIndex::DECLARATION | *ModifierIndex::READONLY);
+    }
+
+    if let Some(ty) = const_definition.type_identifier().and_then(|type_identifier| type_identifier.ty()) {
+        visit_type(ty, builder);
+    }
+
+    if let Some(expression) = const_definition.expression() {
+        visit_expression(expression, builder);
+    }
+}
+
+fn visit_type_alias_definition(type_alias_definition: TypeAliasDefinition, builder: &mut SemanticTokensBuilder) {
+    visit_keywords(type_alias_definition.syntax(), builder);
+
+    if let Some(name) = type_alias_definition.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::TYPE, *ModifierIndex::DECLARATION);
     }
 
+    if let Some(ty) = type_alias_definition.ty() {
+        visit_type(ty, builder);
+    }
+}
+
+fn visit_struct_definition(struct_definition: StructDefinition, builder: &mut SemanticTokensBuilder) {
+    visit_keywords(struct_definition.syntax(), builder);
+
     if let Some(name) = struct_definition.name() {
         let syntax = name.syntax();
         builder.build_token(syntax.text_range(), *TokenIndex::STRUCT, *ModifierIndex::NONE);
@@ -117,14 +192,52 @@ fn visit_struct_definition(struct_definition: StructDefinition, builder: &mut Se
     }
 }
 
+fn visit_impl_definition(impl_definition: ImplDefinition, builder: &mut SemanticTokensBuilder) {
+    if let Some(keyword) = impl_definition.syntax().first_token() {
+        builder.build_token(keyword.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+    }
+
+    if let Some(name) = impl_definition.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::STRUCT, *ModifierIndex::NONE);
+    }
+
+    if let Some(item_list) = impl_definition.item_list() {
+        for function_definition in item_list.functions() {
+            visit_function_definition(function_definition, builder);
+        }
+    }
+}
+
+fn visit_enum_definition(enum_definition: EnumDefinition, builder: &mut SemanticTokensBuilder) {
+    visit_keywords(enum_definition.syntax(), builder);
+
+    if let Some(name) = enum_definition.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::ENUM, *ModifierIndex::DECLARATION);
+    }
+
+    if let Some(variant_list) = enum_definition.variant_list() {
+        for variant in variant_list.variants() {
+            if let Some(name) = variant.name() {
+                builder.build_token(name.syntax().text_range(), *TokenIndex::ENUM_MEMBER, *ModifierIndex::DECLARATION);
+            }
+
+            if let Some(expression) = variant.discriminant().and_then(|discriminant| discriminant.expression()) {
+                visit_expression(expression, builder);
+            }
+        }
+    }
+}
+
 fn visit_struct_member(member: Member, builder: &mut SemanticTokensBuilder) {
+    visit_keywords(member.syntax(), builder);
+
     if let Some(name) = member.name() {
         let syntax = name.syntax();
         builder.build_token(syntax.text_range(), *TokenIndex::PROPERTY, *ModifierIndex::NONE);
     }
 
-    if let Some(type_path) = member.path() {
-        visit_type_path(type_path, builder);
+    if let Some(ty) = member.ty() {
+        visit_type(ty, builder);
     }
 }
 
@@ -138,6 +251,7 @@ fn visit_statement(statement: Statement, builder: &mut SemanticTokensBuilder) {
     match statement.kind() {
         StatementKind::Let(let_statement) => visit_let_statement(let_statement, builder),
         StatementKind::Expression(expression_statement) => visit_expression_statement(expression_statement, builder),
+        StatementKind::Assign(assign_statement) => visit_assign_statement(assign_statement, builder),
     }
 }
 
@@ -146,8 +260,12 @@ fn visit_let_statement(let_statement: LetStatement, builder: &mut SemanticTokens
         builder.build_token(token.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
     }
 
-    if let Some(identifier) = let_statement.identifier() {
-        builder.build_token(identifier.syntax().text_range(), *TokenIndex::VARIABLE, *ModifierIndex::DEFINITION);
+    if let Some(name) = let_statement.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::VARIABLE, *ModifierIndex::DEFINITION);
+    }
+
+    if let Some(ty) = let_statement.type_identifier().and_then(|type_identifier| type_identifier.ty()) {
+        visit_type(ty, builder);
     }
 
     if let Some(expression) = let_statement.expression() {
@@ -161,16 +279,58 @@ fn visit_expression_statement(expression_statement: ExpressionStatement, builder
     }
 }
 
+fn visit_assign_statement(assign_statement: AssignStatement, builder: &mut SemanticTokensBuilder) {
+    if let Some(place) = assign_statement.place() {
+        visit_expression(place, builder);
+    }
+
+    if let Some(value) = assign_statement.value() {
+        visit_expression(value, builder);
+    }
+}
+
 fn visit_expression(expression: Expression, builder: &mut SemanticTokensBuilder) {
     match expression.kind() {
         ExpressionKind::LiteralExpression(literal_expression) => visit_literal_expression(literal_expression, builder),
         ExpressionKind::GroupExpression(group_expression) => visit_group_expression(group_expression, builder),
         ExpressionKind::BinaryExpression(binary_expression) => visit_binary_expression(binary_expression, builder),
+        ExpressionKind::UnaryExpression(unary_expression) => visit_unary_expression(unary_expression, builder),
         ExpressionKind::CallExpression(call_expression) => visit_call_expression(call_expression, builder),
         ExpressionKind::IndexExpression(index_expression) => visit_index_expression(index_expression, builder),
         ExpressionKind::FieldAccessExpression(field_access_expression) => visit_field_access_expression(field_access_expression, builder),
+        ExpressionKind::MethodCallExpression(method_call_expression) => visit_method_call_expression(method_call_expression, builder),
         ExpressionKind::PathExpression(path_expression) => visit_path_expression(path_expression, builder),
         ExpressionKind::StructExpression(struct_expression) => visit_struct_expression(struct_expression, builder),
+        ExpressionKind::BlockExpression(block_expression) => {
+            if let Some(block) = block_expression.block_definition() {
+                visit_block(block, builder);
+            }
+        },
+        ExpressionKind::MatchExpression(match_expression) => visit_match_expression(match_expression, builder),
+    }
+}
+
+fn visit_match_expression(match_expression: MatchExpression, builder: &mut SemanticTokensBuilder) {
+    if let Some(keyword) = match_expression.syntax().first_token() {
+        builder.build_token(keyword.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+    }
+
+    if let Some(scrutinee) = match_expression.scrutinee() {
+        visit_expression(scrutinee, builder);
+    }
+
+    for arm in match_expression.arms() {
+        for pattern in arm.patterns() {
+            match pattern.kind() {
+                PatternKind::Literal(_) => builder.build_token(pattern.syntax().text_range(), *TokenIndex::NUMBER, *ModifierIndex::NONE),
+                PatternKind::Path(_) => builder.build_token(pattern.syntax().text_range(), *TokenIndex::ENUM_MEMBER, *ModifierIndex::NONE),
+                PatternKind::Wildcard => {},
+            }
+        }
+
+        if let Some(expression) = arm.expression() {
+            visit_expression(expression, builder);
+        }
     }
 }
 
@@ -190,8 +350,23 @@ fn visit_binary_expression(binary_expression: BinaryExpression, builder: &mut Se
     }
 }
 
+fn visit_unary_expression(unary_expression: UnaryExpression, builder: &mut SemanticTokensBuilder) {
+    if let Some(child) = unary_expression.expression() {
+        visit_expression(child, builder);
+    }
+}
+
 fn visit_path_expression(path_expression: PathExpression, builder: &mut SemanticTokensBuilder) {
-    builder.build_token(path_expression.syntax().text_range(), *TokenIndex::VARIABLE, *ModifierIndex::NONE);
+    let range = path_expression.syntax().text_range();
+
+    let name = path_expression.syntax().to_string();
+    if builder.is_primitive(&name) {
+        builder.build_token(range, *TokenIndex::TYPE, *ModifierIndex::DEFAULT_LIBRARY);
+    } else if builder.is_intrinsic(&name) {
+        builder.build_token(range, *TokenIndex::FUNCTION, *ModifierIndex::DEFAULT_LIBRARY);
+    } else {
+        builder.build_token(range, *TokenIndex::VARIABLE, *ModifierIndex::NONE);
+    }
 }
 
 fn visit_call_expression(call_expression: CallExpression, builder: &mut SemanticTokensBuilder) {
@@ -200,22 +375,50 @@ fn visit_call_expression(call_expression: CallExpression, builder: &mut Semantic
     }
 
     if let Some(arguments) = call_expression.arguments() {
-        for child in arguments.expressions() {
-            visit_expression(child, builder);
+        for argument in arguments.call_arguments() {
+            if let Some(child) = argument.expression() {
+                visit_expression(child, builder);
+            }
         }
     }
 }
 
 fn visit_index_expression(index_expression: IndexExpression, builder: &mut SemanticTokensBuilder) {
-    for child in index_expression.expressions() {
+    if let Some(child) = index_expression.expression() {
+        visit_expression(child, builder);
+    }
+
+    if let Some(child) = index_expression.indexer().and_then(|indexer| indexer.expression()) {
         visit_expression(child, builder);
     }
 }
 
 fn visit_field_access_expression(field_access_expression: FieldAccessExpression, builder: &mut SemanticTokensBuilder) {
-    for child in field_access_expression.expressions() {
+    if let Some(child) = field_access_expression.expression() {
+        visit_expression(child, builder);
+    }
+
+    if let Some(name) = field_access_expression.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::PROPERTY, *ModifierIndex::NONE);
+    }
+}
+
+fn visit_method_call_expression(method_call_expression: MethodCallExpression, builder: &mut SemanticTokensBuilder) {
+    if let Some(child) = method_call_expression.expression() {
         visit_expression(child, builder);
     }
+
+    if let Some(name) = method_call_expression.name() {
+        builder.build_token(name.syntax().text_range(), *TokenIndex::FUNCTION, *ModifierIndex::NONE);
+    }
+
+    if let Some(arguments) = method_call_expression.arguments() {
+        for argument in arguments.call_arguments() {
+            if let Some(child) = argument.expression() {
+                visit_expression(child, builder);
+            }
+        }
+    }
 }
 
 fn visit_struct_expression(struct_expression: StructExpression, builder: &mut SemanticTokensBuilder) {
@@ -238,6 +441,21 @@ fn visit_struct_expression(struct_expression: StructExpression, builder: &mut Se
     }
 }
 
+fn visit_type(ty: Type, builder: &mut SemanticTokensBuilder) {
+    match ty.kind() {
+        TypeKind::Path(type_path) => visit_type_path(type_path, builder),
+        TypeKind::Array(array_type) => {
+            if let Some(element) = array_type.ty() {
+                visit_type(element, builder);
+            }
+
+            if let Some(length) = array_type.expression() {
+                visit_expression(length, builder);
+            }
+        },
+    }
+}
+
 fn visit_type_path(type_path: Path, builder: &mut SemanticTokensBuilder) {
     if let Some(segment) = type_path.segment() {
         visit_path_segment(segment, builder);
@@ -246,7 +464,19 @@ fn visit_type_path(type_path: Path, builder: &mut SemanticTokensBuilder) {
 
 fn visit_path_segment(segment: PathSegment, builder: &mut SemanticTokensBuilder) {
     if let Some(name) = segment.name() {
-        builder.build_token(name.syntax().text_range(), *TokenIndex::TYPE, *ModifierIndex::NONE);
+        let modifier = if builder.is_primitive(&name.syntax().to_string()) {
+            *ModifierIndex::DEFAULT_LIBRARY
+        } else {
+            *ModifierIndex::NONE
+        };
+
+        builder.build_token(name.syntax().text_range(), *TokenIndex::TYPE, modifier);
+    }
+
+    if let Some(generic_argument_list) = segment.generic_argument_list() {
+        for argument in generic_argument_list.paths() {
+            visit_type_path(argument, builder);
+        }
     }
 
     if let Some(next) = segment.segment() {
@@ -255,9 +485,7 @@ fn visit_path_segment(segment: PathSegment, builder: &mut SemanticTokensBuilder)
 }
 
 fn visit_layout_definition(layout_definition: LayoutDefinition, builder: &mut SemanticTokensBuilder) {
-    if let Some(keyword) = layout_definition.syntax().first_token() {
-        builder.build_token(keyword.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
-    }
+    visit_keywords(layout_definition.syntax(), builder);
 
     if let Some(name) = layout_definition.name() {
         let syntax = name.syntax();
@@ -286,7 +514,11 @@ fn visit_layout_member(layout_member: LayoutMember, builder: &mut SemanticTokens
         builder.build_token(binding_kind.syntax().text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
     }
 
-    if let Some(type_path) = layout_member.path() {
-        visit_type_path(type_path, builder);
+    if let Some(name) = layout_member.access_mode().and_then(|access_mode| access_mode.name()) {
+        builder.build_token(name.text_range(), *TokenIndex::KEYWORD, *ModifierIndex::NONE);
+    }
+
+    if let Some(ty) = layout_member.ty() {
+        visit_type(ty, builder);
     }
 }
@@ -17,6 +17,8 @@ impl TokenIndex {
     pub const TYPE: TokenIndex = TokenIndex::new(6, "type");
     pub const VARIABLE: TokenIndex = TokenIndex::new(7, "variable");
     pub const NUMBER: TokenIndex = TokenIndex::new(8, "number");
+    pub const ENUM: TokenIndex = TokenIndex::new(9, "enum");
+    pub const ENUM_MEMBER: TokenIndex = TokenIndex::new(10, "enumMember");
     // Don't forget to add it to Self::legend as well
 
     const fn new(index: u32, tag: &'static str) -> Self {
@@ -41,6 +43,8 @@ impl TokenIndex {
             Self::TYPE.token_type(),
             Self::VARIABLE.token_type(),
             Self::NUMBER.token_type(),
+            Self::ENUM.token_type(),
+            Self::ENUM_MEMBER.token_type(),
         ]
     }
 }
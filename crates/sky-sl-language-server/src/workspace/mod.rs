@@ -66,8 +66,9 @@ impl VSCodeWorkspaces {
             .map(|workspace| {
                 workspace.synchronize();
                 let hir = workspace.workspace.get_hir(&path).unwrap();
+                let typed_hir = workspace.workspace.get_typed_hir(&path).unwrap();
                 let line_index = workspace.workspace.get_line_index(&path).unwrap();
-                crate::queries::hover(hir, position, line_index, &workspace.workspace)
+                crate::queries::hover(hir, typed_hir, position, line_index, &workspace.workspace)
             }).flatten()
     }
 
@@ -91,7 +92,15 @@ impl VSCodeWorkspaces {
                 workspace.synchronize();
                 let ast = workspace.workspace.get_ast(&path).unwrap().tree();
                 let line_index = workspace.workspace.get_line_index(&path).unwrap();
-                let tokens = crate::queries::get_semantic_tokens(ast, &line_index);
+                let primitives = workspace.workspace.get_primitives()
+                    .iter()
+                    .map(|primitive| workspace.workspace.interned_name(primitive.name))
+                    .collect();
+                let intrinsics = workspace.workspace.get_intrinsics()
+                    .iter()
+                    .map(|intrinsic| workspace.workspace.interned_name(intrinsic.name))
+                    .collect();
+                let tokens = crate::queries::get_semantic_tokens(ast, &line_index, &primitives, &intrinsics);
                 SemanticTokensResult::Tokens(tokens)
             })
     }
@@ -0,0 +1,495 @@
+use crate::{
+    hir::{named::NamedItemKind, symbol::SymbolKind, type_check::ScalarTy, untyped, HirDatabase},
+    syn::cst::{BinaryOperator, Literal, LiteralSuffix, LiteralValue, UnaryOperator},
+};
+use rowan::TextRange;
+use thiserror::Error;
+
+/// A value computed at compile time, e.g. for a binding index
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConstValue {
+    Bool(bool),
+    Int { value: i128, signed: bool, width: u8 },
+    Float { value: f64, width: u8 },
+}
+
+// floats are only compared to decide if dependent queries need to be recomputed
+impl Eq for ConstValue {}
+
+impl ConstValue {
+    fn from_scalar(ty: ScalarTy, value: f64) -> Self {
+        match ty {
+            ScalarTy::Bool => ConstValue::Bool(value != 0.0),
+            ScalarTy::Int { signed, width } => ConstValue::Int { value: value as i128, signed, width },
+            ScalarTy::Float { width } => ConstValue::Float { value, width },
+        }
+    }
+
+    /// Converts a literal given outside of a shader, e.g. on the command line, to a value of `ty`.
+    /// Returns `None` if the literal has another type or is out of range.
+    pub fn from_literal(literal: Literal, negated: bool, ty: ScalarTy) -> Option<Self> {
+        let suffix_ty = match literal.suffix {
+            Some(LiteralSuffix::Int { signed, width }) => Some(ScalarTy::Int { signed, width }),
+            Some(LiteralSuffix::Float { width }) => Some(ScalarTy::Float { width }),
+            None => None,
+        };
+
+        if matches!(suffix_ty, Some(suffix_ty) if suffix_ty != ty) {
+            return None;
+        }
+
+        let value = match (literal.value, ty) {
+            (LiteralValue::Boolean(value), ScalarTy::Bool) => ConstValue::Bool(value),
+            (LiteralValue::Integer(value), ScalarTy::Int { signed, width }) => ConstValue::Int { value: value as i128, signed, width },
+            (LiteralValue::Integer(value), ScalarTy::Float { width }) => ConstValue::Float { value: value as f64, width },
+            (LiteralValue::Float(value), ScalarTy::Float { width }) => ConstValue::Float { value, width },
+            _ => return None,
+        };
+
+        let value = match negated {
+            true => eval_unary(UnaryOperator::FlipSign, value, TextRange::default()).ok()?,
+            false => value,
+        };
+
+        value.check(TextRange::default()).ok()
+    }
+
+    pub fn ty(self) -> ScalarTy {
+        match self {
+            ConstValue::Bool(_) => ScalarTy::Bool,
+            ConstValue::Int { signed, width, .. } => ScalarTy::Int { signed, width },
+            ConstValue::Float { width, .. } => ScalarTy::Float { width },
+        }
+    }
+
+    /// Returns the value if it is a non-negative integer that fits into 32 bits, e.g. for indices
+    pub fn as_u32(self) -> Option<u32> {
+        match self {
+            ConstValue::Int { value, .. } if value >= 0 && value <= u32::MAX as i128 => Some(value as u32),
+            _ => None,
+        }
+    }
+
+    pub fn display(self) -> String {
+        match self {
+            ConstValue::Bool(value) => value.to_string(),
+            ConstValue::Int { value, .. } => format!("{}{}", value, self.ty().display()),
+            ConstValue::Float { value, .. } => format!("{:?}{}", value, self.ty().display()),
+        }
+    }
+
+    /// Reports an overflow if the value cannot be represented by its type and NaN results, e.g. of `sqrt(-1.0)`
+    fn check(self, span: TextRange) -> Result<Self, ConstEvalError> {
+        if let ConstValue::Float { value, .. } = self {
+            if value.is_nan() {
+                return Err(ConstEvalError::NotANumber { ty: self.ty().display(), span });
+            }
+        }
+
+        let in_range = match self {
+            ConstValue::Bool(_) => true,
+            ConstValue::Int { value, signed: true, width } => {
+                let limit = 1i128 << (width - 1);
+                -limit <= value && value < limit
+            },
+            ConstValue::Int { value, signed: false, width } => 0 <= value && value < 1i128 << width,
+            ConstValue::Float { value, width: 16 } => value.abs() <= 65504.0,
+            ConstValue::Float { value, width: 32 } => value.abs() <= f32::MAX as f64,
+            ConstValue::Float { value, .. } => value.is_finite(),
+        };
+
+        if !in_range {
+            return Err(ConstEvalError::Overflow { ty: self.ty().display(), span });
+        }
+
+        // round to the precision of the type so folding gives the same result as the shader would
+        match self {
+            ConstValue::Float { value, width: 32 } => Ok(ConstValue::Float { value: value as f32 as f64, width: 32 }),
+            value => Ok(value),
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum ConstEvalError {
+    #[error("Expression cannot be evaluated at compile time")]
+    NotConstant { span: TextRange },
+
+    #[error("Division by zero in constant expression")]
+    DivisionByZero { span: TextRange },
+
+    #[error("Constant expression overflows `{ty}`")]
+    Overflow { ty: String, span: TextRange },
+
+    #[error("Constant expression of type `{ty}` is not a number")]
+    NotANumber { ty: String, span: TextRange },
+
+    #[error("Cannot apply `{operator}` to `{lhs}` and `{rhs}` in constant expression")]
+    InvalidOperands { operator: &'static str, lhs: String, rhs: String, span: TextRange },
+
+    #[error("Cannot apply `{operator}` to `{operand}` in constant expression")]
+    InvalidOperand { operator: &'static str, operand: String, span: TextRange },
+
+    #[error("`{name}` cannot be evaluated with arguments of type ({arguments})")]
+    InvalidArguments { name: String, arguments: String, span: TextRange },
+
+    #[error("`{name}` depends on its own value")]
+    Cycle { name: String, span: TextRange },
+
+    /// Refers to a constant with an error that is already reported where the constant is declared
+    #[error("`{name}` cannot be evaluated")]
+    InvalidConst { name: String, span: TextRange },
+
+    #[error("`{name}` can be overridden by the pipeline and cannot be used in constant expressions")]
+    Overridable { name: String, span: TextRange },
+}
+
+impl ConstEvalError {
+    pub fn span(&self) -> TextRange {
+        match self {
+            ConstEvalError::NotConstant { span } => *span,
+            ConstEvalError::DivisionByZero { span } => *span,
+            ConstEvalError::Overflow { span, .. } => *span,
+            ConstEvalError::NotANumber { span, .. } => *span,
+            ConstEvalError::InvalidOperands { span, .. } => *span,
+            ConstEvalError::InvalidOperand { span, .. } => *span,
+            ConstEvalError::InvalidArguments { span, .. } => *span,
+            ConstEvalError::Cycle { span, .. } => *span,
+            ConstEvalError::InvalidConst { span, .. } => *span,
+            ConstEvalError::Overridable { span, .. } => *span,
+        }
+    }
+}
+
+/// Evaluates an expression at compile time. Literals, constants, operators, scalar conversions and built-in math
+/// on scalars are folded, unsuffixed literals take the type of `expected` like in the type checker.
+pub fn eval_expression(db: &dyn HirDatabase, expression: &untyped::ExpressionKind, expected: Option<ScalarTy>) -> Result<ConstValue, ConstEvalError> {
+    match expression {
+        untyped::ExpressionKind::LiteralExpression(literal_expression) => {
+            eval_literal(literal_expression, expected).check(literal_expression.span)
+        },
+        untyped::ExpressionKind::GroupExpression(group_expression) => eval_expression(db, &group_expression.inner, expected),
+        untyped::ExpressionKind::UnaryExpression(unary_expression) => {
+            let operator = unary_expression.operator;
+            let span = unary_expression.span;
+
+            // the literal is checked after negating it so the smallest signed integer can be written, e.g. `-128i8`
+            let operand = match (operator, unary_expression.operand.as_ref()) {
+                (UnaryOperator::FlipSign, untyped::ExpressionKind::LiteralExpression(literal_expression)) => eval_literal(literal_expression, expected),
+                (_, operand) => eval_expression(db, operand, expected)?,
+            };
+
+            eval_unary(operator, operand, span)
+        },
+        untyped::ExpressionKind::BinaryExpression(binary_expression) => {
+            let (lhs, rhs) = if binary_expression.lhs.is_unsuffixed_literal() {
+                let rhs = eval_expression(db, &binary_expression.rhs, expected)?;
+                let lhs = eval_expression(db, &binary_expression.lhs, Some(rhs.ty()))?;
+                (lhs, rhs)
+            } else {
+                let lhs = eval_expression(db, &binary_expression.lhs, expected)?;
+                let rhs = eval_expression(db, &binary_expression.rhs, Some(lhs.ty()))?;
+                (lhs, rhs)
+            };
+
+            eval_binary(binary_expression.operator, lhs, rhs, binary_expression.span)
+        },
+        untyped::ExpressionKind::CallExpression(call_expression) => eval_call(db, call_expression, expected),
+        untyped::ExpressionKind::PathExpression(path_expression) => match &path_expression.item {
+            Some(NamedItemKind::Symbol(symbol)) if symbol.kind == SymbolKind::Value => {
+                let name = db.lookup_intern_name(symbol.name);
+                db.eval_const(symbol.file, symbol.name).map_err(|error| match error {
+                    ConstEvalError::Overridable { .. } => ConstEvalError::Overridable { name, span: path_expression.span },
+                    _ => ConstEvalError::InvalidConst { name, span: path_expression.span },
+                })
+            },
+            _ => Err(ConstEvalError::NotConstant { span: path_expression.span }),
+        },
+        expression => Err(ConstEvalError::NotConstant { span: expression.span() }),
+    }
+}
+
+/// Types a literal like the type checker does, without checking its range
+fn eval_literal(literal_expression: &untyped::LiteralExpression, expected: Option<ScalarTy>) -> ConstValue {
+    let ty = match (literal_expression.value, literal_expression.suffix) {
+        (LiteralValue::Boolean(_), _) => ScalarTy::Bool,
+        (_, Some(LiteralSuffix::Int { signed, width })) => ScalarTy::Int { signed, width },
+        (_, Some(LiteralSuffix::Float { width })) => ScalarTy::Float { width },
+        (LiteralValue::Integer(_), None) => match expected {
+            Some(ty @ ScalarTy::Int { .. }) => ty,
+            _ => ScalarTy::Int { signed: true, width: 32 },
+        },
+        (LiteralValue::Float(_), None) => match expected {
+            Some(ty @ ScalarTy::Float { .. }) => ty,
+            _ => ScalarTy::Float { width: 32 },
+        },
+    };
+
+    match (literal_expression.value, ty) {
+        (LiteralValue::Boolean(value), _) => ConstValue::Bool(value),
+        (LiteralValue::Integer(value), ScalarTy::Int { signed, width }) => ConstValue::Int { value: value as i128, signed, width },
+        (LiteralValue::Integer(value), _) => ConstValue::from_scalar(ty, value as f64),
+        (LiteralValue::Float(value), _) => ConstValue::from_scalar(ty, value),
+    }
+}
+
+fn eval_unary(operator: UnaryOperator, operand: ConstValue, span: TextRange) -> Result<ConstValue, ConstEvalError> {
+    let value = match (operator, operand) {
+        (UnaryOperator::BooleanNot, ConstValue::Bool(value)) => ConstValue::Bool(!value),
+        (UnaryOperator::BitwiseNot, ConstValue::Int { value, signed: true, width }) => ConstValue::Int { value: !value, signed: true, width },
+        (UnaryOperator::BitwiseNot, ConstValue::Int { value, signed: false, width }) => {
+            let mask = (1i128 << width) - 1;
+            ConstValue::Int { value: !value & mask, signed: false, width }
+        },
+        (UnaryOperator::KeepSign, ConstValue::Int { .. }) | (UnaryOperator::KeepSign, ConstValue::Float { .. }) => operand,
+        (UnaryOperator::FlipSign, ConstValue::Int { value, signed: true, width }) => ConstValue::Int { value: -value, signed: true, width },
+        (UnaryOperator::FlipSign, ConstValue::Float { value, width }) => ConstValue::Float { value: -value, width },
+        _ => {
+            return Err(ConstEvalError::InvalidOperand {
+                operator: operator.symbol(),
+                operand: operand.ty().display(),
+                span,
+            });
+        },
+    };
+
+    value.check(span)
+}
+
+fn eval_binary(operator: BinaryOperator, lhs: ConstValue, rhs: ConstValue, span: TextRange) -> Result<ConstValue, ConstEvalError> {
+    let invalid = || ConstEvalError::InvalidOperands {
+        operator: operator.symbol(),
+        lhs: lhs.ty().display(),
+        rhs: rhs.ty().display(),
+        span,
+    };
+    let overflow = || ConstEvalError::Overflow { ty: lhs.ty().display(), span };
+
+    if lhs.ty() != rhs.ty() {
+        return Err(invalid());
+    }
+
+    let value = match (lhs, rhs) {
+        (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => {
+            let value = match operator {
+                BinaryOperator::Equals => lhs == rhs,
+                BinaryOperator::NotEquals => lhs != rhs,
+                BinaryOperator::BitwiseAnd => lhs & rhs,
+                BinaryOperator::BitwiseOr => lhs | rhs,
+                BinaryOperator::BitwiseXOr => lhs ^ rhs,
+                _ => return Err(invalid()),
+            };
+
+            ConstValue::Bool(value)
+        },
+        (ConstValue::Int { value: lhs, signed, width }, ConstValue::Int { value: rhs, .. }) => {
+            let value = match operator {
+                BinaryOperator::Add => lhs + rhs,
+                BinaryOperator::Subtract => lhs - rhs,
+                BinaryOperator::Multiply => lhs.checked_mul(rhs).ok_or_else(overflow)?,
+                BinaryOperator::Divide | BinaryOperator::Remainder if rhs == 0 => return Err(ConstEvalError::DivisionByZero { span }),
+                BinaryOperator::Divide => lhs / rhs,
+                BinaryOperator::Remainder => lhs % rhs,
+                BinaryOperator::BitwiseAnd => lhs & rhs,
+                BinaryOperator::BitwiseOr => lhs | rhs,
+                BinaryOperator::BitwiseXOr => lhs ^ rhs,
+                BinaryOperator::LeftShift | BinaryOperator::RightShift if rhs < 0 || rhs >= width as i128 => {
+                    return Err(overflow());
+                },
+                BinaryOperator::LeftShift => lhs << rhs,
+                BinaryOperator::RightShift => lhs >> rhs,
+                BinaryOperator::Equals => return Ok(ConstValue::Bool(lhs == rhs)),
+                BinaryOperator::NotEquals => return Ok(ConstValue::Bool(lhs != rhs)),
+                BinaryOperator::GreaterThan => return Ok(ConstValue::Bool(lhs > rhs)),
+                BinaryOperator::GreatherOrEqualThan => return Ok(ConstValue::Bool(lhs >= rhs)),
+                BinaryOperator::LessThan => return Ok(ConstValue::Bool(lhs < rhs)),
+                BinaryOperator::LessOrEqualThan => return Ok(ConstValue::Bool(lhs <= rhs)),
+                BinaryOperator::Exponent => return Err(invalid()),
+            };
+
+            ConstValue::Int { value, signed, width }
+        },
+        (ConstValue::Float { value: lhs, width }, ConstValue::Float { value: rhs, .. }) => {
+            let value = match operator {
+                BinaryOperator::Add => lhs + rhs,
+                BinaryOperator::Subtract => lhs - rhs,
+                BinaryOperator::Multiply => lhs * rhs,
+                BinaryOperator::Divide | BinaryOperator::Remainder if rhs == 0.0 => return Err(ConstEvalError::DivisionByZero { span }),
+                BinaryOperator::Divide => lhs / rhs,
+                BinaryOperator::Remainder => lhs % rhs,
+                BinaryOperator::Exponent => lhs.powf(rhs),
+                BinaryOperator::Equals => return Ok(ConstValue::Bool(lhs == rhs)),
+                BinaryOperator::NotEquals => return Ok(ConstValue::Bool(lhs != rhs)),
+                BinaryOperator::GreaterThan => return Ok(ConstValue::Bool(lhs > rhs)),
+                BinaryOperator::GreatherOrEqualThan => return Ok(ConstValue::Bool(lhs >= rhs)),
+                BinaryOperator::LessThan => return Ok(ConstValue::Bool(lhs < rhs)),
+                BinaryOperator::LessOrEqualThan => return Ok(ConstValue::Bool(lhs <= rhs)),
+                _ => return Err(invalid()),
+            };
+
+            ConstValue::Float { value, width }
+        },
+        _ => return Err(invalid()),
+    };
+
+    value.check(span)
+}
+
+/// Folds scalar conversions like `u32(3)` and built-in math like `max(1, 2)`
+fn eval_call(db: &dyn HirDatabase, call_expression: &untyped::CallExpression, expected: Option<ScalarTy>) -> Result<ConstValue, ConstEvalError> {
+    let span = call_expression.span;
+    let item = match call_expression.callee.as_ref() {
+        untyped::ExpressionKind::PathExpression(path) => path.item.clone(),
+        _ => None,
+    };
+
+    // unsuffixed literals take the type of the other arguments, e.g. `1.0` in `max(x, 1.0)`
+    let mut arguments = Vec::new();
+    for argument in call_expression.arguments.iter().filter(|argument| !argument.is_unsuffixed_literal()) {
+        arguments.push((argument.span(), eval_expression(db, argument, None)?));
+    }
+
+    let element = arguments.first().map(|(_, argument)| argument.ty());
+    for argument in call_expression.arguments.iter().filter(|argument| argument.is_unsuffixed_literal()) {
+        arguments.push((argument.span(), eval_expression(db, argument, element.or(expected))?));
+    }
+
+    arguments.sort_by_key(|(span, _)| span.start());
+    let arguments: Vec<ConstValue> = arguments.into_iter().map(|(_, argument)| argument).collect();
+
+    let (name, value) = match item {
+        Some(NamedItemKind::Primitive(primitive)) => {
+            let name = db.lookup_intern_name(primitive.name);
+            let value = match (primitive.kind.scalar(), arguments.as_slice()) {
+                (Some(ty), [argument]) => convert(*argument, ty),
+                _ => None,
+            };
+            (name, value)
+        },
+        Some(NamedItemKind::Intrinsic(intrinsic)) => {
+            let name = db.lookup_intern_name(intrinsic.name);
+            let value = eval_intrinsic(&name, &arguments);
+            (name, value)
+        },
+        _ => return Err(ConstEvalError::NotConstant { span }),
+    };
+
+    match value {
+        Some(value) => value.check(span),
+        None => {
+            let arguments = arguments.iter().map(|argument| argument.ty().display()).collect::<Vec<_>>().join(", ");
+            Err(ConstEvalError::InvalidArguments { name, arguments, span })
+        },
+    }
+}
+
+/// Converts a scalar like the shader would, `None` for NaN converted to an integer or boolean
+fn convert(value: ConstValue, ty: ScalarTy) -> Option<ConstValue> {
+    let converted = match (value, ty) {
+        (ConstValue::Int { value, .. }, ScalarTy::Int { signed, width }) => ConstValue::Int { value, signed, width },
+        (ConstValue::Int { value, .. }, _) => ConstValue::from_scalar(ty, value as f64),
+        (ConstValue::Float { value, .. }, ScalarTy::Float { width }) => ConstValue::Float { value, width },
+        (ConstValue::Float { value, .. }, _) if value.is_nan() => return None,
+        (ConstValue::Float { value, .. }, ScalarTy::Bool) => ConstValue::Bool(value != 0.0),
+        (ConstValue::Float { value, .. }, _) => ConstValue::from_scalar(ty, value.trunc()),
+        (ConstValue::Bool(value), _) => ConstValue::from_scalar(ty, if value { 1.0 } else { 0.0 }),
+    };
+
+    Some(converted)
+}
+
+/// Built-in math on scalars, `None` if the function is not supported at compile time or the arguments do not match
+fn eval_intrinsic(name: &str, arguments: &[ConstValue]) -> Option<ConstValue> {
+    if arguments.iter().any(|argument| argument.ty() != arguments[0].ty()) {
+        return None;
+    }
+
+    let float = |function: fn(f64) -> f64| match arguments {
+        [ConstValue::Float { value, width }] => Some(ConstValue::Float { value: function(*value), width: *width }),
+        _ => None,
+    };
+
+    match name {
+        "sin" => float(f64::sin),
+        "cos" => float(f64::cos),
+        "tan" => float(f64::tan),
+        "asin" => float(f64::asin),
+        "acos" => float(f64::acos),
+        "atan" => float(f64::atan),
+        "sinh" => float(f64::sinh),
+        "cosh" => float(f64::cosh),
+        "tanh" => float(f64::tanh),
+        "exp" => float(f64::exp),
+        "exp2" => float(f64::exp2),
+        "log" => float(f64::ln),
+        "log2" => float(f64::log2),
+        "sqrt" => float(f64::sqrt),
+        "inverse_sqrt" => float(|x| 1.0 / x.sqrt()),
+        "floor" => float(f64::floor),
+        "ceil" => float(f64::ceil),
+        "round" => float(f64::round),
+        "trunc" => float(f64::trunc),
+        "fract" => float(|x| x - x.floor()),
+        "radians" => float(f64::to_radians),
+        "degrees" => float(f64::to_degrees),
+        "atan2" | "pow" | "step" => match arguments {
+            [ConstValue::Float { value: a, width }, ConstValue::Float { value: b, .. }] => {
+                let value = match name {
+                    "atan2" => a.atan2(*b),
+                    "pow" => a.powf(*b),
+                    _ => if b < a { 0.0 } else { 1.0 },
+                };
+                Some(ConstValue::Float { value, width: *width })
+            },
+            _ => None,
+        },
+        "abs" => match arguments {
+            [ConstValue::Int { value, signed, width }] => Some(ConstValue::Int { value: value.abs(), signed: *signed, width: *width }),
+            [ConstValue::Float { value, width }] => Some(ConstValue::Float { value: value.abs(), width: *width }),
+            _ => None,
+        },
+        "sign" => match arguments {
+            [ConstValue::Int { value, signed: true, width }] => Some(ConstValue::Int { value: value.signum(), signed: true, width: *width }),
+            [ConstValue::Float { value, width }] => {
+                let value = if *value == 0.0 { 0.0 } else { value.signum() };
+                Some(ConstValue::Float { value, width: *width })
+            },
+            _ => None,
+        },
+        "min" | "max" => match arguments {
+            [ConstValue::Int { value: a, signed, width }, ConstValue::Int { value: b, .. }] => {
+                let value = if name == "min" { *a.min(b) } else { *a.max(b) };
+                Some(ConstValue::Int { value, signed: *signed, width: *width })
+            },
+            [ConstValue::Float { value: a, width }, ConstValue::Float { value: b, .. }] => {
+                let value = if name == "min" { a.min(*b) } else { a.max(*b) };
+                Some(ConstValue::Float { value, width: *width })
+            },
+            _ => None,
+        },
+        "clamp" => match arguments {
+            [ConstValue::Int { value, signed, width }, ConstValue::Int { value: low, .. }, ConstValue::Int { value: high, .. }] => {
+                Some(ConstValue::Int { value: *value.max(low).min(high), signed: *signed, width: *width })
+            },
+            [ConstValue::Float { value, width }, ConstValue::Float { value: low, .. }, ConstValue::Float { value: high, .. }] => {
+                Some(ConstValue::Float { value: value.max(*low).min(*high), width: *width })
+            },
+            _ => None,
+        },
+        "mix" | "smoothstep" | "fma" => match arguments {
+            [ConstValue::Float { value: a, width }, ConstValue::Float { value: b, .. }, ConstValue::Float { value: c, .. }] => {
+                let value = match name {
+                    "mix" => a + (b - a) * c,
+                    "fma" => a * b + c,
+                    _ => {
+                        let t = ((c - a) / (b - a)).clamp(0.0, 1.0);
+                        t * t * (3.0 - 2.0 * t)
+                    },
+                };
+                Some(ConstValue::Float { value, width: *width })
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -1,18 +1,61 @@
 use super::{
+    const_eval::{eval_expression, ConstEvalError, ConstValue},
+    intrinsic::{AtomicFunction, GenericBound, IntrinsicKind, IntrinsicList, IntrinsicListBuilder, Overload, ScalarClass, ShapeBound, SignatureTy, TextureFunction},
     named::{NamedScope, NamedScopeBuilder},
-    primitive::{PrimitiveKind, PrimitiveList, PrimitiveListBuilder},
-    symbol::{find_symbols, SymbolList},
+    primitive::{PrimitiveKind, PrimitiveList, PrimitiveListBuilder, TexelFormat, TextureClass, TextureDimension},
+    symbol::{find_symbols, SymbolKind, SymbolList},
+    lower::{lower_const, lower_type_alias},
+    memory_layout::{lay_out_struct, recursive_struct_layout, LayoutRules, StructLayout},
+    pipeline::{match_stage_interface, merge_pipeline_layout, EntryPointId, PipelineLayout, StageInterface},
+    type_check::{declare_generic_parameters, infer_function, resolve_type, ArrayTy, ArrayTyData, Env, ScalarTy, StructTy, Ty, TypeCheckError},
+    typed, untyped,
 };
-use crate::{fs::FileId, syn::db::SyntaxDatabase};
+use crate::{fs::FileId, intern::Name, package::PackageDatabase, syn::{ast::{AstNode, ConstDefinitionOwner, TypeAliasDefinitionOwner}, db::SyntaxDatabase}};
 
 #[salsa::query_group(HirDatabaseStorage)]
-pub trait HirDatabase: SyntaxDatabase {
+pub trait HirDatabase: SyntaxDatabase + PackageDatabase {
     fn get_symbols(&self, file: FileId) -> SymbolList;
     fn get_hir(&self, file: FileId) -> super::untyped::Module;
     fn get_typed_hir(&self, file: FileId) -> super::typed::Module;
     fn get_primitives(&self) -> PrimitiveList;
+    fn get_intrinsics(&self) -> IntrinsicList;
     fn get_local_scope(&self, file: FileId) -> NamedScope;
     fn get_import_scope(&self, file: FileId) -> NamedScope;
+
+    /// Evaluates a constant, errors in its declaration are reported when type checking the declaring module
+    #[salsa::cycle(recover_const_cycle)]
+    fn eval_const(&self, file: FileId, name: Name) -> Result<ConstValue, ConstEvalError>;
+
+    /// Expands a type alias to the type it names, following aliases of aliases. Errors in the aliased type
+    /// are reported when type checking the declaring module and result in `Ty::Error`, only cycles are an `Err`
+    #[salsa::cycle(recover_type_alias_cycle)]
+    fn resolve_type_alias(&self, file: FileId, name: Name) -> Result<Ty, TypeCheckError>;
+
+    /// Computes the size, alignment and member offsets of a struct under the given layout rules
+    #[salsa::cycle(recover_struct_layout_cycle)]
+    fn struct_layout(&self, struct_ty: StructTy, rules: LayoutRules) -> StructLayout;
+
+    /// Evaluates the binding index of a layout member, `None` if the member has no binding index
+    fn eval_binding_index(&self, file: FileId, layout: Name, member: Name) -> Option<Result<ConstValue, ConstEvalError>>;
+
+    /// Evaluates the location of a struct member, `None` if the member has no location
+    fn eval_location(&self, file: FileId, structure: Name, member: Name) -> Option<Result<ConstValue, ConstEvalError>>;
+
+    /// Evaluates the set index of a layout, `None` if the layout has no explicit set index
+    fn eval_set_index(&self, file: FileId, layout: Name) -> Option<Result<ConstValue, ConstEvalError>>;
+
+    /// Merges the bindings of entry points sharing a pipeline and reports bindings they disagree on
+    fn pipeline_layout(&self, entry_points: Vec<EntryPointId>) -> PipelineLayout;
+
+    /// Matches the inputs of a fragment entry point with the outputs of a vertex entry point
+    fn stage_interface(&self, vertex: EntryPointId, fragment: EntryPointId) -> StageInterface;
+
+    /// Checks a generic function with concrete generic arguments, `None` if there is no such generic function
+    #[salsa::cycle(recover_instance_cycle)]
+    fn get_function_instance(&self, file: FileId, name: Name, generic_arguments: Vec<Ty>) -> Option<typed::FunctionInstance>;
+
+    #[salsa::interned]
+    fn intern_array_ty(&self, data: ArrayTyData) -> ArrayTy;
 }
 
 fn get_hir(db: &dyn HirDatabase, file: FileId) -> super::untyped::Module {
@@ -21,10 +64,165 @@ fn get_hir(db: &dyn HirDatabase, file: FileId) -> super::untyped::Module {
 }
 
 fn get_typed_hir(db: &dyn HirDatabase, file: FileId) -> super::typed::Module {
-    // let hir = db.get_hir(file);
-    // let mut env = super::type_check::Env::empty();
-    // super::type_check::infer_module(&hir, &mut env)
-    todo!()
+    let hir = db.get_hir(file);
+    let env = super::type_check::Env::new(db, file);
+    super::type_check::infer_module(&hir, env)
+}
+
+fn eval_const(db: &dyn HirDatabase, file: FileId, name: Name) -> Result<ConstValue, ConstEvalError> {
+    let symbol = db.get_symbols(file).find_by_name_and_kind(name, SymbolKind::Value);
+    let invalid = || ConstEvalError::InvalidConst {
+        name: db.lookup_intern_name(name),
+        span: symbol.as_ref().map(|symbol| symbol.selection_span).unwrap_or_default(),
+    };
+
+    // the constant is lowered on its own so it can be used while its module is lowered
+    let span = symbol.as_ref().map(|symbol| symbol.span).ok_or_else(invalid)?;
+    let ast = db.get_ast(file);
+    let const_definition = ast.tree().const_definitions()
+        .find(|const_definition| const_definition.syntax().text_range() == span)
+        .ok_or_else(invalid)?;
+    let const_kind = lower_const(file, db, const_definition).map_err(|_| invalid())?;
+
+    if const_kind.override_id.is_some() {
+        return Err(ConstEvalError::Overridable {
+            name: db.lookup_intern_name(name),
+            span: symbol.map(|symbol| symbol.selection_span).unwrap_or_default(),
+        });
+    }
+
+    let expected = match resolve_type(&const_kind.type_reference, &mut Env::new(db, file)) {
+        Ty::Scalar(scalar) => scalar,
+        _ => return Err(invalid()),
+    };
+
+    let value = eval_expression(db, &const_kind.expression, Some(expected))?;
+    if value.ty() != expected {
+        return Err(invalid());
+    }
+
+    Ok(value)
+}
+
+fn recover_const_cycle(db: &dyn HirDatabase, _cycle: &[String], file: &FileId, name: &Name) -> Result<ConstValue, ConstEvalError> {
+    let symbol = db.get_symbols(*file).find_by_name_and_kind(*name, SymbolKind::Value);
+    Err(ConstEvalError::Cycle {
+        name: db.lookup_intern_name(*name),
+        span: symbol.map(|symbol| symbol.selection_span).unwrap_or_default(),
+    })
+}
+
+fn resolve_type_alias(db: &dyn HirDatabase, file: FileId, name: Name) -> Result<Ty, TypeCheckError> {
+    let span = match db.get_symbols(file).find_by_name_and_kind(name, SymbolKind::TypeAlias) {
+        Some(symbol) => symbol.span,
+        None => return Ok(Ty::Error),
+    };
+
+    // the alias is lowered on its own so it can be used while its module is lowered
+    let ast = db.get_ast(file);
+    let type_alias = ast.tree().type_alias_definitions()
+        .find(|type_alias| type_alias.syntax().text_range() == span)
+        .and_then(|type_alias| lower_type_alias(file, db, type_alias).ok());
+
+    match type_alias {
+        Some(type_alias) => Ok(resolve_type(&type_alias.type_reference, &mut Env::new(db, file))),
+        None => Ok(Ty::Error),
+    }
+}
+
+fn recover_type_alias_cycle(db: &dyn HirDatabase, _cycle: &[String], file: &FileId, name: &Name) -> Result<Ty, TypeCheckError> {
+    let symbol = db.get_symbols(*file).find_by_name_and_kind(*name, SymbolKind::TypeAlias);
+    Err(TypeCheckError::RecursiveTypeAlias {
+        name: db.lookup_intern_name(*name),
+        span: symbol.map(|symbol| symbol.selection_span).unwrap_or_default(),
+    })
+}
+
+fn struct_layout(db: &dyn HirDatabase, struct_ty: StructTy, rules: LayoutRules) -> StructLayout {
+    lay_out_struct(db, struct_ty, rules)
+}
+
+fn recover_struct_layout_cycle(db: &dyn HirDatabase, _cycle: &[String], struct_ty: &StructTy, _rules: &LayoutRules) -> StructLayout {
+    recursive_struct_layout(db, *struct_ty)
+}
+
+fn get_function_instance(db: &dyn HirDatabase, file: FileId, name: Name, generic_arguments: Vec<Ty>) -> Option<typed::FunctionInstance> {
+    let module = db.get_hir(file);
+    let function = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Function(function) if function.signature.name == name => Some(function),
+        _ => None,
+    })?;
+
+    let parameters = &function.signature.generic_parameters;
+    if parameters.is_empty() || parameters.len() != generic_arguments.len() {
+        return None;
+    }
+
+    // errors the generic function already has are reported when checking its module, not for every instantiation
+    let mut generic_env = Env::new(db, file);
+    declare_generic_parameters(&function.signature, &mut generic_env);
+    infer_function(function, &mut generic_env);
+    let is_generic_valid = generic_env.error_count() == 0;
+
+    let mut env = Env::new(db, file);
+    env.set_generics(parameters.iter().map(|parameter| parameter.name).zip(generic_arguments).collect());
+    let instance = infer_function(function, &mut env);
+
+    let errors = if is_generic_valid { env.into_errors() } else { Vec::new() };
+    Some(typed::FunctionInstance::new(instance, errors))
+}
+
+/// A generic function instantiating itself is reported as a recursive call
+fn recover_instance_cycle(_db: &dyn HirDatabase, _cycle: &[String], _file: &FileId, _name: &Name, _generic_arguments: &[Ty]) -> Option<typed::FunctionInstance> {
+    None
+}
+
+fn eval_binding_index(db: &dyn HirDatabase, file: FileId, layout: Name, member: Name) -> Option<Result<ConstValue, ConstEvalError>> {
+    let module = db.get_hir(file);
+    let member = module.items.iter()
+        .find_map(|item| match item {
+            untyped::ItemKind::Layout(layout_kind) if layout_kind.name == layout => Some(layout_kind),
+            _ => None,
+        })?
+        .members.iter()
+        .find(|layout_member| layout_member.name == member)?;
+
+    let binding_index = member.binding_index.as_ref()?;
+    Some(eval_expression(db, binding_index, Some(ScalarTy::Int { signed: false, width: 32 })))
+}
+
+fn eval_location(db: &dyn HirDatabase, file: FileId, structure: Name, member: Name) -> Option<Result<ConstValue, ConstEvalError>> {
+    let module = db.get_hir(file);
+    let member = module.items.iter()
+        .find_map(|item| match item {
+            untyped::ItemKind::Struct(struct_kind) if struct_kind.name == structure => Some(struct_kind),
+            _ => None,
+        })?
+        .members.iter()
+        .find(|struct_member| struct_member.name == member)?;
+
+    let location = member.location.as_ref()?;
+    Some(eval_expression(db, location, Some(ScalarTy::Int { signed: false, width: 32 })))
+}
+
+fn pipeline_layout(db: &dyn HirDatabase, entry_points: Vec<EntryPointId>) -> PipelineLayout {
+    merge_pipeline_layout(db, &entry_points)
+}
+
+fn stage_interface(db: &dyn HirDatabase, vertex: EntryPointId, fragment: EntryPointId) -> StageInterface {
+    match_stage_interface(db, vertex, fragment)
+}
+
+fn eval_set_index(db: &dyn HirDatabase, file: FileId, layout: Name) -> Option<Result<ConstValue, ConstEvalError>> {
+    let module = db.get_hir(file);
+    let set_index = module.items.iter()
+        .find_map(|item| match item {
+            untyped::ItemKind::Layout(layout_kind) if layout_kind.name == layout => Some(layout_kind),
+            _ => None,
+        })?
+        .set_index.as_ref()?;
+
+    Some(eval_expression(db, set_index, Some(ScalarTy::Int { signed: false, width: 32 })))
 }
 
 fn get_symbols(db: &dyn HirDatabase, file: FileId) -> SymbolList {
@@ -37,19 +235,132 @@ fn get_primitives(db: &dyn HirDatabase) -> PrimitiveList {
 
     builder.add_primitive("bool", PrimitiveKind::Boolean);
 
-    builder.add_primitive("u8", PrimitiveKind::Integer);
-    builder.add_primitive("u16", PrimitiveKind::Integer);
-    builder.add_primitive("u32", PrimitiveKind::Integer);
-    builder.add_primitive("u64", PrimitiveKind::Integer);
+    builder.add_primitive("u8", PrimitiveKind::Integer { signed: false, width: 8 });
+    builder.add_primitive("u16", PrimitiveKind::Integer { signed: false, width: 16 });
+    builder.add_primitive("u32", PrimitiveKind::Integer { signed: false, width: 32 });
+    builder.add_primitive("u64", PrimitiveKind::Integer { signed: false, width: 64 });
+
+    builder.add_primitive("i8", PrimitiveKind::Integer { signed: true, width: 8 });
+    builder.add_primitive("i16", PrimitiveKind::Integer { signed: true, width: 16 });
+    builder.add_primitive("i32", PrimitiveKind::Integer { signed: true, width: 32 });
+    builder.add_primitive("i64", PrimitiveKind::Integer { signed: true, width: 64 });
+
+    builder.add_primitive("f16", PrimitiveKind::FloatingPoint { width: 16 });
+    builder.add_primitive("f32", PrimitiveKind::FloatingPoint { width: 32 });
+    builder.add_primitive("f64", PrimitiveKind::FloatingPoint { width: 64 });
+
+    for components in 2..=4 {
+        builder.add_primitive(&format!("vec{}", components), PrimitiveKind::Vector { components });
+    }
+
+    for columns in 2..=4 {
+        for rows in 2..=4 {
+            builder.add_primitive(&format!("mat{}x{}", columns, rows), PrimitiveKind::Matrix { columns, rows });
+        }
+    }
+
+    let textures = [
+        (TextureClass::Sampled, TextureDimension::D1, false),
+        (TextureClass::Sampled, TextureDimension::D2, false),
+        (TextureClass::Sampled, TextureDimension::D2, true),
+        (TextureClass::Sampled, TextureDimension::D3, false),
+        (TextureClass::Sampled, TextureDimension::Cube, false),
+        (TextureClass::Sampled, TextureDimension::Cube, true),
+        (TextureClass::Depth, TextureDimension::D2, false),
+        (TextureClass::Depth, TextureDimension::D2, true),
+        (TextureClass::Depth, TextureDimension::Cube, false),
+        (TextureClass::Depth, TextureDimension::Cube, true),
+        (TextureClass::Storage, TextureDimension::D1, false),
+        (TextureClass::Storage, TextureDimension::D2, false),
+        (TextureClass::Storage, TextureDimension::D2, true),
+        (TextureClass::Storage, TextureDimension::D3, false),
+    ];
+
+    for (class, dimension, arrayed) in textures.iter().copied() {
+        let suffix = if arrayed { "_array" } else { "" };
+        let name = format!("texture_{}{}{}", class.prefix(), dimension.name(), suffix);
+        builder.add_primitive(&name, PrimitiveKind::Texture { dimension, arrayed, class });
+    }
+
+    for format in TexelFormat::ALL.iter().copied() {
+        builder.add_primitive(format.name(), PrimitiveKind::TexelFormat(format));
+    }
+
+    // `sampler` is the binding keyword, so the types are named after their state
+    builder.add_primitive("sampler_state", PrimitiveKind::Sampler { comparison: false });
+    builder.add_primitive("sampler_comparison_state", PrimitiveKind::Sampler { comparison: true });
+
+    builder.add_primitive("atomic", PrimitiveKind::Atomic);
+
+    builder.build()
+}
+
+fn get_intrinsics(db: &dyn HirDatabase) -> IntrinsicList {
+    use SignatureTy::{Boolean as B, Element as S, Generic as T};
+
+    const FLOAT: GenericBound = GenericBound::new(ScalarClass::Float, ShapeBound::ScalarOrVector);
+    const FLOAT_VECTOR: GenericBound = GenericBound::new(ScalarClass::Float, ShapeBound::Vector);
+    const FLOAT_VECTOR3: GenericBound = GenericBound::new(ScalarClass::Float, ShapeBound::Vector3);
+    const SIGNED: GenericBound = GenericBound::new(ScalarClass::Signed, ShapeBound::ScalarOrVector);
+    const NUMERIC: GenericBound = GenericBound::new(ScalarClass::Numeric, ShapeBound::ScalarOrVector);
+    const NUMERIC_VECTOR: GenericBound = GenericBound::new(ScalarClass::Numeric, ShapeBound::Vector);
+    const BOOL_VECTOR: GenericBound = GenericBound::new(ScalarClass::Bool, ShapeBound::Vector);
+
+    let mut builder = IntrinsicListBuilder::new(db);
+
+    let unary_float = [
+        "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh",
+        "exp", "exp2", "log", "log2", "sqrt", "inverse_sqrt",
+        "floor", "ceil", "round", "trunc", "fract", "radians", "degrees",
+        "dpdx", "dpdy", "fwidth",
+    ];
+
+    for name in unary_float.iter() {
+        builder.add_overloaded(name, vec![Overload::new(FLOAT, &[("x", T)], T)]);
+    }
+
+    builder.add_overloaded("atan2", vec![Overload::new(FLOAT, &[("y", T), ("x", T)], T)]);
+    builder.add_overloaded("pow", vec![Overload::new(FLOAT, &[("base", T), ("exponent", T)], T)]);
+    builder.add_overloaded("step", vec![Overload::new(FLOAT, &[("edge", T), ("x", T)], T)]);
+    builder.add_overloaded("smoothstep", vec![Overload::new(FLOAT, &[("low", T), ("high", T), ("x", T)], T)]);
+    builder.add_overloaded("fma", vec![Overload::new(FLOAT, &[("a", T), ("b", T), ("c", T)], T)]);
+    builder.add_overloaded("mix", vec![
+        Overload::new(FLOAT, &[("a", T), ("b", T), ("t", T)], T),
+        Overload::new(FLOAT, &[("a", T), ("b", T), ("t", S)], T),
+    ]);
+
+    builder.add_overloaded("abs", vec![Overload::new(NUMERIC, &[("x", T)], T)]);
+    builder.add_overloaded("sign", vec![Overload::new(SIGNED, &[("x", T)], T)]);
+    builder.add_overloaded("min", vec![Overload::new(NUMERIC, &[("a", T), ("b", T)], T)]);
+    builder.add_overloaded("max", vec![Overload::new(NUMERIC, &[("a", T), ("b", T)], T)]);
+    builder.add_overloaded("clamp", vec![
+        Overload::new(NUMERIC, &[("x", T), ("low", T), ("high", T)], T),
+        Overload::new(NUMERIC, &[("x", T), ("low", S), ("high", S)], T),
+    ]);
+
+    builder.add_overloaded("length", vec![Overload::new(FLOAT, &[("x", T)], S)]);
+    builder.add_overloaded("distance", vec![Overload::new(FLOAT, &[("a", T), ("b", T)], S)]);
+    builder.add_overloaded("dot", vec![Overload::new(NUMERIC_VECTOR, &[("a", T), ("b", T)], S)]);
+    builder.add_overloaded("normalize", vec![Overload::new(FLOAT_VECTOR, &[("x", T)], T)]);
+    builder.add_overloaded("cross", vec![Overload::new(FLOAT_VECTOR3, &[("a", T), ("b", T)], T)]);
+    builder.add_overloaded("reflect", vec![Overload::new(FLOAT_VECTOR, &[("incident", T), ("normal", T)], T)]);
+    builder.add_overloaded("refract", vec![Overload::new(FLOAT_VECTOR, &[("incident", T), ("normal", T), ("eta", S)], T)]);
+
+    builder.add_overloaded("all", vec![Overload::new(BOOL_VECTOR, &[("x", T)], S)]);
+    builder.add_overloaded("any", vec![Overload::new(BOOL_VECTOR, &[("x", T)], S)]);
+    builder.add_overloaded("select", vec![Overload::new(NUMERIC, &[("a", T), ("b", T), ("condition", B)], T)]);
 
-    builder.add_primitive("i8", PrimitiveKind::Integer);
-    builder.add_primitive("i16", PrimitiveKind::Integer);
-    builder.add_primitive("i32", PrimitiveKind::Integer);
-    builder.add_primitive("i64", PrimitiveKind::Integer);
+    builder.add_intrinsic("sample", IntrinsicKind::Texture(TextureFunction::Sample));
+    builder.add_intrinsic("sample_level", IntrinsicKind::Texture(TextureFunction::SampleLevel));
+    builder.add_intrinsic("sample_compare", IntrinsicKind::Texture(TextureFunction::SampleCompare));
+    builder.add_intrinsic("sample_compare_level", IntrinsicKind::Texture(TextureFunction::SampleCompareLevel));
+    builder.add_intrinsic("load", IntrinsicKind::Texture(TextureFunction::Load));
+    builder.add_intrinsic("store", IntrinsicKind::Texture(TextureFunction::Store));
+    builder.add_intrinsic("dimensions", IntrinsicKind::Texture(TextureFunction::Dimensions));
 
-    builder.add_primitive("f16", PrimitiveKind::FloatingPoint);
-    builder.add_primitive("f32", PrimitiveKind::FloatingPoint);
-    builder.add_primitive("f64", PrimitiveKind::FloatingPoint);
+    for function in AtomicFunction::ALL.iter().copied() {
+        builder.add_intrinsic(function.name(), IntrinsicKind::Atomic(function));
+    }
 
     builder.build()
 }
@@ -58,6 +369,7 @@ fn get_local_scope(db: &dyn HirDatabase, file: FileId) -> NamedScope {
     let mut builder = NamedScopeBuilder::new(db);
 
     builder.add_primitives();
+    builder.add_intrinsics();
     builder.import_usings(file);
     builder.add_file_symbols(file);
 
@@ -0,0 +1,307 @@
+use crate::{hir::HirDatabase, intern::Name};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum IntrinsicKind {
+    /// A function on scalars and vectors like `clamp` or `dot`, described by its overloads
+    Overloaded(Vec<Overload>),
+
+    /// A function taking a texture as its first argument like `sample`
+    Texture(TextureFunction),
+
+    /// A function taking an atomic as its first argument like `atomic_add`
+    Atomic(AtomicFunction),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TextureFunction {
+    /// `sample(texture, sampler, coordinates)` samples a texture with implicit level of detail
+    Sample,
+
+    /// `sample_level(texture, sampler, coordinates, level)` samples a texture at an explicit mip level
+    SampleLevel,
+
+    /// `sample_compare(texture, sampler, coordinates, reference)` compares a depth texture with `reference` using a
+    /// comparison sampler and returns the filtered result
+    SampleCompare,
+
+    /// `sample_compare_level(texture, sampler, coordinates, reference)` compares with the first mip level only
+    SampleCompareLevel,
+
+    /// `load(texture, coordinates, level)` reads a single texel without a sampler
+    Load,
+
+    /// `store(texture, coordinates, value)` writes a single texel of a storage texture
+    Store,
+
+    /// `dimensions(texture)` returns the size of a texture in texels
+    Dimensions,
+}
+
+impl TextureFunction {
+    pub fn signature(self) -> &'static str {
+        match self {
+            TextureFunction::Sample => "fn sample(texture, sampler, coordinates, [array_index]) -> texel",
+            TextureFunction::SampleLevel => "fn sample_level(texture, sampler, coordinates, [array_index], level) -> texel",
+            TextureFunction::SampleCompare => "fn sample_compare(texture, sampler, coordinates, [array_index], reference) -> f32",
+            TextureFunction::SampleCompareLevel => "fn sample_compare_level(texture, sampler, coordinates, [array_index], reference) -> f32",
+            TextureFunction::Load => "fn load(texture, coordinates, [array_index], [level]) -> texel",
+            TextureFunction::Store => "fn store(texture, coordinates, [array_index], value)",
+            TextureFunction::Dimensions => "fn dimensions(texture) -> size",
+        }
+    }
+}
+
+/// The functions operating on an `atomic<u32>` or `atomic<i32>` in storage or workgroup memory. All but
+/// `atomic_load` write to the atomic, the read-modify-write functions return the value before the operation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AtomicFunction {
+    Load,
+    Store,
+    Add,
+    Sub,
+    Min,
+    Max,
+    And,
+    Or,
+    Xor,
+    Exchange,
+
+    /// `atomic_compare_exchange(atomic, comparator, value)` stores the value if the atomic equals the comparator
+    CompareExchange,
+}
+
+impl AtomicFunction {
+    pub const ALL: [AtomicFunction; 11] = [
+        AtomicFunction::Load,
+        AtomicFunction::Store,
+        AtomicFunction::Add,
+        AtomicFunction::Sub,
+        AtomicFunction::Min,
+        AtomicFunction::Max,
+        AtomicFunction::And,
+        AtomicFunction::Or,
+        AtomicFunction::Xor,
+        AtomicFunction::Exchange,
+        AtomicFunction::CompareExchange,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AtomicFunction::Load => "atomic_load",
+            AtomicFunction::Store => "atomic_store",
+            AtomicFunction::Add => "atomic_add",
+            AtomicFunction::Sub => "atomic_sub",
+            AtomicFunction::Min => "atomic_min",
+            AtomicFunction::Max => "atomic_max",
+            AtomicFunction::And => "atomic_and",
+            AtomicFunction::Or => "atomic_or",
+            AtomicFunction::Xor => "atomic_xor",
+            AtomicFunction::Exchange => "atomic_exchange",
+            AtomicFunction::CompareExchange => "atomic_compare_exchange",
+        }
+    }
+
+    pub fn writes(self) -> bool {
+        self != AtomicFunction::Load
+    }
+
+    pub fn signature(self) -> String {
+        match self {
+            AtomicFunction::Load => "fn atomic_load(atomic) -> T".to_string(),
+            AtomicFunction::Store => "fn atomic_store(atomic, value: T)".to_string(),
+            AtomicFunction::CompareExchange => "fn atomic_compare_exchange(atomic, comparator: T, value: T) -> T".to_string(),
+            function => format!("fn {}(atomic, value: T) -> T", function.name()),
+        }
+    }
+}
+
+/// The element types a generic type parameter accepts
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ScalarClass {
+    Float,
+
+    /// Signed integers and floats
+    Signed,
+    Integer,
+
+    /// Integers and floats
+    Numeric,
+    Bool,
+}
+
+/// The shapes a generic type parameter accepts
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ShapeBound {
+    ScalarOrVector,
+    Vector,
+
+    /// Only vectors with 3 components, e.g. for `cross`
+    Vector3,
+}
+
+/// The bound on the generic type `T` of an overload, e.g. float scalars or vectors for `sin`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct GenericBound {
+    pub class: ScalarClass,
+    pub shape: ShapeBound,
+}
+
+impl GenericBound {
+    pub const fn new(class: ScalarClass, shape: ShapeBound) -> Self {
+        Self { class, shape }
+    }
+
+    pub fn display(self) -> String {
+        let class = match self.class {
+            ScalarClass::Float => "float",
+            ScalarClass::Signed => "signed",
+            ScalarClass::Integer => "integer",
+            ScalarClass::Numeric => "numeric",
+            ScalarClass::Bool => "bool",
+        };
+
+        let shape = match self.shape {
+            ShapeBound::ScalarOrVector => "scalar or vector",
+            ShapeBound::Vector => "vector",
+            ShapeBound::Vector3 => "vector with 3 components",
+        };
+
+        format!("{} {}", class, shape)
+    }
+}
+
+/// A type in an overload signature, given in terms of the generic type `T`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SignatureTy {
+    /// The generic type `T` itself
+    Generic,
+
+    /// The scalar element type `S` of `T`, e.g. `f32` for `vec3<f32>`
+    Element,
+
+    /// `T` with `bool` elements, e.g. `vec3<bool>` for `vec3<f32>`
+    Boolean,
+}
+
+impl SignatureTy {
+    pub fn display(self) -> &'static str {
+        match self {
+            SignatureTy::Generic => "T",
+            SignatureTy::Element => "S",
+            SignatureTy::Boolean => "B",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub ty: SignatureTy,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Overload {
+    pub bound: GenericBound,
+    pub parameters: Vec<Parameter>,
+    pub return_ty: SignatureTy,
+}
+
+impl Overload {
+    pub fn new(bound: GenericBound, parameters: &[(&'static str, SignatureTy)], return_ty: SignatureTy) -> Self {
+        let parameters = parameters.iter()
+            .map(|&(name, ty)| Parameter { name, ty })
+            .collect();
+
+        Self {
+            bound,
+            parameters,
+            return_ty,
+        }
+    }
+
+    /// Formats the overload like `fn length(x: T) -> S where T: float scalar or vector, S: element of T`
+    pub fn display(&self, name: &str) -> String {
+        let parameters = self.parameters.iter()
+            .map(|parameter| format!("{}: {}", parameter.name, parameter.ty.display()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut bounds = vec![format!("T: {}", self.bound.display())];
+        let uses = |ty| self.return_ty == ty || self.parameters.iter().any(|parameter| parameter.ty == ty);
+        if uses(SignatureTy::Element) {
+            bounds.push("S: element of T".to_string());
+        }
+        if uses(SignatureTy::Boolean) {
+            bounds.push("B: T with bool elements".to_string());
+        }
+
+        format!("fn {}({}) -> {} where {}", name, parameters, self.return_ty.display(), bounds.join(", "))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Intrinsic {
+    pub name: Name,
+    pub kind: IntrinsicKind,
+}
+
+impl Intrinsic {
+    pub fn new(name: Name, kind: IntrinsicKind) -> Self {
+        Self { name, kind }
+    }
+
+    /// One line per overload, e.g. for hover
+    pub fn signatures(&self, name: &str) -> Vec<String> {
+        match &self.kind {
+            IntrinsicKind::Overloaded(overloads) => overloads.iter().map(|overload| overload.display(name)).collect(),
+            IntrinsicKind::Texture(function) => vec![function.signature().to_string()],
+            IntrinsicKind::Atomic(function) => vec![function.signature()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IntrinsicList {
+    inner: Arc<Vec<Arc<Intrinsic>>>,
+}
+
+impl IntrinsicList {
+    pub fn new(inner: Arc<Vec<Arc<Intrinsic>>>) -> Self {
+        Self { inner }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<Intrinsic>> {
+        self.inner.iter()
+    }
+}
+
+pub struct IntrinsicListBuilder<'a> {
+    db: &'a dyn HirDatabase,
+    inner: Vec<Arc<Intrinsic>>,
+}
+
+impl<'a> IntrinsicListBuilder<'a> {
+    pub fn new(db: &'a dyn HirDatabase) -> Self {
+        Self {
+            db,
+            inner: Vec::new(),
+        }
+    }
+
+    pub fn add_intrinsic(&mut self, name: &str, kind: IntrinsicKind) {
+        let name = self.db.intern_name(name.to_string());
+        self.inner.push(Arc::new(Intrinsic {
+            name,
+            kind,
+        }));
+    }
+
+    pub fn add_overloaded(&mut self, name: &str, overloads: Vec<Overload>) {
+        self.add_intrinsic(name, IntrinsicKind::Overloaded(overloads));
+    }
+
+    pub fn build(self) -> IntrinsicList {
+        IntrinsicList::new(Arc::new(self.inner))
+    }
+}
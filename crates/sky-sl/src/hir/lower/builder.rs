@@ -1,5 +1,6 @@
 use super::LowerToHirError;
-use crate::{fs::FileId, hir::{HirDatabase, named::{NamedItemKind, NamedScope}, untyped::{ItemKind, Module}}, intern::Name, syn::ast::*};
+use rowan::TextRange;
+use crate::{fs::FileId, hir::{HirDatabase, const_eval::{eval_expression, ConstEvalError, ConstValue}, type_check::ScalarTy, named::{Import, NamedItemKind, NamedScope, resolve_use_tree}, untyped::{ExpressionKind, ItemKind, Module}}, intern::Name, syn::ast::*};
 
 pub struct HirModuleBuilder<'a> {
     db: &'a dyn HirDatabase,
@@ -7,6 +8,12 @@ pub struct HirModuleBuilder<'a> {
     items: Vec<ItemKind>,
     diagnostics: Vec<LowerToHirError>,
     scope: NamedScope,
+
+    /// The generic parameters of the function being lowered, they shadow items with the same name in types
+    generic_parameters: Vec<Name>,
+
+    /// The struct of the impl block being lowered, the type of `self` and the item its functions refer to
+    impl_target: Option<(Name, NamedItemKind)>,
 }
 
 impl<'a> HirModuleBuilder<'a> {
@@ -19,6 +26,8 @@ impl<'a> HirModuleBuilder<'a> {
             items: Vec::new(),
             diagnostics: Vec::new(),
             scope,
+            generic_parameters: Vec::new(),
+            impl_target: None,
         }
     }
 
@@ -30,19 +39,63 @@ impl<'a> HirModuleBuilder<'a> {
         self.db.intern_name(name)
     }
 
+    pub fn lookup_name(&self, name: Name) -> String {
+        self.db.lookup_intern_name(name)
+    }
+
+    pub fn resolve_use_tree(&self, use_tree: UseTree) -> Vec<Import> {
+        resolve_use_tree(self.db, self.current_file, use_tree)
+    }
+
     pub fn lookup_item(&self, name: Name) -> Option<NamedItemKind> {
         self.scope.lookup(name)
     }
 
-    pub fn lookup_item_in(&self, name: Name, file_id: FileId) -> Option<NamedItemKind> {
+    /// Looks up an item declared in another module, items that are not visible from the current file are reported at `span`
+    pub fn lookup_item_in(&mut self, name: Name, file_id: FileId, span: TextRange) -> Option<NamedItemKind> {
         let scope = self.db.get_import_scope(file_id);
-        scope.lookup(name)
+        let item = scope.lookup(name)?;
+        self.check_visibility(&item, span);
+        Some(item)
+    }
+
+    /// Reports an item that is not visible from the current file, it is still resolved to avoid follow-up errors
+    pub fn check_visibility(&mut self, item: &NamedItemKind, span: TextRange) {
+        if let NamedItemKind::Symbol(symbol) = item {
+            if !symbol.is_visible_from(self.db, self.current_file) {
+                self.add_diagnostic(LowerToHirError::PrivateItem {
+                    name: self.lookup_name(symbol.name),
+                    scope: symbol.visibility.scope(),
+                    span,
+                });
+            }
+        }
     }
 
     pub fn lookup_module_file(&self, current_module: FileId, name: Name) -> Option<FileId> {
         self.db.child_module(current_module, name)
     }
 
+    pub fn set_generic_parameters(&mut self, generic_parameters: Vec<Name>) {
+        self.generic_parameters = generic_parameters;
+    }
+
+    pub fn is_generic_parameter(&self, name: Name) -> bool {
+        self.generic_parameters.contains(&name)
+    }
+
+    pub fn set_impl_target(&mut self, impl_target: Option<(Name, NamedItemKind)>) {
+        self.impl_target = impl_target;
+    }
+
+    pub fn impl_target(&self) -> Option<(Name, NamedItemKind)> {
+        self.impl_target.clone()
+    }
+
+    pub fn eval_const(&self, expression: &ExpressionKind, expected: Option<ScalarTy>) -> Result<ConstValue, ConstEvalError> {
+        eval_expression(self.db, expression, expected)
+    }
+
     pub fn add_item(&mut self, item: ItemKind) {
         self.items.push(item);
     }
@@ -1,3 +1,5 @@
+use crate::{hir::const_eval::ConstEvalError, syn::cst::LiteralError};
+use rowan::TextRange;
 use thiserror::*;
 
 #[derive(Debug, Error, Eq, PartialEq, Clone)]
@@ -18,6 +20,24 @@ pub enum LowerToHirError {
     #[error("Incomplete layout declaration")]
     IncompleteLayoutDeclaration,
 
+    #[error("Incomplete constant declaration")]
+    IncompleteConstDeclaration,
+
+    #[error("Incomplete enum declaration")]
+    IncompleteEnumDeclaration,
+
+    #[error("Incomplete type alias declaration")]
+    IncompleteTypeAliasDeclaration,
+
+    #[error("Incomplete impl block")]
+    IncompleteImplDeclaration,
+
+    #[error("`{name}` is not a struct declared in this module")]
+    InvalidImplTarget { name: String },
+
+    #[error("`self` can only be the first argument of a function in an impl block")]
+    InvalidReceiver,
+
     #[error("Incomplete statement")]
     IncompleteStatement,
 
@@ -27,6 +47,48 @@ pub enum LowerToHirError {
     #[error("Incomplete use declaration")]
     IncompleteUseDeclaration,
 
+    #[error("Unresolved import `{name}`")]
+    UnresolvedImport { name: String },
+
+    #[error("`{name}` is private to its {scope}, consider declaring it `pub`")]
+    PrivateItem { name: String, scope: &'static str, span: TextRange },
+
+    #[error("Unknown shader stage `{name}`, expected `vertex`, `fragment` or `compute`")]
+    UnknownShaderStage { name: String },
+
+    #[error("Unknown interpolation `{name}`, expected `perspective`, `linear` or `flat`")]
+    UnknownInterpolation { name: String },
+
+    #[error("Unknown access mode `{name}`, expected `read` or `read_write`")]
+    UnknownAccessMode { name: String },
+
+    #[error("Only storage bindings have an access mode, `{binding}` bindings can't declare one")]
+    UnexpectedAccessMode { binding: &'static str },
+
     #[error("Incomplete path")]
     IncompletePath,
+
+    #[error("Incomplete type")]
+    IncompleteType,
+
+    #[error("Invalid literal: {error}")]
+    InvalidLiteral { error: LiteralError },
+
+    #[error("Array length must be a positive integer constant")]
+    InvalidArrayLength { span: TextRange },
+
+    #[error("{error}")]
+    ConstEval { error: ConstEvalError },
+}
+
+impl LowerToHirError {
+    /// The source range of the error, not every error is located yet
+    pub fn span(&self) -> Option<TextRange> {
+        match self {
+            LowerToHirError::PrivateItem { span, .. } => Some(*span),
+            LowerToHirError::InvalidArrayLength { span } => Some(*span),
+            LowerToHirError::ConstEval { error } => Some(error.span()),
+            _ => None,
+        }
+    }
 }
@@ -1,7 +1,9 @@
 use super::HirDatabase;
 use crate::fs::FileId;
-use crate::syn::ast::*;
-use crate::hir::untyped;
+use crate::intern::Name;
+use rowan::TextRange;
+use crate::syn::{ast::*, cst::{LiteralError, SyntaxKind}};
+use crate::hir::{named::NamedItemKind, symbol::SymbolKind, type_check::ScalarTy, untyped};
 
 mod builder;
 mod error;
@@ -28,6 +30,10 @@ pub fn lower_ast_to_hir(file: FileId, db: &dyn HirDatabase, ast: &Root) -> untyp
             ModuleItemKind::FunctionDefinition(function_definition) => visit_function_definition(function_definition, &mut builder),
             ModuleItemKind::StructDefinition(struct_definition) => visit_struct_definition(struct_definition, &mut builder),
             ModuleItemKind::LayoutDefinition(layout_definition) => visit_layout_definition(layout_definition, &mut builder),
+            ModuleItemKind::ConstDefinition(const_definition) => visit_const_definition(const_definition, &mut builder),
+            ModuleItemKind::EnumDefinition(enum_definition) => visit_enum_definition(enum_definition, &mut builder),
+            ModuleItemKind::ImplDefinition(impl_definition) => visit_impl_definition(impl_definition, &mut builder),
+            ModuleItemKind::TypeAliasDefinition(type_alias_definition) => visit_type_alias_definition(type_alias_definition, &mut builder),
             _ => { /* TODO */ }
         };
     }
@@ -36,55 +42,118 @@ pub fn lower_ast_to_hir(file: FileId, db: &dyn HirDatabase, ast: &Root) -> untyp
 }
 
 fn visit_use_declaration(use_declaration: UseDeclaration, builder: &mut HirModuleBuilder) {
-    lower_use_declaration(use_declaration, builder).unwrap();
+    if let Err(error) = lower_use_declaration(use_declaration, builder) {
+        builder.add_diagnostic(error);
+    }
 }
 
 fn lower_use_declaration(use_declaration: UseDeclaration, builder: &mut HirModuleBuilder) -> Result<(), LowerToHirError> {
-    let use_tree = use_declaration.use_tree().ok_or_else(|| LowerToHirError::IncompleteUseDeclaration)?;
+    let use_tree = use_declaration.use_tree().ok_or(LowerToHirError::IncompleteUseDeclaration)?;
+
+    // the imports themselves are added to the local scope, only unresolved and private paths are reported here
+    for import in builder.resolve_use_tree(use_tree) {
+        match &import.item {
+            Some(item) => builder.check_visibility(item, import.span),
+            None => {
+                let name = builder.lookup_name(import.name);
+                builder.add_diagnostic(LowerToHirError::UnresolvedImport { name });
+            },
+        }
+    }
 
     Ok(())
 }
 
 fn visit_function_definition(function_definition: FunctionDefinition, builder: &mut HirModuleBuilder) {
-    match lower_function_definition(function_definition, builder) {
+    let function = lower_function_definition(function_definition, builder);
+
+    // the generic parameters of a function are in scope for its signature and body only
+    builder.set_generic_parameters(Vec::new());
+
+    match function {
         Ok(function_kind) => builder.add_item(untyped::ItemKind::Function(function_kind)),
         Err(error) => builder.add_diagnostic(error),
     }
 }
 
 fn lower_function_definition(function_definition: FunctionDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::FunctionKind, LowerToHirError> {
-    let function_signature = function_definition.signature().map(|s| lower_function_signature(s, builder)).ok_or_else(|| LowerToHirError::MissingFunctionSignature)??;
-    let block = lower_block_definition(function_definition.block_definition().ok_or_else(|| LowerToHirError::IncompleteFunctionBody)?, builder)?;
-    Ok(untyped::FunctionKind::new(function_signature, block, function_definition.syntax.text_range()))
+    let function_signature = function_definition.signature().map(|s| lower_function_signature(s, builder)).ok_or(LowerToHirError::MissingFunctionSignature)??;
+    let stage = match function_definition.stage() {
+        Some(stage) => Some(lower_stage(stage)?),
+        None => None,
+    };
+
+    let block = lower_block_definition(function_definition.block_definition().ok_or(LowerToHirError::IncompleteFunctionBody)?, builder)?;
+    Ok(untyped::FunctionKind::new(function_signature, stage, block, function_definition.syntax.text_range()))
+}
+
+fn lower_stage(stage: Stage) -> Result<untyped::ShaderStage, LowerToHirError> {
+    let name = stage.name().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
+    untyped::ShaderStage::from_name(name.text()).ok_or_else(|| LowerToHirError::UnknownShaderStage {
+        name: name.text().to_string(),
+    })
 }
 
 fn lower_function_signature(function_signature: FunctionSignature, builder: &mut HirModuleBuilder) -> Result<untyped::FunctionSignature, LowerToHirError> {
-    let name = function_signature.name().ok_or_else(|| LowerToHirError::IncompleteFunctionSignature)?;
+    let name = function_signature.name().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
     let name = builder.intern_name(name.syntax().to_string());
-    let item = builder.lookup_item(name).ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
+
+    // functions of an impl block are not module items, they refer to the struct instead
+    let item = match builder.impl_target() {
+        Some((_, item)) => item,
+        None => builder.lookup_item(name).ok_or(LowerToHirError::IncompleteStructDeclaration)?,
+    };
+
+    let mut generic_parameters = Vec::new();
+    if let Some(generic_parameter_list) = function_signature.generic_parameter_list() {
+        for parameter in generic_parameter_list.parameters() {
+            let name = parameter.name().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
+            let name = builder.intern_name(name.syntax().to_string());
+
+            let mut bounds = Vec::new();
+            for bound in parameter.bounds() {
+                let bound_name = bound.name().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
+                let bound_name = builder.intern_name(bound_name.syntax().to_string());
+                bounds.push(untyped::TypeBound::new(bound_name, bound.syntax().text_range()));
+            }
+
+            generic_parameters.push(untyped::GenericParameter::new(name, bounds, parameter.syntax().text_range()));
+        }
+    }
+    builder.set_generic_parameters(generic_parameters.iter().map(|parameter| parameter.name).collect());
 
     let mut arguments = Vec::new();
-    let argument_list = function_signature.argument_list().ok_or_else(|| LowerToHirError::IncompleteFunctionSignature)?;
-    for argument in argument_list.arguments() {
-        let name = argument.name().ok_or_else(|| LowerToHirError::IncompleteFunctionSignature)?;
+    let mut has_receiver = false;
+    let argument_list = function_signature.argument_list().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
+    for (index, argument) in argument_list.arguments().enumerate() {
+        let name = argument.name().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
+        let is_receiver = name.syntax().to_string() == "self";
         let name = builder.intern_name(name.syntax().to_string());
-        let ty_name = argument.path().ok_or_else(|| LowerToHirError::IncompleteFunctionSignature)?;
-        let ty_name = builder.intern_name(ty_name.syntax().to_string());
-        let item_type = builder.lookup_item(ty_name).ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
+
+        let type_reference = match (argument.ty(), builder.impl_target()) {
+            (Some(type_reference), _) if !is_receiver => lower_type(type_reference, builder)?,
+            (None, _) if !is_receiver => return Err(LowerToHirError::IncompleteFunctionSignature),
+            (None, Some((target, item))) if index == 0 => {
+                // the receiver takes the implemented struct as its type
+                let span = argument.syntax().text_range();
+                let segment = untyped::ItemPathSegment::new(target, item, Vec::new(), None, span);
+                untyped::TypeReference::Path(untyped::ItemPath::new(segment, span))
+            },
+            (Some(type_reference), Some(_)) if index == 0 => lower_type(type_reference, builder)?,
+            _ => return Err(LowerToHirError::InvalidReceiver),
+        };
+        has_receiver |= is_receiver;
 
         arguments.push(untyped::FunctionArgument::new(
             name,
-            item_type,
+            type_reference,
             argument.syntax().text_range(), 
         ));
     }
 
     let return_type = if let Some(return_type) = function_signature.return_type() {
-        let return_type_name = return_type.path().ok_or_else(|| LowerToHirError::IncompleteFunctionSignature)?;
-        let return_type_name = builder.intern_name(return_type_name.syntax().to_string());
-        let return_type = builder.lookup_item(return_type_name).ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
-
-        Some(return_type)
+        let return_type = return_type.ty().ok_or(LowerToHirError::IncompleteFunctionSignature)?;
+        Some(lower_type(return_type, builder)?)
     } else {
         None
     };
@@ -92,7 +161,9 @@ fn lower_function_signature(function_signature: FunctionSignature, builder: &mut
     Ok(untyped::FunctionSignature::new(
         name,
         item,
+        generic_parameters,
         arguments,
+        has_receiver,
         return_type,
         function_signature.syntax().text_range()
     ))
@@ -115,104 +186,312 @@ fn lower_statement(statement: Statement, builder: &mut HirModuleBuilder) -> Resu
     match statement.kind() {
         StatementKind::Let(let_statement) => Ok(untyped::StatementKind::Let(lower_let_statement(let_statement, builder)?)),
         StatementKind::Expression(expression_statement) => Ok(untyped::StatementKind::Expression(lower_expression_statement(expression_statement, builder)?)),
+        StatementKind::Assign(assign_statement) => Ok(untyped::StatementKind::Assign(lower_assign_statement(assign_statement, builder)?)),
     }
 }
 
 fn lower_let_statement(let_statement: LetStatement, builder: &mut HirModuleBuilder) -> Result<untyped::LetStatement, LowerToHirError> {
-    let name = let_statement.identifier().ok_or_else(|| LowerToHirError::IncompleteStatement)?;
+    let name = let_statement.name().ok_or(LowerToHirError::IncompleteStatement)?;
     let name = builder.intern_name(name.syntax().to_string());
-    let expression = lower_expression(let_statement.expression().ok_or_else(|| LowerToHirError::IncompleteStatement)?, builder)?;
+
+    let type_reference = if let Some(type_identifier) = let_statement.type_identifier() {
+        let type_reference = type_identifier.ty().ok_or(LowerToHirError::IncompleteStatement)?;
+        Some(lower_type(type_reference, builder)?)
+    } else {
+        None
+    };
+
+    let expression = lower_expression(let_statement.expression().ok_or(LowerToHirError::IncompleteStatement)?, builder)?;
     
     Ok(untyped::LetStatement::new(
         name,
+        type_reference,
         expression,
         let_statement.syntax().text_range()
     ))
 }
 
 fn lower_expression_statement(expression_statement: ExpressionStatement, builder: &mut HirModuleBuilder) -> Result<untyped::ExpressionStatement, LowerToHirError> {
-    let expression = lower_expression(expression_statement.expression().ok_or_else(|| LowerToHirError::IncompleteStatement)?, builder)?;
+    let expression = lower_expression(expression_statement.expression().ok_or(LowerToHirError::IncompleteStatement)?, builder)?;
     
     Ok(untyped::ExpressionStatement::new(
         expression,
+        expression_statement.has_semicolon(),
         expression_statement.syntax().text_range()
     ))
 }
 
+fn lower_assign_statement(assign_statement: AssignStatement, builder: &mut HirModuleBuilder) -> Result<untyped::AssignStatement, LowerToHirError> {
+    let place = lower_expression(assign_statement.place().ok_or(LowerToHirError::IncompleteStatement)?, builder)?;
+    let value = lower_expression(assign_statement.value().ok_or(LowerToHirError::IncompleteStatement)?, builder)?;
+
+    Ok(untyped::AssignStatement::new(
+        place,
+        value,
+        assign_statement.syntax().text_range()
+    ))
+}
+
 fn lower_expression(expression: Expression, builder: &mut HirModuleBuilder) -> Result<untyped::ExpressionKind, LowerToHirError> {
     match expression.kind() {
         ExpressionKind::LiteralExpression(litreal_expression) => Ok(untyped::ExpressionKind::LiteralExpression(lower_literal_expression(litreal_expression)?)),
         ExpressionKind::GroupExpression(group_expression) => Ok(untyped::ExpressionKind::GroupExpression(lower_group_expression(group_expression, builder)?)),
         ExpressionKind::BinaryExpression(binary_expression) => Ok(untyped::ExpressionKind::BinaryExpression(lower_binary_expression(binary_expression, builder)?)),
+        ExpressionKind::UnaryExpression(unary_expression) => Ok(untyped::ExpressionKind::UnaryExpression(lower_unary_expression(unary_expression, builder)?)),
         ExpressionKind::CallExpression(call_expression) => Ok(untyped::ExpressionKind::CallExpression(lower_call_expression(call_expression, builder)?)),
-        ExpressionKind::FieldAccessExpression(field_access_expression) => Ok(untyped::ExpressionKind::FieldAccessExpression(lower_field_access_expression(field_access_expression)?)),
-        ExpressionKind::IndexExpression(index_expression) => Ok(untyped::ExpressionKind::IndexExpression(lower_index_expression(index_expression)?)),
+        ExpressionKind::FieldAccessExpression(field_access_expression) => Ok(untyped::ExpressionKind::FieldAccessExpression(lower_field_access_expression(field_access_expression, builder)?)),
+        ExpressionKind::MethodCallExpression(method_call_expression) => Ok(untyped::ExpressionKind::MethodCallExpression(lower_method_call_expression(method_call_expression, builder)?)),
+        ExpressionKind::IndexExpression(index_expression) => Ok(untyped::ExpressionKind::IndexExpression(lower_index_expression(index_expression, builder)?)),
         ExpressionKind::PathExpression(path_expression) => Ok(untyped::ExpressionKind::PathExpression(lower_path_expression(path_expression, builder)?)),
         ExpressionKind::StructExpression(struct_expression) => Ok(untyped::ExpressionKind::StructExpression(lower_struct_expression(struct_expression)?)),
+        ExpressionKind::BlockExpression(block_expression) => Ok(untyped::ExpressionKind::BlockExpression(lower_block_expression(block_expression, builder)?)),
+        ExpressionKind::MatchExpression(match_expression) => Ok(untyped::ExpressionKind::MatchExpression(lower_match_expression(match_expression, builder)?)),
     }
 }
 
 fn lower_literal_expression(literal_expression: LiteralExpression) -> Result<untyped::LiteralExpression, LowerToHirError> {
-    Ok(untyped::LiteralExpression::new(literal_expression.syntax().text_range()))
+    let literal = match literal_expression.value() {
+        Ok(literal) => literal,
+        Err(LiteralError::NotALiteral) => return Err(LowerToHirError::IncompleteExpression),
+        Err(error) => return Err(LowerToHirError::InvalidLiteral { error }),
+    };
+
+    Ok(untyped::LiteralExpression::new(literal.value, literal.suffix, literal_expression.syntax().text_range()))
 }
 
 fn lower_group_expression(group_expression: GroupExpression, builder: &mut HirModuleBuilder) -> Result<untyped::GroupExpression, LowerToHirError> {
-    let inner = lower_expression(group_expression.expression().ok_or_else(|| LowerToHirError::IncompleteExpression)?, builder)?;
+    let inner = lower_expression(group_expression.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
     Ok(untyped::GroupExpression::new(Box::new(inner), group_expression.syntax().text_range()))
 }
 
 fn lower_binary_expression(binary_expression: BinaryExpression, builder: &mut HirModuleBuilder) -> Result<untyped::BinaryExpression, LowerToHirError> {
-    let lhs = lower_expression(binary_expression.lhs().ok_or_else(|| LowerToHirError::IncompleteExpression)?, builder)?;
-    let rhs = lower_expression(binary_expression.rhs().ok_or_else(|| LowerToHirError::IncompleteExpression)?, builder)?;
-    let _operator = binary_expression.operator().ok_or_else(|| LowerToHirError::IncompleteExpression)?;
-
-    // TODO
-    // dbg!(operator);
+    let lhs = lower_expression(binary_expression.lhs().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    let rhs = lower_expression(binary_expression.rhs().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    let operator = binary_expression.operator().and_then(|o| o.binary_operator()).ok_or(LowerToHirError::IncompleteExpression)?;
 
     Ok(untyped::BinaryExpression::new(
         Box::new(lhs),
         Box::new(rhs),
+        operator,
         binary_expression.syntax().text_range(),
     ))
 }
 
+fn lower_unary_expression(unary_expression: UnaryExpression, builder: &mut HirModuleBuilder) -> Result<untyped::UnaryExpression, LowerToHirError> {
+    let operand = lower_expression(unary_expression.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    let operator = unary_expression.operator().and_then(|o| o.unary_operator()).ok_or(LowerToHirError::IncompleteExpression)?;
+
+    Ok(untyped::UnaryExpression::new(
+        Box::new(operand),
+        operator,
+        unary_expression.syntax().text_range(),
+    ))
+}
+
 fn lower_call_expression(call_expression: CallExpression, builder: &mut HirModuleBuilder) -> Result<untyped::CallExpression, LowerToHirError> {
+    let callee = lower_expression(call_expression.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+
     let mut arguments = Vec::new();
-    let argument_list = call_expression.arguments().ok_or_else(|| LowerToHirError::IncompleteExpression)?;
+    let argument_list = call_expression.arguments().ok_or(LowerToHirError::IncompleteExpression)?;
 
-    for argument_expr in argument_list.expressions() {
+    for argument in argument_list.call_arguments() {
+        let argument_expr = argument.expression().ok_or(LowerToHirError::IncompleteExpression)?;
         arguments.push(lower_expression(argument_expr, builder)?);
     }
 
     Ok(untyped::CallExpression::new(
+        Box::new(callee),
         arguments,
         call_expression.syntax().text_range(),
     ))
 }
 
-fn lower_field_access_expression(field_access_expression: FieldAccessExpression) -> Result<untyped::FieldAccessExpression, LowerToHirError> {
-    // TODO
+fn lower_field_access_expression(field_access_expression: FieldAccessExpression, builder: &mut HirModuleBuilder) -> Result<untyped::FieldAccessExpression, LowerToHirError> {
+    let expression = lower_expression(field_access_expression.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    let field = field_access_expression.name().ok_or(LowerToHirError::IncompleteExpression)?;
+    let field = builder.intern_name(field.syntax().to_string());
+
     Ok(untyped::FieldAccessExpression::new(
+        Box::new(expression),
+        field,
         field_access_expression.syntax().text_range(),
     ))
 }
 
-fn lower_index_expression(index_expression: IndexExpression) -> Result<untyped::IndexExpression, LowerToHirError> {
-    // TODO
+fn lower_method_call_expression(method_call_expression: MethodCallExpression, builder: &mut HirModuleBuilder) -> Result<untyped::MethodCallExpression, LowerToHirError> {
+    let receiver = lower_expression(method_call_expression.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    let method = method_call_expression.name().ok_or(LowerToHirError::IncompleteExpression)?;
+    let method = builder.intern_name(method.syntax().to_string());
+
+    let mut arguments = Vec::new();
+    let argument_list = method_call_expression.arguments().ok_or(LowerToHirError::IncompleteExpression)?;
+    for argument in argument_list.call_arguments() {
+        let argument_expr = argument.expression().ok_or(LowerToHirError::IncompleteExpression)?;
+        arguments.push(lower_expression(argument_expr, builder)?);
+    }
+
+    Ok(untyped::MethodCallExpression::new(
+        Box::new(receiver),
+        method,
+        arguments,
+        method_call_expression.syntax().text_range(),
+    ))
+}
+
+fn lower_index_expression(index_expression: IndexExpression, builder: &mut HirModuleBuilder) -> Result<untyped::IndexExpression, LowerToHirError> {
+    let expression = lower_expression(index_expression.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    let index = index_expression.indexer().and_then(|indexer| indexer.expression()).ok_or(LowerToHirError::IncompleteExpression)?;
+    let index = lower_expression(index, builder)?;
+
     Ok(untyped::IndexExpression::new(
+        Box::new(expression),
+        Box::new(index),
         index_expression.syntax().text_range(),
     ))
 }
 
 fn lower_path_expression(path_expression: PathExpression, builder: &mut HirModuleBuilder) -> Result<untyped::PathExpression, LowerToHirError> {
-    // TODO
-    let name = builder.intern_name(path_expression.syntax().to_string());
+    // the joined segment names are used to look up local variables and to report unresolved names
+    let path = path_expression.path().ok_or(LowerToHirError::IncompleteExpression)?;
+
+    let mut segment_names = Vec::new();
+    let mut segment_spans = Vec::new();
+    let mut generic = false;
+    let mut segment = path.segment();
+    while let Some(current) = segment {
+        let name = current.name().ok_or(LowerToHirError::IncompletePath)?;
+        segment_names.push(name.syntax().to_string());
+        segment_spans.push(name.syntax().text_range());
+        generic |= current.generic_argument_list().is_some();
+        segment = current.segment();
+    }
+
+    // generic arguments are only parsed before call arguments, e.g. `vec3<f32>(1.0, 2.0, 3.0)`
+    let generic_path = match generic {
+        true => Some(lower_path(path.clone(), builder)?),
+        false => None,
+    };
+
+    let name = builder.intern_name(segment_names.join("::"));
+    let (item, member) = if segment_names.len() == 1 {
+        (builder.lookup_item(name), None)
+    } else if let Ok(path) = lower_path(path, builder) {
+        // a path through modules like `foo::bar` always names an item
+        (Some(path.last_segment().item.clone()), None)
+    } else {
+        match lower_member_path(&segment_names, &segment_spans, builder) {
+            Some((item, member)) => (Some(item), Some(member)),
+            None => (None, None),
+        }
+    };
+
     Ok(untyped::PathExpression::new(
         name,
+        item,
+        member,
+        generic_path,
         path_expression.syntax().text_range(),
     ))
 }
 
+/// Resolves a path like `foo::LightKind::Point` or `foo::Light::new` where every segment but the last names a module,
+/// the enum or the struct, `segment_spans` locate the segments to report private items
+fn lower_member_path(segment_names: &[String], segment_spans: &[TextRange], builder: &mut HirModuleBuilder) -> Option<(NamedItemKind, Name)> {
+    let (member, prefix) = segment_names.split_last()?;
+    let (first, modules) = prefix.split_first()?;
+
+    let mut name = builder.intern_name(first.clone());
+    let mut item = builder.lookup_item(name)?;
+    let mut scope = builder.current_file();
+    for (segment, span) in modules.iter().zip(&segment_spans[1..]) {
+        scope = builder.lookup_module_file(scope, name)?;
+        name = builder.intern_name(segment.clone());
+        item = builder.lookup_item_in(name, scope, *span)?;
+    }
+
+    match &item {
+        NamedItemKind::Symbol(symbol) if matches!(symbol.kind, SymbolKind::Enum | SymbolKind::Struct) => Some((item, builder.intern_name(member.clone()))),
+        _ => None,
+    }
+}
+
+fn lower_block_expression(block_expression: BlockExpression, builder: &mut HirModuleBuilder) -> Result<untyped::BlockExpression, LowerToHirError> {
+    let block = lower_block_definition(block_expression.block_definition().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+    Ok(untyped::BlockExpression::new(block, block_expression.syntax().text_range()))
+}
+
+fn lower_match_expression(match_expression: MatchExpression, builder: &mut HirModuleBuilder) -> Result<untyped::MatchExpression, LowerToHirError> {
+    let scrutinee = lower_expression(match_expression.scrutinee().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+
+    let mut arms = Vec::new();
+    for arm in match_expression.arms() {
+        let mut patterns = Vec::new();
+        for pattern in arm.patterns() {
+            patterns.push(lower_pattern(pattern, builder)?);
+        }
+
+        if patterns.is_empty() {
+            return Err(LowerToHirError::IncompleteExpression);
+        }
+
+        let expression = lower_expression(arm.expression().ok_or(LowerToHirError::IncompleteExpression)?, builder)?;
+        arms.push(untyped::MatchArm::new(
+            patterns,
+            expression,
+            arm.syntax().text_range(),
+        ));
+    }
+
+    Ok(untyped::MatchExpression::new(
+        Box::new(scrutinee),
+        arms,
+        match_expression.syntax().text_range(),
+    ))
+}
+
+fn lower_pattern(pattern: Pattern, builder: &mut HirModuleBuilder) -> Result<untyped::Pattern, LowerToHirError> {
+    match pattern.kind() {
+        PatternKind::Wildcard => Ok(untyped::Pattern::Wildcard { span: pattern.syntax().text_range() }),
+        PatternKind::Literal(literal_pattern) => {
+            let literal = lower_literal_expression(literal_pattern.literal().ok_or(LowerToHirError::IncompleteExpression)?)?;
+            Ok(untyped::Pattern::Literal(untyped::LiteralPattern::new(
+                literal.value,
+                literal.suffix,
+                literal_pattern.is_negated(),
+                literal_pattern.syntax().text_range(),
+            )))
+        },
+        PatternKind::Path(path_pattern) => {
+            let path = path_pattern.path().ok_or(LowerToHirError::IncompletePath)?;
+
+            let mut segment_names = Vec::new();
+            let mut segment_spans = Vec::new();
+            let mut segment = path.segment();
+            while let Some(current) = segment {
+                let name = current.name().ok_or(LowerToHirError::IncompletePath)?;
+                segment_names.push(name.syntax().to_string());
+                segment_spans.push(name.syntax().text_range());
+                segment = current.segment();
+            }
+
+            // a pattern path always names an enum variant, unresolved ones are reported by the type check
+            let name = builder.intern_name(segment_names.join("::"));
+            let (item, variant) = match lower_member_path(&segment_names, &segment_spans, builder) {
+                Some((item, variant)) => (Some(item), Some(variant)),
+                None => (None, None),
+            };
+
+            Ok(untyped::Pattern::Path(untyped::PathExpression::new(
+                name,
+                item,
+                variant,
+                None,
+                path_pattern.syntax().text_range(),
+            )))
+        },
+    }
+}
+
 fn lower_struct_expression(struct_expression: StructExpression) -> Result<untyped::StructExpression, LowerToHirError> {
     // TODO
     Ok(untyped::StructExpression::new(
@@ -220,6 +499,122 @@ fn lower_struct_expression(struct_expression: StructExpression) -> Result<untype
     ))
 }
 
+fn visit_const_definition(const_definition: ConstDefinition, builder: &mut HirModuleBuilder) {
+    match lower_const_definition(const_definition, builder) {
+        Ok(const_kind) => builder.add_item(untyped::ItemKind::Const(const_kind)),
+        Err(diagnostic) => builder.add_diagnostic(diagnostic),
+    }
+}
+
+/// Lowers a single constant without lowering the rest of its module, used to evaluate constants
+/// while the module itself is lowered, e.g. for array lengths
+pub fn lower_const(file: FileId, db: &dyn HirDatabase, const_definition: ConstDefinition) -> Result<untyped::ConstKind, LowerToHirError> {
+    let mut builder = HirModuleBuilder::new(db, file);
+    lower_const_definition(const_definition, &mut builder)
+}
+
+fn lower_const_definition(const_definition: ConstDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::ConstKind, LowerToHirError> {
+    let name = const_definition.name().ok_or(LowerToHirError::IncompleteConstDeclaration)?;
+    let name = builder.intern_name(name.syntax().to_string());
+    let item = builder.lookup_item(name).ok_or(LowerToHirError::IncompleteConstDeclaration)?;
+
+    let type_reference = const_definition.type_identifier()
+        .and_then(|type_identifier| type_identifier.ty())
+        .ok_or(LowerToHirError::IncompleteConstDeclaration)?;
+    let type_reference = lower_type(type_reference, builder)?;
+
+    let expression = const_definition.expression().ok_or(LowerToHirError::IncompleteConstDeclaration)?;
+    let expression = lower_expression(expression, builder)?;
+
+    let override_id = match const_definition.override_id() {
+        Some(override_id) => {
+            let override_id = override_id.expression().ok_or(LowerToHirError::IncompleteConstDeclaration)?;
+            Some(lower_expression(override_id, builder)?)
+        },
+        None => None,
+    };
+
+    Ok(untyped::ConstKind::new(
+        name,
+        item,
+        type_reference,
+        expression,
+        override_id,
+        const_definition.syntax().text_range(),
+    ))
+}
+
+fn visit_type_alias_definition(type_alias_definition: TypeAliasDefinition, builder: &mut HirModuleBuilder) {
+    match lower_type_alias_definition(type_alias_definition, builder) {
+        Ok(type_alias_kind) => builder.add_item(untyped::ItemKind::TypeAlias(type_alias_kind)),
+        Err(diagnostic) => builder.add_diagnostic(diagnostic),
+    }
+}
+
+/// Lowers a single type alias without lowering the rest of its module, used to resolve aliases
+/// while the module itself is lowered, e.g. for the type of a constant in an array length
+pub fn lower_type_alias(file: FileId, db: &dyn HirDatabase, type_alias_definition: TypeAliasDefinition) -> Result<untyped::TypeAliasKind, LowerToHirError> {
+    let mut builder = HirModuleBuilder::new(db, file);
+    lower_type_alias_definition(type_alias_definition, &mut builder)
+}
+
+fn lower_type_alias_definition(type_alias_definition: TypeAliasDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::TypeAliasKind, LowerToHirError> {
+    let name = type_alias_definition.name().ok_or(LowerToHirError::IncompleteTypeAliasDeclaration)?;
+    let name = builder.intern_name(name.syntax().to_string());
+    let item = builder.lookup_item(name).ok_or(LowerToHirError::IncompleteTypeAliasDeclaration)?;
+
+    let type_reference = type_alias_definition.ty().ok_or(LowerToHirError::IncompleteTypeAliasDeclaration)?;
+    let type_reference = lower_type(type_reference, builder)?;
+
+    Ok(untyped::TypeAliasKind::new(
+        name,
+        item,
+        type_reference,
+        type_alias_definition.syntax().text_range(),
+    ))
+}
+
+fn visit_enum_definition(enum_definition: EnumDefinition, builder: &mut HirModuleBuilder) {
+    match lower_enum_definition(enum_definition, builder) {
+        Ok(enum_kind) => builder.add_item(untyped::ItemKind::Enum(enum_kind)),
+        Err(diagnostic) => builder.add_diagnostic(diagnostic),
+    }
+}
+
+fn lower_enum_definition(enum_definition: EnumDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::EnumKind, LowerToHirError> {
+    let name = enum_definition.name().ok_or(LowerToHirError::IncompleteEnumDeclaration)?;
+    let name = builder.intern_name(name.syntax().to_string());
+    let item = builder.lookup_item(name).ok_or(LowerToHirError::IncompleteEnumDeclaration)?;
+
+    let mut variants = Vec::new();
+    let variant_list = enum_definition.variant_list().ok_or(LowerToHirError::IncompleteEnumDeclaration)?;
+    for variant in variant_list.variants() {
+        let name = variant.name().ok_or(LowerToHirError::IncompleteEnumDeclaration)?;
+        let name = builder.intern_name(name.syntax().to_string());
+
+        let discriminant = match variant.discriminant() {
+            Some(discriminant) => {
+                let discriminant = discriminant.expression().ok_or(LowerToHirError::IncompleteEnumDeclaration)?;
+                Some(lower_expression(discriminant, builder)?)
+            },
+            None => None,
+        };
+
+        variants.push(untyped::EnumVariant::new(
+            name,
+            discriminant,
+            variant.syntax().text_range(),
+        ));
+    }
+
+    Ok(untyped::EnumKind::new(
+        name,
+        item,
+        variants,
+        enum_definition.syntax().text_range(),
+    ))
+}
+
 fn visit_struct_definition(struct_definition: StructDefinition, builder: &mut HirModuleBuilder) {
     match lower_struct_definition(struct_definition, builder) {
         Ok(struct_kind) => builder.add_item(untyped::ItemKind::Struct(struct_kind)),
@@ -228,22 +623,37 @@ fn visit_struct_definition(struct_definition: StructDefinition, builder: &mut Hi
 }
 
 fn lower_struct_definition(struct_definition: StructDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::StructKind, LowerToHirError> {
-    let name = struct_definition.name().ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
+    let name = struct_definition.name().ok_or(LowerToHirError::IncompleteStructDeclaration)?;
     let name = builder.intern_name(name.syntax().to_string());
-    let item = builder.lookup_item(name).ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
+    let item = builder.lookup_item(name).ok_or(LowerToHirError::IncompleteStructDeclaration)?;
 
     let mut members = Vec::new();
-    let member_list = struct_definition.member_list().ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
+    let member_list = struct_definition.member_list().ok_or(LowerToHirError::IncompleteStructDeclaration)?;
     for member in member_list.member() {
-        let name = member.name().ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
+        let name = member.name().ok_or(LowerToHirError::IncompleteStructDeclaration)?;
         let name = builder.intern_name(name.syntax().to_string());
 
-        let item_path = member.path().ok_or_else(|| LowerToHirError::IncompleteStructDeclaration)?;
-        let item_path = lower_path(item_path, builder)?;
+        let location = match member.location() {
+            Some(location) => {
+                let location = location.expression().ok_or(LowerToHirError::IncompleteStructDeclaration)?;
+                Some(lower_expression(location, builder)?)
+            },
+            None => None,
+        };
+
+        let interpolation = match member.interpolation() {
+            Some(interpolation) => Some(lower_interpolation(interpolation)?),
+            None => None,
+        };
+
+        let type_reference = member.ty().ok_or(LowerToHirError::IncompleteStructDeclaration)?;
+        let type_reference = lower_type(type_reference, builder)?;
 
         members.push(untyped::StructMember::new(
             name,
-            item_path,
+            location,
+            interpolation,
+            type_reference,
             member.syntax().text_range(),
         ));
     }
@@ -256,6 +666,53 @@ fn lower_struct_definition(struct_definition: StructDefinition, builder: &mut Hi
     ))
 }
 
+fn lower_interpolation(interpolation: Interpolation) -> Result<untyped::Interpolation, LowerToHirError> {
+    let name = interpolation.name().ok_or(LowerToHirError::IncompleteStructDeclaration)?;
+    untyped::Interpolation::from_name(name.text()).ok_or_else(|| LowerToHirError::UnknownInterpolation {
+        name: name.text().to_string(),
+    })
+}
+
+fn visit_impl_definition(impl_definition: ImplDefinition, builder: &mut HirModuleBuilder) {
+    match lower_impl_definition(impl_definition, builder) {
+        Ok(impl_kind) => builder.add_item(untyped::ItemKind::Impl(impl_kind)),
+        Err(diagnostic) => builder.add_diagnostic(diagnostic),
+    }
+}
+
+fn lower_impl_definition(impl_definition: ImplDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::ImplKind, LowerToHirError> {
+    let name = impl_definition.name().ok_or(LowerToHirError::IncompleteImplDeclaration)?;
+    let name = builder.intern_name(name.syntax().to_string());
+
+    // methods are looked up next to the struct, so they have to be declared in the same module
+    let item = match builder.lookup_item(name) {
+        Some(NamedItemKind::Symbol(symbol)) if symbol.kind == SymbolKind::Struct && symbol.file == builder.current_file() => NamedItemKind::Symbol(symbol),
+        _ => return Err(LowerToHirError::InvalidImplTarget { name: builder.lookup_name(name) }),
+    };
+
+    let item_list = impl_definition.item_list().ok_or(LowerToHirError::IncompleteImplDeclaration)?;
+
+    builder.set_impl_target(Some((name, item.clone())));
+    let mut functions = Vec::new();
+    for function_definition in item_list.functions() {
+        let function = lower_function_definition(function_definition, builder);
+        builder.set_generic_parameters(Vec::new());
+
+        match function {
+            Ok(function_kind) => functions.push(function_kind),
+            Err(error) => builder.add_diagnostic(error),
+        }
+    }
+    builder.set_impl_target(None);
+
+    Ok(untyped::ImplKind::new(
+        name,
+        item,
+        functions,
+        impl_definition.syntax().text_range(),
+    ))
+}
+
 fn visit_layout_definition(layout_definition: LayoutDefinition, builder: &mut HirModuleBuilder) {
     match lower_layout_definition(layout_definition, builder) {
         Ok(layout_kind) => builder.add_item(untyped::ItemKind::Layout(layout_kind)),
@@ -264,22 +721,54 @@ fn visit_layout_definition(layout_definition: LayoutDefinition, builder: &mut Hi
 }
 
 fn lower_layout_definition(layout_definition: LayoutDefinition, builder: &mut HirModuleBuilder) -> Result<untyped::LayoutKind, LowerToHirError> {
-    let name = layout_definition.name().ok_or_else(|| LowerToHirError::IncompleteLayoutDeclaration)?;
+    let name = layout_definition.name().ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
     let name = builder.intern_name(name.syntax().to_string());
-    let item = builder.lookup_item(name).ok_or_else(|| LowerToHirError::IncompleteLayoutDeclaration)?;
+    let item = builder.lookup_item(name).ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
+
+    let set_index = match layout_definition.set_index() {
+        Some(set_index) => {
+            let set_index = set_index.expression().ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
+            Some(lower_expression(set_index, builder)?)
+        },
+        None => None,
+    };
 
     let mut members = Vec::new();
 
-    let member_list = layout_definition.layout_member_list().ok_or_else(|| LowerToHirError::IncompleteLayoutDeclaration)?;
+    let member_list = layout_definition.layout_member_list().ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
     for member in member_list.layout_member() {
-        let name = member.name().ok_or_else(|| LowerToHirError::IncompleteLayoutDeclaration)?;
+        let name = member.name().ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
         let name = builder.intern_name(name.syntax().to_string());
-        let item_path = member.path().ok_or_else(|| LowerToHirError::IncompleteLayoutDeclaration)?;
-        let item_path = lower_path(item_path, builder)?;
+        let binding_kind = match member.binding_kind().and_then(|binding_kind| binding_kind.keyword()) {
+            Some(SyntaxKind::UniformKeyword) => untyped::BindingKind::Uniform,
+            Some(SyntaxKind::StorageKeyword) => untyped::BindingKind::Storage,
+            Some(SyntaxKind::ImageKeyword) => untyped::BindingKind::Image,
+            Some(SyntaxKind::SamplerKeyword) => untyped::BindingKind::Sampler,
+            Some(SyntaxKind::PushConstantKeyword) => untyped::BindingKind::PushConstant,
+            Some(SyntaxKind::WorkgroupKeyword) => untyped::BindingKind::Workgroup,
+            _ => return Err(LowerToHirError::IncompleteLayoutDeclaration),
+        };
+
+        let access = match member.access_mode() {
+            Some(access_mode) if binding_kind == untyped::BindingKind::Storage => Some(lower_access_mode(access_mode)?),
+            Some(_) => return Err(LowerToHirError::UnexpectedAccessMode { binding: binding_kind.keyword() }),
+            None => None,
+        };
+
+        let binding_index = match member.binding_index().and_then(|binding_index| binding_index.expression()) {
+            Some(binding_index) => Some(lower_expression(binding_index, builder)?),
+            None => None,
+        };
+
+        let type_reference = member.ty().ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
+        let type_reference = lower_type(type_reference, builder)?;
 
         members.push(untyped::LayoutMember::new(
             name,
-            item_path,
+            binding_index,
+            binding_kind,
+            access,
+            type_reference,
             member.syntax().text_range(), 
         ));
     }
@@ -287,13 +776,75 @@ fn lower_layout_definition(layout_definition: LayoutDefinition, builder: &mut Hi
     Ok(untyped::LayoutKind::new(
         name,
         item,
+        set_index,
         members,
         layout_definition.syntax.text_range()
     ))
 }
 
+fn lower_access_mode(access_mode: AccessMode) -> Result<untyped::AccessMode, LowerToHirError> {
+    let name = access_mode.name().ok_or(LowerToHirError::IncompleteLayoutDeclaration)?;
+    untyped::AccessMode::from_name(name.text()).ok_or_else(|| LowerToHirError::UnknownAccessMode {
+        name: name.text().to_string(),
+    })
+}
+
+fn lower_type(ty: Type, builder: &mut HirModuleBuilder) -> Result<untyped::TypeReference, LowerToHirError> {
+    match ty.kind() {
+        TypeKind::Path(path) => {
+            if let Some(generic) = lower_generic_type(&path, builder) {
+                return Ok(untyped::TypeReference::Generic(generic));
+            }
+
+            Ok(untyped::TypeReference::Path(lower_path(path, builder)?))
+        },
+        TypeKind::Array(array_type) => {
+            let element = array_type.ty().ok_or(LowerToHirError::IncompleteType)?;
+            let element = lower_type(element, builder)?;
+
+            // an invalid length is reported here and the array becomes an error type so the rest of the item is kept
+            let length = match array_type.expression().map(|length| lower_array_length(length, builder)) {
+                Some(Ok(length)) => Some(length),
+                Some(Err(error)) => {
+                    builder.add_diagnostic(error);
+                    return Ok(untyped::TypeReference::Error(array_type.syntax().text_range()));
+                },
+                None => None,
+            };
+
+            Ok(untyped::TypeReference::Array(untyped::ArrayTypeReference::new(
+                Box::new(element),
+                length,
+                array_type.syntax().text_range(),
+            )))
+        },
+    }
+}
+
+/// A single segment path without generic arguments naming a generic parameter, e.g. `T`
+fn lower_generic_type(path: &Path, builder: &mut HirModuleBuilder) -> Option<untyped::GenericTypeReference> {
+    let segment = path.segment()?;
+    if segment.segment().is_some() || segment.generic_argument_list().is_some() {
+        return None;
+    }
+
+    let name = builder.intern_name(segment.name()?.syntax().to_string());
+    builder.is_generic_parameter(name).then(|| untyped::GenericTypeReference::new(name, path.syntax().text_range()))
+}
+
+fn lower_array_length(length: Expression, builder: &mut HirModuleBuilder) -> Result<u32, LowerToHirError> {
+    let span = length.syntax().text_range();
+    let length = lower_expression(length, builder)?;
+    let length = builder.eval_const(&length, Some(ScalarTy::Int { signed: false, width: 32 }))
+        .map_err(|error| LowerToHirError::ConstEval { error })?;
+
+    length.as_u32()
+        .filter(|length| *length > 0)
+        .ok_or(LowerToHirError::InvalidArrayLength { span })
+}
+
 fn lower_path(path: Path, builder: &mut HirModuleBuilder) -> Result<untyped::ItemPath, LowerToHirError> {
-    let first_segment = path.segment().ok_or_else(|| LowerToHirError::IncompletePath)?;
+    let first_segment = path.segment().ok_or(LowerToHirError::IncompletePath)?;
     let first_segment = lower_path_segment(first_segment, builder, builder.current_file(), true)?;
 
     Ok(untyped::ItemPath::new(
@@ -303,25 +854,38 @@ fn lower_path(path: Path, builder: &mut HirModuleBuilder) -> Result<untyped::Ite
 }
 
 fn lower_path_segment(path_segment: PathSegment, builder: &mut HirModuleBuilder, scope: FileId, local: bool) -> Result<untyped::ItemPathSegment, LowerToHirError> {
-    let name = path_segment.name().ok_or_else(|| LowerToHirError::IncompletePath)?;
+    let name = path_segment.name().ok_or(LowerToHirError::IncompletePath)?;
+    let span = name.syntax().text_range();
     let name = builder.intern_name(name.syntax().to_string());
 
+    // the module is resolved first so a path into a type like `foo::Light::new` fails before the item is checked
+    let next_scope = match path_segment.segment() {
+        Some(_) => Some(builder.lookup_module_file(scope, name).ok_or(LowerToHirError::IncompletePath)?),
+        None => None,
+    };
+
     let item = if local {
-        builder.lookup_item(name).ok_or_else(|| LowerToHirError::IncompletePath)?
+        builder.lookup_item(name).ok_or(LowerToHirError::IncompletePath)?
     } else {
-        builder.lookup_item_in(name, scope).ok_or_else(|| LowerToHirError::IncompletePath)?
+        builder.lookup_item_in(name, scope, span).ok_or(LowerToHirError::IncompletePath)?
     };
 
-    let next_segment = if let Some(next) = path_segment.segment() {
-        let next_scope = builder.lookup_module_file(scope, name).ok_or_else(|| LowerToHirError::IncompletePath)?;
-        Some(Box::new(lower_path_segment(next, builder, next_scope, false)?))
-    } else {
-        None
+    let mut generic_arguments = Vec::new();
+    if let Some(generic_argument_list) = path_segment.generic_argument_list() {
+        for argument in generic_argument_list.paths() {
+            generic_arguments.push(lower_path(argument, builder)?);
+        }
+    }
+
+    let next_segment = match (path_segment.segment(), next_scope) {
+        (Some(next), Some(next_scope)) => Some(Box::new(lower_path_segment(next, builder, next_scope, false)?)),
+        _ => None,
     };
 
     Ok(untyped::ItemPathSegment::new(
         name,
         item,
+        generic_arguments,
         next_segment,
         path_segment.syntax().text_range(),
     ))
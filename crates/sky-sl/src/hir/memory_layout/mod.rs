@@ -0,0 +1,237 @@
+use crate::{
+    hir::{type_check::{resolve_type, Env, ScalarTy, StructTy, Ty}, untyped, HirDatabase},
+    intern::Name,
+};
+use thiserror::Error;
+
+/// The rules used to lay out data shared with the host, see the Vulkan specification on offset and stride assignment
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LayoutRules {
+    /// Arrays and structs are aligned to 16 bytes, the default for uniform bindings
+    Std140,
+
+    /// Like std140 without rounding arrays and structs to 16 bytes, the default for storage bindings
+    Std430,
+
+    /// Every type is aligned to its scalar element, e.g. a `vec3<f32>` takes 12 bytes and is aligned to 4
+    Scalar,
+}
+
+impl LayoutRules {
+    /// The rules a binding is laid out with unless requested otherwise, `None` for opaque bindings and workgroup
+    /// memory, which is not shared with the host
+    pub fn for_binding(binding_kind: untyped::BindingKind) -> Option<Self> {
+        match binding_kind {
+            untyped::BindingKind::Uniform => Some(LayoutRules::Std140),
+            untyped::BindingKind::Storage | untyped::BindingKind::PushConstant => Some(LayoutRules::Std430),
+            untyped::BindingKind::Image | untyped::BindingKind::Sampler | untyped::BindingKind::Workgroup => None,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "std140" => Some(LayoutRules::Std140),
+            "std430" => Some(LayoutRules::Std430),
+            "scalar" => Some(LayoutRules::Scalar),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutRules::Std140 => "std140",
+            LayoutRules::Std430 => "std430",
+            LayoutRules::Scalar => "scalar",
+        }
+    }
+}
+
+/// The size and alignment of a type in bytes
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TypeLayout {
+    /// The size without trailing padding, 0 for runtime-sized arrays
+    pub size: u32,
+    pub alignment: u32,
+
+    /// The distance between elements of an array or columns of a matrix
+    pub stride: Option<u32>,
+}
+
+impl TypeLayout {
+    /// Used for types that can't be laid out so the remaining members still get an offset
+    const EMPTY: TypeLayout = TypeLayout { size: 0, alignment: 1, stride: None };
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StructLayout {
+    /// The size including padding to the alignment, for structs ending in a runtime-sized array the size without the array
+    pub size: u32,
+    pub alignment: u32,
+    pub members: Vec<MemberLayout>,
+
+    /// Members that can't be laid out, including members of nested structs
+    pub errors: Vec<MemoryLayoutError>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MemberLayout {
+    pub name: Name,
+    pub ty: Ty,
+    pub offset: u32,
+    pub layout: TypeLayout,
+}
+
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum MemoryLayoutError {
+    #[error("`{member}` of type `{ty}` has no memory layout and can't be shared with the host")]
+    InvalidMemberType { member: String, ty: String },
+
+    #[error("`{name}` contains itself and can't be laid out")]
+    RecursiveStruct { name: String },
+}
+
+/// Lays out the members of a struct in declaration order, see `HirDatabase::struct_layout`
+pub fn lay_out_struct(db: &dyn HirDatabase, struct_ty: StructTy, rules: LayoutRules) -> StructLayout {
+    let module = db.get_hir(struct_ty.file);
+    let structure = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Struct(structure) if structure.name == struct_ty.name => Some(structure),
+        _ => None,
+    });
+
+    let mut layout = StructLayout {
+        size: 0,
+        alignment: 1,
+        members: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let structure = match structure {
+        Some(structure) => structure,
+        None => return layout,
+    };
+
+    // members are resolved in the struct's own module, their errors are reported there
+    let mut env = Env::new(db, struct_ty.file);
+    let struct_name = db.lookup_intern_name(struct_ty.name);
+
+    let mut offset = 0;
+    for member in &structure.members {
+        let ty = resolve_type(&member.type_reference, &mut env);
+        let path = format!("{}::{}", struct_name, db.lookup_intern_name(member.name));
+        let member_layout = type_layout(db, ty, rules, &path, &mut layout.errors);
+
+        offset = round_up(offset, member_layout.alignment);
+        layout.alignment = layout.alignment.max(member_layout.alignment);
+        layout.members.push(MemberLayout {
+            name: member.name,
+            ty,
+            offset,
+            layout: member_layout,
+        });
+
+        offset += member_layout.size;
+    }
+
+    if rules == LayoutRules::Std140 {
+        layout.alignment = round_up(layout.alignment, 16);
+    }
+
+    layout.size = round_up(offset, layout.alignment);
+    layout
+}
+
+/// The layout of a struct containing itself, the recursion is reported when type checking the struct
+pub fn recursive_struct_layout(db: &dyn HirDatabase, struct_ty: StructTy) -> StructLayout {
+    StructLayout {
+        size: 0,
+        alignment: 1,
+        members: Vec::new(),
+        errors: vec![MemoryLayoutError::RecursiveStruct { name: db.lookup_intern_name(struct_ty.name) }],
+    }
+}
+
+/// Computes the layout of a type stored in `member`, the path of a struct member or binding used in errors
+pub fn type_layout(db: &dyn HirDatabase, ty: Ty, rules: LayoutRules, member: &str, errors: &mut Vec<MemoryLayoutError>) -> TypeLayout {
+    let layout = match ty {
+        Ty::Scalar(scalar) => scalar_layout(scalar),
+        Ty::Vector(scalar, components) => scalar_layout(scalar).map(|scalar| vector_layout(scalar, components, rules)),
+        Ty::Matrix(scalar, columns, rows) => scalar_layout(scalar).map(|scalar| {
+            let column = vector_layout(scalar, rows, rules);
+            array_layout(column, Some(columns as u32), rules)
+        }),
+        // values of enums are their `u32` discriminants
+        Ty::Enum(_) => scalar_layout(ScalarTy::Int { signed: false, width: 32 }),
+        Ty::Atomic(scalar) => scalar_layout(scalar),
+        Ty::Array(array_ty) => {
+            let array = db.lookup_intern_array_ty(array_ty);
+            let element = type_layout(db, array.element, rules, member, errors);
+            Some(array_layout(element, array.length, rules))
+        },
+        Ty::Struct(struct_ty) => {
+            let struct_layout = db.struct_layout(struct_ty, rules);
+            errors.extend(struct_layout.errors.iter().cloned());
+            Some(TypeLayout {
+                size: struct_layout.size,
+                alignment: struct_layout.alignment,
+                stride: None,
+            })
+        },
+        // already reported while type checking
+        Ty::Error => Some(TypeLayout::EMPTY),
+        Ty::Unit | Ty::Texture(_) | Ty::Sampler { .. } | Ty::Layout(_) | Ty::Generic(_) => None,
+    };
+
+    layout.unwrap_or_else(|| {
+        errors.push(MemoryLayoutError::InvalidMemberType {
+            member: member.to_string(),
+            ty: ty.display(db),
+        });
+        TypeLayout::EMPTY
+    })
+}
+
+/// Booleans have no defined representation in memory shared with the host
+fn scalar_layout(scalar: ScalarTy) -> Option<TypeLayout> {
+    let size = match scalar {
+        ScalarTy::Bool => return None,
+        ScalarTy::Int { width, .. } | ScalarTy::Float { width } => width as u32 / 8,
+    };
+
+    Some(TypeLayout { size, alignment: size, stride: None })
+}
+
+fn vector_layout(scalar: TypeLayout, components: u8, rules: LayoutRules) -> TypeLayout {
+    // a `vec3` is aligned like a `vec4` unless laid out with scalar rules
+    let alignment = match (rules, components) {
+        (LayoutRules::Scalar, _) => scalar.alignment,
+        (_, 2) => scalar.alignment * 2,
+        _ => scalar.alignment * 4,
+    };
+
+    TypeLayout {
+        size: scalar.size * components as u32,
+        alignment,
+        stride: None,
+    }
+}
+
+/// Arrays and matrices, which are laid out as arrays of their columns
+fn array_layout(element: TypeLayout, length: Option<u32>, rules: LayoutRules) -> TypeLayout {
+    let (alignment, stride) = match rules {
+        LayoutRules::Std140 => {
+            let alignment = round_up(element.alignment, 16);
+            (alignment, round_up(element.size, alignment))
+        },
+        LayoutRules::Std430 | LayoutRules::Scalar => (element.alignment, round_up(element.size, element.alignment)),
+    };
+
+    TypeLayout {
+        size: stride * length.unwrap_or(0),
+        alignment,
+        stride: Some(stride),
+    }
+}
+
+fn round_up(value: u32, alignment: u32) -> u32 {
+    value.div_ceil(alignment) * alignment
+}
@@ -2,8 +2,12 @@ mod db;
 
 pub use db::*;
 
+pub mod const_eval;
+pub mod intrinsic;
 pub mod lower;
+pub mod memory_layout;
 pub mod named;
+pub mod pipeline;
 pub mod primitive;
 pub mod symbol;
 pub mod type_check;
@@ -1,11 +1,23 @@
-use super::{primitive::Primitive, symbol::Symbol};
+use super::{intrinsic::Intrinsic, primitive::Primitive, symbol::Symbol};
 use crate::{fs::FileId, hir::HirDatabase, intern::Name, syn::ast::*};
+use rowan::TextRange;
 use std::{collections::HashMap, sync::Arc};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum NamedItemKind {
     Symbol(Arc<Symbol>),
     Primitive(Arc<Primitive>),
+    Intrinsic(Arc<Intrinsic>),
+}
+
+impl NamedItemKind {
+    /// Checks if the item can be named in the given file, primitives and intrinsics are visible everywhere
+    pub fn is_visible_from(&self, db: &dyn HirDatabase, file: FileId) -> bool {
+        match self {
+            NamedItemKind::Symbol(symbol) => symbol.is_visible_from(db, file),
+            NamedItemKind::Primitive(_) | NamedItemKind::Intrinsic(_) => true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -42,11 +54,23 @@ impl<'a> NamedScopeBuilder<'a> {
         }
     }
 
+    pub fn add_intrinsics(&mut self) {
+        for intrinsic in self.db.get_intrinsics().iter() {
+            self.items.insert(intrinsic.name, NamedItemKind::Intrinsic(intrinsic.clone()));
+        }
+    }
+
     pub fn import_usings(&mut self, file: FileId) {
         let ast = self.db.get_ast(file);
 
-        for _using in ast.tree().uses() {
-            // TODO
+        for using in ast.tree().uses() {
+            if let Some(use_tree) = using.use_tree() {
+                for import in resolve_use_tree(self.db, file, use_tree) {
+                    if let Some(item) = import.item {
+                        self.items.insert(import.name, item);
+                    }
+                }
+            }
         }
     }
 
@@ -60,3 +84,73 @@ impl<'a> NamedScopeBuilder<'a> {
         NamedScope::new(Arc::new(self.items))
     }
 }
+
+/// A name brought into scope by a `use` declaration
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Import {
+    pub name: Name,
+
+    /// The imported item, `None` if the path could not be resolved
+    pub item: Option<NamedItemKind>,
+    pub span: TextRange,
+}
+
+/// Resolves all names imported by a use tree like `foo::{bar, baz::*}` declared in `file`
+pub fn resolve_use_tree(db: &dyn HirDatabase, file: FileId, use_tree: UseTree) -> Vec<Import> {
+    let mut imports = Vec::new();
+    resolve_use_tree_in(db, file, file, use_tree, &mut imports);
+    imports
+}
+
+fn resolve_use_tree_in(db: &dyn HirDatabase, file: FileId, module: FileId, use_tree: UseTree, imports: &mut Vec<Import>) {
+    let segment = match use_tree.segment() {
+        Some(segment) => segment,
+        None => return,
+    };
+
+    let span = segment.syntax().text_range();
+    let name = db.intern_name(segment.syntax().to_string().trim().to_string());
+
+    let is_leaf = use_tree.use_tree().is_none() && use_tree.use_all().is_none() && use_tree.use_group().is_none();
+    if is_leaf {
+        let item = if segment.is_package() {
+            None
+        } else {
+            db.get_import_scope(module).lookup(name)
+        };
+
+        imports.push(Import { name, item, span });
+        return;
+    }
+
+    let next = if segment.is_package() {
+        db.package_root(file)
+    } else {
+        db.child_module(module, name)
+    };
+
+    let next = match next {
+        Some(next) => next,
+        None => {
+            imports.push(Import { name, item: None, span });
+            return;
+        },
+    };
+
+    if let Some(nested) = use_tree.use_tree() {
+        resolve_use_tree_in(db, file, next, nested, imports);
+    } else if use_tree.use_all().is_some() {
+        // a glob only imports what the importing module can see
+        for symbol in db.get_symbols(next).iter().filter(|symbol| symbol.is_visible_from(db, file)) {
+            imports.push(Import {
+                name: symbol.name,
+                item: Some(NamedItemKind::Symbol(symbol.clone())),
+                span,
+            });
+        }
+    } else if let Some(group) = use_tree.use_group() {
+        for nested in group.use_trees() {
+            resolve_use_tree_in(db, file, next, nested, imports);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use super::{find_entry_point, EntryPointId};
+use crate::{
+    fs::FileId,
+    hir::{type_check::{ScalarTy, StructTy, Ty}, typed, untyped::Interpolation, HirDatabase},
+    intern::Name,
+};
+use rowan::TextRange;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// The values passed from a vertex to a fragment entry point, see `HirDatabase::stage_interface`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StageInterface {
+    /// The members of the struct returned by the vertex entry point
+    pub outputs: Vec<InterfaceVariable>,
+
+    /// The members of the struct arguments and the other non-layout arguments of the fragment entry point
+    pub inputs: Vec<InterfaceVariable>,
+    pub errors: Vec<InterfaceMismatch>,
+}
+
+/// A member of a struct or an argument of the fragment entry point passed between stages
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InterfaceVariable {
+    pub name: Name,
+    pub location: u32,
+
+    /// Whether the location was assigned automatically because the member has no valid `location(...)`
+    pub automatic_location: bool,
+    pub ty: Ty,
+
+    /// The explicit interpolation or the default for the type
+    pub interpolation: Interpolation,
+
+    /// The struct declaring the member, `None` for arguments of the fragment entry point
+    pub structure: Option<StructTy>,
+    pub file: FileId,
+    pub span: TextRange,
+}
+
+/// A fragment input that doesn't match the vertex output of the same name
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InterfaceMismatch {
+    pub input: InterfaceVariable,
+    pub error: InterfaceError,
+}
+
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum InterfaceError {
+    #[error("`{name}` is not an output of the vertex stage")]
+    MissingOutput { name: String },
+
+    #[error("`{name}` is not an output of the vertex stage, which writes `{output}` to location {location}")]
+    RenamedOutput { name: String, location: u32, output: String },
+
+    #[error("`{name}` is read from location {input} but the vertex stage writes it to location {output}")]
+    LocationMismatch { name: String, input: u32, output: u32 },
+
+    #[error("`{name}` has type `{input}` but the vertex stage writes a `{output}`")]
+    TypeMismatch { name: String, input: String, output: String },
+
+    #[error("`{name}` uses {input} interpolation but the vertex stage uses {output} interpolation")]
+    InterpolationMismatch { name: String, input: &'static str, output: &'static str },
+}
+
+/// Matches the fragment inputs with the vertex outputs of the same name. Every input has to be written by the
+/// vertex stage at the same location, with the same type and interpolation, outputs that are not read are allowed.
+pub fn match_stage_interface(db: &dyn HirDatabase, vertex: EntryPointId, fragment: EntryPointId) -> StageInterface {
+    // a vertex stage returning a vector only writes the position
+    let outputs = find_entry_point(db, vertex)
+        .map(|function| match function.signature.return_type {
+            Ty::Struct(struct_ty) => interface_variables(struct_members(db, struct_ty)),
+            _ => Vec::new(),
+        })
+        .unwrap_or_default();
+
+    // arguments that are not structs are passed like members without a `location(...)`
+    let inputs = find_entry_point(db, fragment)
+        .map(|function| {
+            let members = function.signature.arguments.into_iter()
+                .flat_map(|argument| match argument.ty {
+                    Ty::Struct(struct_ty) => struct_members(db, struct_ty),
+                    Ty::Layout(_) | Ty::Error => Vec::new(),
+                    ty => vec![(None, fragment.file, typed::StructMember::new(argument.name, ty, None, None, argument.span))],
+                })
+                .collect();
+
+            interface_variables(members)
+        })
+        .unwrap_or_default();
+
+    let errors = inputs.iter()
+        .filter_map(|input| {
+            let error = check_input(db, input, &outputs)?;
+            Some(InterfaceMismatch { input: *input, error })
+        })
+        .collect();
+
+    StageInterface {
+        outputs,
+        inputs,
+        errors,
+    }
+}
+
+/// Assigns the lowest location not used explicitly to members without one
+fn interface_variables(members: Vec<(Option<StructTy>, FileId, typed::StructMember)>) -> Vec<InterfaceVariable> {
+    let explicit: HashSet<u32> = members.iter().filter_map(|(_, _, member)| member.location).collect();
+    let mut next = 0;
+
+    members.into_iter()
+        .map(|(structure, file, member)| {
+            let location = match member.location {
+                Some(location) => location,
+                None => {
+                    while explicit.contains(&next) {
+                        next += 1;
+                    }
+
+                    let location = next;
+                    next += 1;
+                    location
+                },
+            };
+
+            InterfaceVariable {
+                name: member.name,
+                location,
+                automatic_location: member.location.is_none(),
+                ty: member.ty,
+                interpolation: member.interpolation.unwrap_or_else(|| default_interpolation(member.ty)),
+                structure,
+                file,
+                span: member.span,
+            }
+        })
+        .collect()
+}
+
+fn struct_members(db: &dyn HirDatabase, struct_ty: StructTy) -> Vec<(Option<StructTy>, FileId, typed::StructMember)> {
+    db.get_typed_hir(struct_ty.file).items.into_iter()
+        .find_map(|item| match item {
+            typed::ItemKind::Struct(structure) if structure.name == struct_ty.name => Some(structure.members),
+            _ => None,
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|member| (Some(struct_ty), struct_ty.file, member))
+        .collect()
+}
+
+/// Integers can't be interpolated, everything else is interpolated perspective correct
+pub fn default_interpolation(ty: Ty) -> Interpolation {
+    match ty {
+        Ty::Enum(_) => Interpolation::Flat,
+        ty if ty.element().is_some_and(ScalarTy::is_integer) => Interpolation::Flat,
+        _ => Interpolation::Perspective,
+    }
+}
+
+fn check_input(db: &dyn HirDatabase, input: &InterfaceVariable, outputs: &[InterfaceVariable]) -> Option<InterfaceError> {
+    let name = db.lookup_intern_name(input.name);
+
+    let output = match outputs.iter().find(|output| output.name == input.name) {
+        Some(output) => output,
+        None => {
+            // most likely a renamed member if the vertex stage writes something else to the location
+            let error = match outputs.iter().find(|output| output.location == input.location) {
+                Some(output) => InterfaceError::RenamedOutput {
+                    name,
+                    location: output.location,
+                    output: db.lookup_intern_name(output.name),
+                },
+                None => InterfaceError::MissingOutput { name },
+            };
+            return Some(error);
+        },
+    };
+
+    if output.location != input.location {
+        return Some(InterfaceError::LocationMismatch {
+            name,
+            input: input.location,
+            output: output.location,
+        });
+    }
+
+    if output.ty != input.ty {
+        return Some(InterfaceError::TypeMismatch {
+            name,
+            input: input.ty.display(db),
+            output: output.ty.display(db),
+        });
+    }
+
+    if output.interpolation != input.interpolation {
+        return Some(InterfaceError::InterpolationMismatch {
+            name,
+            input: input.interpolation.name(),
+            output: output.interpolation.name(),
+        });
+    }
+
+    None
+}
@@ -0,0 +1,199 @@
+use crate::{
+    fs::FileId,
+    hir::{memory_layout::LayoutRules, type_check::{assign_set_indices, LayoutTy, Ty}, typed, untyped::{AccessMode, BindingKind}, HirDatabase},
+    intern::Name,
+};
+use serde::Serialize;
+use thiserror::Error;
+
+mod interface;
+
+pub use interface::*;
+
+/// A function identified by the file it is declared in and its name, only entry points contribute bindings
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct EntryPointId {
+    pub file: FileId,
+    pub name: Name,
+}
+
+/// The merged bindings of entry points sharing a pipeline, see `HirDatabase::pipeline_layout`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PipelineLayout {
+    /// Ordered by set and binding index
+    pub bindings: Vec<PipelineBinding>,
+    pub errors: Vec<PipelineError>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PipelineBinding {
+    pub set: u32,
+    pub binding: u32,
+    pub kind: BindingKind,
+
+    /// The access mode of a storage binding, read and write if any of the entry points may write it
+    pub access: Option<AccessMode>,
+    pub ty: Ty,
+
+    /// The layout declaring the binding, the first one if the entry points use different layouts
+    pub layout: LayoutTy,
+    pub member: Name,
+}
+
+#[derive(Debug, Error, Clone, Eq, PartialEq, Serialize)]
+pub enum PipelineError {
+    #[error("Set {set} binding {binding} is bound as `{first_kind}` by `{first}` but as `{second_kind}` by `{second}`")]
+    KindMismatch { set: u32, binding: u32, first: String, first_kind: &'static str, second: String, second_kind: &'static str },
+
+    #[error("Set {set} binding {binding} has type `{first_ty}` in `{first}` but `{second_ty}` in `{second}`")]
+    TypeMismatch { set: u32, binding: u32, first: String, first_ty: String, second: String, second_ty: String },
+
+    #[error("Set {set} binding {binding} has different {rules} layouts in `{first}` of type `{first_ty}` and `{second}` of type `{second_ty}`")]
+    IncompatibleLayout { set: u32, binding: u32, rules: &'static str, first: String, first_ty: String, second: String, second_ty: String },
+}
+
+/// Merges the bindings of the layouts passed to the entry points, functions without a stage are skipped.
+/// Bindings with the same set and binding index must have the same kind and a type with the same memory layout
+pub fn merge_pipeline_layout(db: &dyn HirDatabase, entry_points: &[EntryPointId]) -> PipelineLayout {
+    let mut pipeline = PipelineLayout {
+        bindings: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for entry_point in entry_points {
+        let layouts = entry_point_layouts(db, *entry_point);
+        let sets = assign_set_indices(db, &layouts);
+
+        for (layout_ty, set) in layouts.into_iter().zip(sets) {
+            for member in layout_members(db, layout_ty) {
+                // invalid binding indices are reported when type checking the layout
+                let binding = match member.binding_index {
+                    Some(binding) => binding,
+                    None => continue,
+                };
+
+                let binding = PipelineBinding {
+                    set,
+                    binding,
+                    kind: member.binding_kind,
+                    access: member.access,
+                    ty: member.ty,
+                    layout: layout_ty,
+                    member: member.name,
+                };
+
+                match pipeline.bindings.iter_mut().find(|other| other.set == set && other.binding == binding.binding) {
+                    Some(first) => {
+                        if let Some(error) = check_compatible(db, first, &binding) {
+                            if !pipeline.errors.contains(&error) {
+                                pipeline.errors.push(error);
+                            }
+                        } else if binding.access == Some(AccessMode::ReadWrite) {
+                            first.access = binding.access;
+                        }
+                    },
+                    None => pipeline.bindings.push(binding),
+                }
+            }
+        }
+    }
+
+    pipeline.bindings.sort_by_key(|binding| (binding.set, binding.binding));
+    pipeline
+}
+
+fn find_entry_point(db: &dyn HirDatabase, entry_point: EntryPointId) -> Option<typed::FunctionKind> {
+    db.get_typed_hir(entry_point.file).items.into_iter().find_map(|item| match item {
+        typed::ItemKind::Function(function) if function.signature.name == entry_point.name && function.stage.is_some() => Some(function),
+        _ => None,
+    })
+}
+
+fn entry_point_layouts(db: &dyn HirDatabase, entry_point: EntryPointId) -> Vec<LayoutTy> {
+    find_entry_point(db, entry_point).into_iter()
+        .flat_map(|function| function.signature.arguments)
+        .filter_map(|argument| match argument.ty {
+            Ty::Layout(layout_ty) => Some(layout_ty),
+            _ => None,
+        })
+        .collect()
+}
+
+fn layout_members(db: &dyn HirDatabase, layout_ty: LayoutTy) -> Vec<typed::LayoutMember> {
+    db.get_typed_hir(layout_ty.file).items.into_iter()
+        .find_map(|item| match item {
+            typed::ItemKind::Layout(layout) if layout.name == layout_ty.name => Some(layout.members),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+fn check_compatible(db: &dyn HirDatabase, first: &PipelineBinding, second: &PipelineBinding) -> Option<PipelineError> {
+    let display = |binding: &PipelineBinding| format!("{}::{}", db.lookup_intern_name(binding.layout.name), db.lookup_intern_name(binding.member));
+
+    if first.kind != second.kind {
+        return Some(PipelineError::KindMismatch {
+            set: first.set,
+            binding: first.binding,
+            first: display(first),
+            first_kind: first.kind.keyword(),
+            second: display(second),
+            second_kind: second.kind.keyword(),
+        });
+    }
+
+    let rules = LayoutRules::for_binding(first.kind);
+    let compatible = match rules {
+        Some(rules) => has_same_layout(db, first.ty, second.ty, rules),
+        None => first.ty == second.ty,
+    };
+
+    if compatible {
+        return None;
+    }
+
+    let error = match (rules, first.ty, second.ty) {
+        (Some(rules), Ty::Struct(_) | Ty::Array(_), Ty::Struct(_) | Ty::Array(_)) => PipelineError::IncompatibleLayout {
+            set: first.set,
+            binding: first.binding,
+            rules: rules.name(),
+            first: display(first),
+            first_ty: first.ty.display(db),
+            second: display(second),
+            second_ty: second.ty.display(db),
+        },
+        _ => PipelineError::TypeMismatch {
+            set: first.set,
+            binding: first.binding,
+            first: display(first),
+            first_ty: first.ty.display(db),
+            second: display(second),
+            second_ty: second.ty.display(db),
+        },
+    };
+
+    Some(error)
+}
+
+/// Different structs are compatible if their members have the same offsets and compatible types, names are ignored
+fn has_same_layout(db: &dyn HirDatabase, first: Ty, second: Ty, rules: LayoutRules) -> bool {
+    match (first, second) {
+        _ if first == second => true,
+        (Ty::Struct(first), Ty::Struct(second)) => {
+            let first = db.struct_layout(first, rules);
+            let second = db.struct_layout(second, rules);
+
+            first.size == second.size
+                && first.members.len() == second.members.len()
+                && first.members.iter().zip(&second.members).all(|(first, second)| {
+                    first.offset == second.offset && has_same_layout(db, first.ty, second.ty, rules)
+                })
+        },
+        (Ty::Array(first), Ty::Array(second)) => {
+            let first = db.lookup_intern_array_ty(first);
+            let second = db.lookup_intern_array_ty(second);
+            first.length == second.length && has_same_layout(db, first.element, second.element, rules)
+        },
+        _ => false,
+    }
+}
@@ -4,8 +4,131 @@ use std::sync::Arc;
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum PrimitiveKind {
     Boolean,
-    Integer,
-    FloatingPoint,
+    Integer { signed: bool, width: u8 },
+    FloatingPoint { width: u8 },
+
+    /// A vector with 2 to 4 components, e.g. `vec3<f32>`
+    Vector { components: u8 },
+
+    /// A column-major matrix with 2 to 4 columns and rows, e.g. `mat4x3<f32>` (4 columns, 3 rows)
+    Matrix { columns: u8, rows: u8 },
+
+    /// A texture, e.g. `texture_2d<f32>`, `texture_depth_cube` or `texture_storage_2d<rgba8unorm>`
+    Texture { dimension: TextureDimension, arrayed: bool, class: TextureClass },
+
+    /// The texel format of a storage texture, e.g. the `rgba8unorm` in `texture_storage_2d<rgba8unorm>`
+    TexelFormat(TexelFormat),
+
+    /// A texture sampler, `sampler_state` or `sampler_comparison_state`
+    Sampler { comparison: bool },
+
+    /// A 32 bit integer only accessed through atomic functions, e.g. `atomic<u32>`
+    Atomic,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TextureDimension {
+    D1,
+    D2,
+    D3,
+    Cube,
+}
+
+impl TextureDimension {
+    pub fn name(self) -> &'static str {
+        match self {
+            TextureDimension::D1 => "1d",
+            TextureDimension::D2 => "2d",
+            TextureDimension::D3 => "3d",
+            TextureDimension::Cube => "cube",
+        }
+    }
+}
+
+/// What a texture holds and how it is accessed
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TextureClass {
+    /// Sampled through a sampler, generic over the sampled element type
+    Sampled,
+
+    /// Holds depth values, can be sampled with a comparison sampler
+    Depth,
+
+    /// Read and written directly, generic over the texel format
+    Storage,
+}
+
+impl TextureClass {
+    /// The part of the texture name after `texture_`, e.g. `depth_` for `texture_depth_2d`
+    pub fn prefix(self) -> &'static str {
+        match self {
+            TextureClass::Sampled => "",
+            TextureClass::Depth => "depth_",
+            TextureClass::Storage => "storage_",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TexelFormat {
+    Rgba8Unorm,
+    Rgba8Snorm,
+    Rgba8Uint,
+    Rgba8Sint,
+    Rgba16Uint,
+    Rgba16Sint,
+    Rgba16Float,
+    R32Uint,
+    R32Sint,
+    R32Float,
+    Rg32Uint,
+    Rg32Sint,
+    Rg32Float,
+    Rgba32Uint,
+    Rgba32Sint,
+    Rgba32Float,
+}
+
+impl TexelFormat {
+    pub const ALL: [TexelFormat; 16] = [
+        TexelFormat::Rgba8Unorm,
+        TexelFormat::Rgba8Snorm,
+        TexelFormat::Rgba8Uint,
+        TexelFormat::Rgba8Sint,
+        TexelFormat::Rgba16Uint,
+        TexelFormat::Rgba16Sint,
+        TexelFormat::Rgba16Float,
+        TexelFormat::R32Uint,
+        TexelFormat::R32Sint,
+        TexelFormat::R32Float,
+        TexelFormat::Rg32Uint,
+        TexelFormat::Rg32Sint,
+        TexelFormat::Rg32Float,
+        TexelFormat::Rgba32Uint,
+        TexelFormat::Rgba32Sint,
+        TexelFormat::Rgba32Float,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TexelFormat::Rgba8Unorm => "rgba8unorm",
+            TexelFormat::Rgba8Snorm => "rgba8snorm",
+            TexelFormat::Rgba8Uint => "rgba8uint",
+            TexelFormat::Rgba8Sint => "rgba8sint",
+            TexelFormat::Rgba16Uint => "rgba16uint",
+            TexelFormat::Rgba16Sint => "rgba16sint",
+            TexelFormat::Rgba16Float => "rgba16float",
+            TexelFormat::R32Uint => "r32uint",
+            TexelFormat::R32Sint => "r32sint",
+            TexelFormat::R32Float => "r32float",
+            TexelFormat::Rg32Uint => "rg32uint",
+            TexelFormat::Rg32Sint => "rg32sint",
+            TexelFormat::Rg32Float => "rg32float",
+            TexelFormat::Rgba32Uint => "rgba32uint",
+            TexelFormat::Rgba32Sint => "rgba32sint",
+            TexelFormat::Rgba32Float => "rgba32float",
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
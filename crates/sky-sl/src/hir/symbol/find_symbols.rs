@@ -1,10 +1,17 @@
-use crate::{fs::FileId, hir::HirDatabase, syn::ast::*};
+use crate::{fs::FileId, hir::HirDatabase, intern::Name, syn::ast::*};
+use std::collections::HashMap;
 
-use super::{Symbol, SymbolKind, SymbolList, SymbolMember, SymbolMemberKind, builder::SymbolListBuilder};
+use super::{Symbol, SymbolKind, SymbolList, SymbolMember, SymbolMemberKind, Visibility, builder::SymbolListBuilder};
 
 pub fn find_symbols(file_id: FileId, ast: Root, db: &dyn HirDatabase) -> SymbolList {
     let mut builder = SymbolListBuilder::new(file_id, db);
 
+    // functions of impl blocks are members of the struct they are implemented for
+    let mut methods = HashMap::new();
+    for impl_definition in ast.impl_definitions() {
+        visit_impl_definition(impl_definition, &mut builder, &mut methods);
+    }
+
     for item in ast.module_items() {
         match item.kind() {
             ModuleItemKind::ModuleDeclaration(module_declaration) => {
@@ -14,12 +21,22 @@ pub fn find_symbols(file_id: FileId, ast: Root, db: &dyn HirDatabase) -> SymbolL
                 visit_function_definition(function_definition, &mut builder)
             }
             ModuleItemKind::StructDefinition(struct_definition) => {
-                visit_struct_definition(struct_definition, &mut builder)
+                visit_struct_definition(struct_definition, &mut builder, &mut methods)
             }
             ModuleItemKind::UseDeclaration(_use_declaration) => {}
             ModuleItemKind::LayoutDefinition(layout_definition) => {
                 visit_layout_definition(layout_definition, &mut builder)
             }
+            ModuleItemKind::ConstDefinition(const_definition) => {
+                visit_const_definition(const_definition, &mut builder)
+            }
+            ModuleItemKind::EnumDefinition(enum_definition) => {
+                visit_enum_definition(enum_definition, &mut builder)
+            }
+            ModuleItemKind::ImplDefinition(_impl_definition) => {}
+            ModuleItemKind::TypeAliasDefinition(type_alias_definition) => {
+                visit_type_alias_definition(type_alias_definition, &mut builder)
+            }
         }
     }
 
@@ -41,6 +58,8 @@ fn visit_module_declaration(
             span,
             selection_span,
             SymbolKind::Module,
+            // module declarations have no visibility, their items are restricted individually
+            Visibility::Public,
             Vec::new(),
         ));
     }
@@ -60,6 +79,49 @@ fn visit_function_definition(
             span,
             selection_span,
             SymbolKind::Function,
+            visibility(&function_definition),
+            Vec::new(),
+        ));
+    }
+}
+
+fn visit_const_definition(
+    const_definition: ConstDefinition,
+    builder: &mut SymbolListBuilder,
+) {
+    if let Some(const_name) = const_definition.name() {
+        let name = builder.intern_name(const_name.syntax.to_string());
+        let span = const_definition.syntax.text_range();
+        let selection_span = const_name.syntax.text_range();
+
+        builder.add_symbol(Symbol::new(
+            name,
+            builder.current_file(),
+            span,
+            selection_span,
+            SymbolKind::Value,
+            visibility(&const_definition),
+            Vec::new(),
+        ));
+    }
+}
+
+fn visit_type_alias_definition(
+    type_alias_definition: TypeAliasDefinition,
+    builder: &mut SymbolListBuilder,
+) {
+    if let Some(alias_name) = type_alias_definition.name() {
+        let name = builder.intern_name(alias_name.syntax.to_string());
+        let span = type_alias_definition.syntax.text_range();
+        let selection_span = alias_name.syntax.text_range();
+
+        builder.add_symbol(Symbol::new(
+            name,
+            builder.current_file(),
+            span,
+            selection_span,
+            SymbolKind::TypeAlias,
+            visibility(&type_alias_definition),
             Vec::new(),
         ));
     }
@@ -68,6 +130,7 @@ fn visit_function_definition(
 fn visit_struct_definition(
     struct_definition: StructDefinition,
     builder: &mut SymbolListBuilder,
+    methods: &mut HashMap<Name, Vec<SymbolMember>>,
 ) {
     if let Some(struct_name) = struct_definition.name() {
         let name = builder.intern_name(struct_name.syntax.to_string());
@@ -80,6 +143,7 @@ fn visit_struct_definition(
                 visit_struct_member(member, builder, &mut member_list);
             }
         }
+        member_list.extend(methods.remove(&name).unwrap_or_default());
 
         builder.add_symbol(Symbol::new(
             name,
@@ -87,6 +151,7 @@ fn visit_struct_definition(
             span,
             selection_span,
             SymbolKind::Struct,
+            visibility(&struct_definition),
             member_list,
         ));
     }
@@ -107,6 +172,84 @@ fn visit_struct_member(
             span,
             selection_span,
             SymbolMemberKind::Field,
+            visibility(&member),
+        ));
+    }
+}
+
+fn visit_impl_definition(
+    impl_definition: ImplDefinition,
+    builder: &mut SymbolListBuilder,
+    methods: &mut HashMap<Name, Vec<SymbolMember>>,
+) {
+    let (type_name, item_list) = match (impl_definition.name(), impl_definition.item_list()) {
+        (Some(type_name), Some(item_list)) => (type_name, item_list),
+        _ => return,
+    };
+
+    let type_name = builder.intern_name(type_name.syntax.to_string());
+    for function_definition in item_list.functions() {
+        if let Some(function_name) = function_definition.signature().and_then(|s| s.name()) {
+            let name = builder.intern_name(function_name.syntax.to_string());
+            let span = function_definition.syntax.text_range();
+            let selection_span = function_name.syntax.text_range();
+
+            methods.entry(type_name).or_default().push(SymbolMember::new(
+                name,
+                span,
+                selection_span,
+                SymbolMemberKind::Method,
+                visibility(&function_definition),
+            ));
+        }
+    }
+}
+
+fn visit_enum_definition(
+    enum_definition: EnumDefinition,
+    builder: &mut SymbolListBuilder,
+) {
+    if let Some(enum_name) = enum_definition.name() {
+        let name = builder.intern_name(enum_name.syntax.to_string());
+        let span = enum_definition.syntax.text_range();
+        let selection_span = enum_name.syntax.text_range();
+
+        let mut member_list = Vec::new();
+        if let Some(variant_list) = enum_definition.variant_list() {
+            for variant in variant_list.variants() {
+                visit_enum_variant(variant, builder, &mut member_list);
+            }
+        }
+
+        builder.add_symbol(Symbol::new(
+            name,
+            builder.current_file(),
+            span,
+            selection_span,
+            SymbolKind::Enum,
+            visibility(&enum_definition),
+            member_list,
+        ));
+    }
+}
+
+fn visit_enum_variant(
+    variant: EnumVariant,
+    builder: &mut SymbolListBuilder,
+    member_list: &mut Vec<SymbolMember>,
+) {
+    if let Some(variant_name) = variant.name() {
+        let name = builder.intern_name(variant_name.syntax.to_string());
+        let span = variant.syntax().text_range();
+        let selection_span = variant_name.syntax.text_range();
+
+        member_list.push(SymbolMember::new(
+            name,
+            span,
+            selection_span,
+            SymbolMemberKind::Variant,
+            // variants are as visible as their enum
+            Visibility::Public,
         ));
     }
 }
@@ -133,6 +276,7 @@ fn visit_layout_definition(
             span,
             selection_span,
             SymbolKind::Layout,
+            visibility(&layout_definition),
             member_list,
         ));
     }
@@ -153,6 +297,16 @@ fn visit_layout_member(
             span,
             selection_span,
             SymbolMemberKind::Field,
+            // layout members are as visible as their layout
+            Visibility::Public,
         ));
     }
 }
+
+fn visibility(owner: &impl VisibilityOwner) -> Visibility {
+    match owner.visibility() {
+        Some(visibility) if visibility.is_package() => Visibility::Package,
+        Some(_) => Visibility::Public,
+        None => Visibility::Private,
+    }
+}
@@ -1,6 +1,6 @@
 use rowan::TextRange;
 
-use crate::{fs::FileId, intern::Name};
+use crate::{fs::FileId, hir::HirDatabase, intern::Name};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SymbolKind {
@@ -9,6 +9,8 @@ pub enum SymbolKind {
     Struct,
     Value,
     Layout,
+    Enum,
+    TypeAlias,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -18,6 +20,7 @@ pub struct Symbol {
     pub span: TextRange,
     pub selection_span: TextRange,
     pub kind: SymbolKind,
+    pub visibility: Visibility,
     pub member: Vec<SymbolMember>,
 }
 
@@ -28,6 +31,7 @@ impl Symbol {
         span: TextRange,
         selection_span: TextRange,
         kind: SymbolKind,
+        visibility: Visibility,
         member: Vec<SymbolMember>,
     ) -> Self {
         Self {
@@ -36,9 +40,15 @@ impl Symbol {
             span,
             selection_span,
             kind,
+            visibility,
             member,
         }
     }
+
+    /// Checks if the symbol can be named in the given file
+    pub fn is_visible_from(&self, db: &dyn HirDatabase, file: FileId) -> bool {
+        self.visibility.is_visible_from(db, self.file, file)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -47,6 +57,7 @@ pub struct SymbolMember {
     pub span: TextRange,
     pub selection_span: TextRange,
     pub kind: SymbolMemberKind,
+    pub visibility: Visibility,
 }
 
 impl SymbolMember {
@@ -55,12 +66,14 @@ impl SymbolMember {
         span: TextRange,
         selection_span: TextRange,
         kind: SymbolMemberKind,
+        visibility: Visibility,
     ) -> Self {
         Self {
             name,
             span,
             selection_span,
             kind,
+            visibility,
         }
     }
 }
@@ -68,4 +81,38 @@ impl SymbolMember {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SymbolMemberKind {
     Field,
+    Variant,
+    Method,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// Only visible in the module declaring it
+    Private,
+
+    /// Visible in every module of the same package, declared with `pub(package)`
+    Package,
+
+    /// Visible everywhere, declared with `pub`
+    Public,
+}
+
+impl Visibility {
+    /// Checks if something declared in `declared_in` with this visibility can be named in `file`
+    pub fn is_visible_from(self, db: &dyn HirDatabase, declared_in: FileId, file: FileId) -> bool {
+        match self {
+            Visibility::Private => declared_in == file,
+            Visibility::Package => declared_in == file || db.package_root(declared_in) == db.package_root(file),
+            Visibility::Public => true,
+        }
+    }
+
+    /// The scope a restricted visibility limits access to, used in diagnostics
+    pub fn scope(self) -> &'static str {
+        match self {
+            Visibility::Private => "module",
+            Visibility::Package => "package",
+            Visibility::Public => "workspace",
+        }
+    }
 }
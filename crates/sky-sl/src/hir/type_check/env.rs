@@ -1,10 +1,7 @@
-use crate::{hir::{typed, untyped}, intern::Name};
+use super::{Ty, TypeCheckError, TypeCheckWarning};
+use crate::{fs::FileId, hir::{HirDatabase, named::{NamedItemKind, NamedScope}}, intern::Name};
 use std::collections::HashMap;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub struct Ty(u32);
-
-/* TODO
 #[derive(Debug)]
 struct Scope {
     entries: HashMap<Name, Ty>,
@@ -26,31 +23,44 @@ impl Scope {
     }
 }
 
-#[derive(Debug)]
-pub struct Env {
+pub struct Env<'a> {
+    db: &'a dyn HirDatabase,
+    current_file: FileId,
+    items: NamedScope,
     scopes: Vec<Scope>,
-    ty: HashMap<Name, Ty>,
-    ty_i: u32,
+
+    /// The generic parameters of the current function, `Ty::Generic` while checking it generically
+    /// and the generic arguments while checking an instantiation
+    generics: HashMap<Name, Ty>,
+
+    /// The struct of the impl block being checked, its functions are identified by the struct and their name
+    impl_target: Option<Name>,
+    errors: Vec<TypeCheckError>,
+    warnings: Vec<TypeCheckWarning>,
 }
 
-impl Env {
-    pub fn empty() -> Self {
+impl<'a> Env<'a> {
+    pub fn new(db: &'a dyn HirDatabase, current_file: FileId) -> Self {
+        let items = db.get_local_scope(current_file);
+
         Self {
+            db,
+            current_file,
+            items,
             scopes: vec![Scope::new()],
-            ty: HashMap::new(),
-            ty_i: 0,
+            generics: HashMap::new(),
+            impl_target: None,
+            errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
-    pub fn intern_ty(&mut self, ty_name: Name) -> Ty {
-        if let Some(ty) = self.ty.get(&ty_name) {
-            *ty
-        } else {
-            let ty = Ty(self.ty_i);
-            self.ty_i += 1;
-            self.ty.insert(ty_name, ty);
-            ty
-        }
+    pub fn db(&self) -> &'a dyn HirDatabase {
+        self.db
+    }
+
+    pub fn current_file(&self) -> FileId {
+        self.current_file
     }
 
     pub fn push_scope(&mut self) {
@@ -61,10 +71,12 @@ impl Env {
         self.scopes.pop();
     }
 
+    /// Declares a local variable in the innermost scope
     pub fn insert(&mut self, name: Name, ty: Ty) {
         self.scopes.last_mut().unwrap().insert(name, ty);
     }
 
+    /// Looks up a local variable, starting at the innermost scope
     pub fn lookup(&self, name: Name) -> Option<Ty> {
         for scope in self.scopes.iter().rev() {
             if let Some(ty) = scope.lookup(name) {
@@ -74,173 +86,65 @@ impl Env {
 
         None
     }
-}
 
-pub fn infer_module(module: &untyped::Module, env: &mut Env) -> typed::Module {
-    let mut items = Vec::new();
-    let errors = Vec::new();
-
-    for item in &module.items {
-        match item {
-            untyped::ItemKind::Function(function) => {
-                env.push_scope();
-                let function = infer_function(function, env);
-                items.push(typed::ItemKind::Function(function));
-                env.pop_scope();
-            },
-            _ => {},
-        }
+    pub fn set_generics(&mut self, generics: HashMap<Name, Ty>) {
+        self.generics = generics;
     }
 
-    typed::Module::new(items, errors, module.span)
-}
+    pub fn lookup_generic(&self, name: Name) -> Option<Ty> {
+        self.generics.get(&name).copied()
+    }
 
-pub fn infer_function(function: &untyped::FunctionKind, env: &mut Env) -> typed::FunctionKind {
-    let ty = env.intern_ty(function.signature.name);
-    
-    let mut arguments = Vec::new();
-    // add arguments to env
-    for argument in &function.signature.arguments {
-        let ty = env.intern_ty(argument.ty_name);
-        env.insert(argument.name, ty);
-        arguments.push(typed::FunctionArgument::new(
-            argument.name.to_string(),
-            ty,
-            argument.span,
-        ));
-    }
-
-    let return_type = if let Some(return_type) = &function.signature.return_type {
-        env.intern_ty(return_type)
-    } else {
-        env.intern_ty("()")
-    };
-
-    let signature = typed::FunctionSignature::new(
-        function.signature.name,
-        arguments,
-        return_type,
-        function.signature.span,
-    );
-
-    // infer block
-    // TODO block return type
-    let block = infer_block(&function.block, env);
-
-    typed::FunctionKind::new(signature, block, ty, function.span)
-}
+    pub fn set_impl_target(&mut self, impl_target: Option<Name>) {
+        self.impl_target = impl_target;
+    }
 
-pub fn infer_block(block: &untyped::Block, env: &mut Env) -> typed::Block {
-    let mut statements = Vec::new();
-    for statement in &block.statements {
-        let statement = infer_statement(statement, env);
-        statements.push(statement);
+    pub fn impl_target(&self) -> Option<Name> {
+        self.impl_target
     }
 
-    typed::Block::new(statements, block.span)
-}
+    /// Looks up a named item (primitive or symbol) visible in the current file
+    pub fn lookup_item(&self, name: Name) -> Option<NamedItemKind> {
+        self.items.lookup(name)
+    }
 
-pub fn infer_statement(statement: &untyped::StatementKind, env: &mut Env) -> typed::StatementKind {
-    match statement {
-        untyped::StatementKind::Let(let_statement) => {
-            env.push_scope();
-            // infer expression
-            let expr = infer_expression(&let_statement.expression, env);
-            let ty = expr.ty();
-            env.pop_scope();
-
-            // add local variable to the env
-            env.insert(let_statement.name, ty);
-
-            typed::StatementKind::Let(typed::LetStatement::new(
-                let_statement.name,
-                expr,
-                ty,
-                let_statement.span,
-            ))
-        },
-        untyped::StatementKind::Expression(expression_statement) => {
-            // infer expression
-            env.push_scope();
-            let expr = infer_expression(&expression_statement.expression, env);
-            let ty = expr.ty();
-            env.pop_scope();
-
-            typed::StatementKind::Expression(typed::ExpressionStatement::new(
-                expr,
-                ty,
-                expression_statement.span,
-            ))
-        },
+    pub fn name(&self, name: Name) -> String {
+        self.db.lookup_intern_name(name)
     }
-}
 
-pub fn infer_expression(expression: &untyped::ExpressionKind, env: &mut Env) -> typed::ExpressionKind {
-    match expression {
-        untyped::ExpressionKind::LiteralExpression(literal_expression) => {
-            // primitive type
-            // TODO float vs int vs boolean literals
-            let ty = env.intern_ty("int");
-            typed::ExpressionKind::LiteralExpression(typed::LiteralExpression::new(ty, literal_expression.span))
-        },
-        untyped::ExpressionKind::BinaryExpression(binary_expression) => {
-            // infer left
-            let lhs = infer_expression(&binary_expression.lhs, env);
-            // infer right
-            let rhs = infer_expression(&binary_expression.rhs, env);
-
-            // TODO proper inference of operations
-            if lhs.ty() == rhs.ty() {
-                let ty = lhs.ty();
-                typed::ExpressionKind::BinaryExpression(typed::BinaryExpression::new(
-                    Box::new(lhs),
-                    Box::new(rhs),
-                    ty,
-                    binary_expression.span,
-                ))
-            } else {
-                unimplemented!()
-            }
-        },
-        untyped::ExpressionKind::GroupExpression(group_expression) => {
-            infer_expression(&group_expression.inner, env)
-        },
-        untyped::ExpressionKind::CallExpression(call_expression) => {
-            // infer arguments
-            let mut arguments = Vec::new();
-            for argument in &call_expression.arguments {
-                let expr = infer_expression(argument, env);
-                arguments.push(expr);
-            }
+    pub fn display(&self, ty: Ty) -> String {
+        ty.display(self.db)
+    }
 
-            // fn return type
-            todo!()
-        },
-        untyped::ExpressionKind::FieldAccessExpression(_) => {
-            // member type
-            todo!()
-        },
-        untyped::ExpressionKind::IndexExpression(_) => {
-            // indexee type
-            todo!()
-        },
-        untyped::ExpressionKind::PathExpression(path_expression) => {
-            // variables
-            if let Some(ty) = env.lookup(path_expression.path) {
-                typed::ExpressionKind::PathExpression(typed::PathExpression::new(
-                    path_expression.path,
-                    ty,
-                    path_expression.span
-                ))
-            } else {
-                // not found
-                todo!()
-            }
-        },
-        untyped::ExpressionKind::StructExpression(_) => {
-            // struct type
-            todo!()
-        },
+    pub fn add_error(&mut self, error: TypeCheckError) {
+        self.errors.push(error);
+    }
+
+    pub fn add_warning(&mut self, warning: TypeCheckWarning) {
+        self.warnings.push(warning);
+    }
+
+    /// Reports a mismatch unless the types are equal or one of them already failed to type check
+    pub fn expect(&mut self, expected: Ty, found: Ty, span: rowan::TextRange) {
+        if expected != found && !expected.is_error() && !found.is_error() {
+            let error = TypeCheckError::MismatchedTypes {
+                expected: self.display(expected),
+                found: self.display(found),
+                span,
+            };
+            self.add_error(error);
+        }
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn into_errors(self) -> Vec<TypeCheckError> {
+        self.errors
+    }
+
+    pub fn into_diagnostics(self) -> (Vec<TypeCheckError>, Vec<TypeCheckWarning>) {
+        (self.errors, self.warnings)
     }
 }
-*/
@@ -1,4 +1,281 @@
-#[derive(Debug, Eq, PartialEq, Clone)]
+use crate::hir::{const_eval::ConstEvalError, memory_layout::MemoryLayoutError};
+use rowan::TextRange;
+use thiserror::Error;
+
+#[derive(Error, Debug, Eq, PartialEq, Clone)]
 pub enum TypeCheckError {
-    
+
+    #[error("Cannot find value `{name}` in this scope")]
+    UnresolvedName { name: String, span: TextRange },
+
+    #[error("Mismatched types, expected `{expected}` but found `{found}`")]
+    MismatchedTypes { expected: String, found: String, span: TextRange },
+
+    #[error("Cannot apply `{operator}` to `{lhs}` and `{rhs}`")]
+    InvalidBinaryOperands { operator: &'static str, lhs: String, rhs: String, span: TextRange },
+
+    #[error("Cannot apply `{operator}` to `{operand}`")]
+    InvalidUnaryOperand { operator: &'static str, operand: String, span: TextRange },
+
+    #[error("`{name}` is not a type")]
+    NotAType { name: String, span: TextRange },
+
+    #[error("`{name}` expects {expected} generic argument(s) but {found} were given")]
+    InvalidGenericArguments { name: String, expected: usize, found: usize, span: TextRange },
+
+    #[error("`{found}` cannot be used as the element type of `{name}`")]
+    InvalidVectorElement { name: String, found: String, span: TextRange },
+
+    #[error("`{ty}` expects {expected} components but {found} were given")]
+    ConstructorComponentCount { ty: String, expected: usize, found: usize, span: TextRange },
+
+    #[error("No field `{field}` on type `{ty}`")]
+    UnknownField { field: String, ty: String, span: TextRange },
+
+    #[error("Expression of type `{ty}` cannot be called")]
+    NotCallable { ty: String, span: TextRange },
+
+    #[error("`{name}` cannot be called with generic arguments, only constructors like `vec3<f32>(...)` can")]
+    UnexpectedGenericArguments { name: String, span: TextRange },
+
+    #[error("Cannot index into a value of type `{ty}`")]
+    NotIndexable { ty: String, span: TextRange },
+
+    #[error("Cannot index with a value of type `{ty}`, expected an integer")]
+    InvalidIndex { ty: String, span: TextRange },
+
+    #[error("Runtime-sized arrays are only allowed as the last member of a struct or as a storage binding")]
+    RuntimeSizedArray { span: TextRange },
+
+    #[error("`{name}` expects a texel format but `{found}` was given")]
+    ExpectedTexelFormat { name: String, found: String, span: TextRange },
+
+    #[error("`{ty}` can only be used as a binding or function argument")]
+    OpaqueType { ty: String, span: TextRange },
+
+    #[error("Layout `{ty}` can only be an argument of an entry point")]
+    LayoutArgument { ty: String, span: TextRange },
+
+    #[error("Entry points cannot be generic")]
+    GenericEntryPoint { span: TextRange },
+
+    #[error("`{ty}` cannot be bound as `{binding}`")]
+    InvalidBindingType { binding: &'static str, ty: String, span: TextRange },
+
+    #[error("`{name}` expects {expected} argument(s) but {found} were given")]
+    ArgumentCount { name: String, expected: usize, found: usize, span: TextRange },
+
+    #[error("No overload of `{name}` takes arguments of type ({arguments})")]
+    NoMatchingOverload { name: String, arguments: String, span: TextRange },
+
+    #[error("Recursive call to `{name}`, recursion is not supported in shaders")]
+    RecursiveCall { name: String, span: TextRange },
+
+    #[error("`{name}` cannot be called with `{ty}`")]
+    InvalidIntrinsicArgument { name: String, ty: String, span: TextRange },
+
+    #[error("Literal out of range for `{ty}`")]
+    LiteralOutOfRange { ty: String, span: TextRange },
+
+    #[error("{error}")]
+    ConstEval { error: ConstEvalError },
+
+    #[error("{error} in the {rules} layout of this binding")]
+    MemoryLayout { error: MemoryLayoutError, rules: &'static str, span: TextRange },
+
+    #[error("Binding index must be a non-negative 32-bit integer but `{value}` was given")]
+    InvalidBindingIndex { value: String, span: TextRange },
+
+    #[error("Binding index `{index}` is already used by `{name}`")]
+    DuplicateBindingIndex { index: u32, name: String, span: TextRange },
+
+    #[error("Set index must be a non-negative 32-bit integer but `{value}` was given")]
+    InvalidSetIndex { value: String, span: TextRange },
+
+    #[error("Layouts `{first}` and `{second}` both use set {set}")]
+    DuplicateSetIndex { set: u32, first: String, second: String, span: TextRange },
+
+    #[error("`{binding}` members are not bound to a binding index")]
+    UnexpectedBindingIndex { binding: &'static str, span: TextRange },
+
+    #[error("Push constants can be at most {limit} bytes but `{ty}` takes {size} bytes")]
+    PushConstantTooLarge { ty: String, size: u32, limit: u32, span: TextRange },
+
+    #[error("An entry point can only use one push constant but `{first}` and `{second}` are both push constants")]
+    MultiplePushConstants { first: String, second: String, span: TextRange },
+
+    #[error("`{name}` is workgroup memory, which only compute entry points can use")]
+    WorkgroupOutsideCompute { name: String, span: TextRange },
+
+    #[error("`{ty}` contains atomics, which can only be used in storage and workgroup memory")]
+    AtomicOutsideSharedMemory { ty: String, span: TextRange },
+
+    #[error("Atomics can only be written with atomic functions like `atomic_store`")]
+    AtomicAssignment { span: TextRange },
+
+    #[error("Only storage and workgroup memory can be assigned to")]
+    InvalidAssignment { span: TextRange },
+
+    #[error("`{name}` is a read-only `{binding}` binding and can't be written")]
+    ReadOnlyBinding { name: String, binding: String, span: TextRange },
+
+    #[error("Location must be a non-negative 32-bit integer but `{value}` was given")]
+    InvalidLocation { value: String, span: TextRange },
+
+    #[error("Location `{location}` is already used by `{name}`")]
+    DuplicateLocation { location: u32, name: String, span: TextRange },
+
+    #[error("Members of type `{ty}` can't be interpolated, only `interpolate(flat)` is allowed")]
+    InvalidInterpolation { ty: String, span: TextRange },
+
+    #[error("Constants must have a scalar type but `{ty}` was given")]
+    InvalidConstType { ty: String, span: TextRange },
+
+    #[error("Override id must be between 0 and 65535 but `{value}` was given")]
+    InvalidOverrideId { value: String, span: TextRange },
+
+    #[error("Override id `{id}` is already used by `{name}`")]
+    DuplicateOverrideId { id: u32, name: String, span: TextRange },
+
+    #[error("Enum discriminant must be a 32-bit unsigned integer but `{value}` was given")]
+    InvalidDiscriminant { value: String, span: TextRange },
+
+    #[error("Discriminant `{value}` is already used by `{name}`")]
+    DuplicateDiscriminant { value: u32, name: String, span: TextRange },
+
+    #[error("Variant `{name}` is defined multiple times")]
+    DuplicateVariant { name: String, span: TextRange },
+
+    #[error("No variant `{variant}` on enum `{ty}`")]
+    UnknownVariant { variant: String, ty: String, span: TextRange },
+
+    #[error("Cannot match on a value of type `{ty}`, expected an enum or integer")]
+    InvalidMatchScrutinee { ty: String, span: TextRange },
+
+    #[error("Unreachable pattern, the value is already matched by a previous arm")]
+    UnreachablePattern { span: TextRange },
+
+    #[error("Non-exhaustive match, missing {missing}")]
+    NonExhaustiveMatch { missing: String, span: TextRange },
+
+    #[error("Unknown bound `{name}`, expected `float`, `integer`, `vec2`, `vec3` or `vec4`")]
+    UnknownTypeBound { name: String, span: TextRange },
+
+    #[error("Conflicting bounds on `{name}`")]
+    ConflictingTypeBounds { name: String, span: TextRange },
+
+    #[error("Cannot infer `{name}` for the call to `{function}`")]
+    CannotInferGeneric { name: String, function: String, span: TextRange },
+
+    #[error("In `{instance}`: `{ty}` does not satisfy the bound `{bound}`")]
+    UnsatisfiedBound { instance: String, ty: String, bound: String, span: TextRange },
+
+    #[error("In `{instance}`: {error}")]
+    InstantiationFailed { instance: String, error: Box<TypeCheckError>, span: TextRange },
+
+    #[error("No method or associated function `{name}` on type `{ty}`")]
+    UnknownMethod { name: String, ty: String, span: TextRange },
+
+    #[error("`{name}` has no `self` argument, call it as `{ty}::{name}`")]
+    NotAMethod { name: String, ty: String, span: TextRange },
+
+    #[error("Function `{name}` is defined multiple times for `{ty}`")]
+    DuplicateMethod { name: String, ty: String, span: TextRange },
+
+    #[error("Functions in impl blocks cannot be generic")]
+    GenericMethod { span: TextRange },
+
+    #[error("Type alias `{name}` expands to itself")]
+    RecursiveTypeAlias { name: String, span: TextRange },
+
+    #[error("`{ty}::{name}` is private to its {scope}, consider declaring it `pub`")]
+    PrivateMember { name: String, ty: String, scope: &'static str, span: TextRange },
+}
+
+impl TypeCheckError {
+    pub fn span(&self) -> TextRange {
+        match self {
+            TypeCheckError::UnresolvedName { span, .. } => *span,
+            TypeCheckError::MismatchedTypes { span, .. } => *span,
+            TypeCheckError::InvalidBinaryOperands { span, .. } => *span,
+            TypeCheckError::InvalidUnaryOperand { span, .. } => *span,
+            TypeCheckError::NotAType { span, .. } => *span,
+            TypeCheckError::InvalidGenericArguments { span, .. } => *span,
+            TypeCheckError::InvalidVectorElement { span, .. } => *span,
+            TypeCheckError::ConstructorComponentCount { span, .. } => *span,
+            TypeCheckError::UnknownField { span, .. } => *span,
+            TypeCheckError::NotCallable { span, .. } => *span,
+            TypeCheckError::UnexpectedGenericArguments { span, .. } => *span,
+            TypeCheckError::NotIndexable { span, .. } => *span,
+            TypeCheckError::InvalidIndex { span, .. } => *span,
+            TypeCheckError::RuntimeSizedArray { span } => *span,
+            TypeCheckError::ExpectedTexelFormat { span, .. } => *span,
+            TypeCheckError::OpaqueType { span, .. } => *span,
+            TypeCheckError::LayoutArgument { span, .. } => *span,
+            TypeCheckError::GenericEntryPoint { span } => *span,
+            TypeCheckError::InvalidBindingType { span, .. } => *span,
+            TypeCheckError::ArgumentCount { span, .. } => *span,
+            TypeCheckError::NoMatchingOverload { span, .. } => *span,
+            TypeCheckError::RecursiveCall { span, .. } => *span,
+            TypeCheckError::InvalidIntrinsicArgument { span, .. } => *span,
+            TypeCheckError::LiteralOutOfRange { span, .. } => *span,
+            TypeCheckError::ConstEval { error } => error.span(),
+            TypeCheckError::MemoryLayout { span, .. } => *span,
+            TypeCheckError::InvalidBindingIndex { span, .. } => *span,
+            TypeCheckError::DuplicateBindingIndex { span, .. } => *span,
+            TypeCheckError::InvalidSetIndex { span, .. } => *span,
+            TypeCheckError::DuplicateSetIndex { span, .. } => *span,
+            TypeCheckError::UnexpectedBindingIndex { span, .. } => *span,
+            TypeCheckError::PushConstantTooLarge { span, .. } => *span,
+            TypeCheckError::MultiplePushConstants { span, .. } => *span,
+            TypeCheckError::WorkgroupOutsideCompute { span, .. } => *span,
+            TypeCheckError::AtomicOutsideSharedMemory { span, .. } => *span,
+            TypeCheckError::AtomicAssignment { span } => *span,
+            TypeCheckError::InvalidAssignment { span } => *span,
+            TypeCheckError::ReadOnlyBinding { span, .. } => *span,
+            TypeCheckError::InvalidLocation { span, .. } => *span,
+            TypeCheckError::DuplicateLocation { span, .. } => *span,
+            TypeCheckError::InvalidInterpolation { span, .. } => *span,
+            TypeCheckError::InvalidConstType { span, .. } => *span,
+            TypeCheckError::InvalidOverrideId { span, .. } => *span,
+            TypeCheckError::DuplicateOverrideId { span, .. } => *span,
+            TypeCheckError::InvalidDiscriminant { span, .. } => *span,
+            TypeCheckError::DuplicateDiscriminant { span, .. } => *span,
+            TypeCheckError::DuplicateVariant { span, .. } => *span,
+            TypeCheckError::UnknownVariant { span, .. } => *span,
+            TypeCheckError::InvalidMatchScrutinee { span, .. } => *span,
+            TypeCheckError::UnreachablePattern { span } => *span,
+            TypeCheckError::NonExhaustiveMatch { span, .. } => *span,
+            TypeCheckError::UnknownTypeBound { span, .. } => *span,
+            TypeCheckError::ConflictingTypeBounds { span, .. } => *span,
+            TypeCheckError::CannotInferGeneric { span, .. } => *span,
+            TypeCheckError::UnsatisfiedBound { span, .. } => *span,
+            TypeCheckError::InstantiationFailed { span, .. } => *span,
+            TypeCheckError::UnknownMethod { span, .. } => *span,
+            TypeCheckError::NotAMethod { span, .. } => *span,
+            TypeCheckError::DuplicateMethod { span, .. } => *span,
+            TypeCheckError::GenericMethod { span } => *span,
+            TypeCheckError::RecursiveTypeAlias { span, .. } => *span,
+            TypeCheckError::PrivateMember { span, .. } => *span,
+        }
+    }
+}
+
+/// Code that is valid but likely not what was intended
+#[derive(Error, Debug, Eq, PartialEq, Clone)]
+pub enum TypeCheckWarning {
+    #[error("`{name}` is bound to {index} after `{previous}` is bound to {previous_index}, mixing explicit and automatic binding indices out of order")]
+    MixedBindingIndices { name: String, index: u32, previous: String, previous_index: u32, span: TextRange },
+
+    #[error("Unreachable wildcard, the previous arms already match every value")]
+    UnreachableWildcard { span: TextRange },
+}
+
+impl TypeCheckWarning {
+    pub fn span(&self) -> TextRange {
+        match self {
+            TypeCheckWarning::MixedBindingIndices { span, .. } => *span,
+            TypeCheckWarning::UnreachableWildcard { span } => *span,
+        }
+    }
 }
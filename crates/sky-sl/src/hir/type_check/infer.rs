@@ -0,0 +1,1898 @@
+use super::{binary_operator_ty, check_no_atomics, check_not_opaque, check_recursion, check_sized, contains_atomic, declare_generic_parameters, infer_intrinsic_ty, is_runtime_sized_array, resolve_type, resolve_type_path, unary_operator_ty, EnumTy, Env, GenericTy, LayoutTy, ScalarTy, StructTy, Ty, TypeCheckError, TypeCheckWarning, TypeClass};
+use crate::{fs::FileId, syn::cst::{LiteralSuffix, LiteralValue, UnaryOperator}, hir::{const_eval::{eval_expression, ConstEvalError}, intrinsic::IntrinsicKind, memory_layout::{type_layout, LayoutRules}, named::NamedItemKind, pipeline::default_interpolation, primitive::{Primitive, PrimitiveKind}, symbol::{Symbol, SymbolKind}, typed, untyped, HirDatabase}, intern::Name};
+use rowan::TextRange;
+use std::collections::{HashMap, HashSet};
+
+pub fn infer_module(module: &untyped::Module, mut env: Env) -> typed::Module {
+    let mut items = Vec::new();
+    let mut override_ids = HashMap::new();
+
+    for item in &module.items {
+        match item {
+            untyped::ItemKind::Function(function) => {
+                env.push_scope();
+                declare_generic_parameters(&function.signature, &mut env);
+                let function = infer_function(function, &mut env);
+                items.push(typed::ItemKind::Function(function));
+                env.set_generics(HashMap::new());
+                env.pop_scope();
+            },
+            untyped::ItemKind::Struct(structure) => {
+                let structure = infer_struct(structure, &mut env);
+                items.push(typed::ItemKind::Struct(structure));
+            },
+            untyped::ItemKind::Layout(layout) => {
+                let layout = infer_layout(layout, &mut env);
+                items.push(typed::ItemKind::Layout(layout));
+            },
+            untyped::ItemKind::Const(constant) => {
+                let constant = infer_const(constant, &mut env);
+
+                if let Some(id) = constant.override_id {
+                    if let Some(name) = override_ids.insert(id, constant.name) {
+                        let error = TypeCheckError::DuplicateOverrideId {
+                            id,
+                            name: env.name(name),
+                            span: constant.span,
+                        };
+                        env.add_error(error);
+                    }
+                }
+
+                items.push(typed::ItemKind::Const(constant));
+            },
+            untyped::ItemKind::Enum(enumeration) => {
+                let enumeration = infer_enum(enumeration, &mut env);
+                items.push(typed::ItemKind::Enum(enumeration));
+            },
+            untyped::ItemKind::Impl(implementation) => {
+                let implementation = infer_impl(implementation, &mut env);
+                items.push(typed::ItemKind::Impl(implementation));
+            },
+            untyped::ItemKind::TypeAlias(type_alias) => {
+                let type_alias = infer_type_alias(type_alias, &mut env);
+                items.push(typed::ItemKind::TypeAlias(type_alias));
+            },
+        }
+    }
+
+    let (errors, warnings) = env.into_diagnostics();
+    typed::Module::new(items, errors, warnings, module.span)
+}
+
+pub fn infer_struct(structure: &untyped::StructKind, env: &mut Env) -> typed::StructKind {
+    let mut members = Vec::new();
+    let mut locations = HashMap::new();
+    for (index, member) in structure.members.iter().enumerate() {
+        let ty = resolve_type(&member.type_reference, env);
+
+        // a runtime-sized array may only end a struct, which is then bound as storage
+        let is_last = index + 1 == structure.members.len();
+        if !(is_last && is_runtime_sized_array(ty, env)) {
+            check_sized(ty, member.type_reference.span(), env);
+        }
+        check_not_opaque(ty, member.type_reference.span(), env);
+
+        let location = infer_location(structure, member, env);
+        if let Some(location) = location {
+            if let Some(name) = locations.insert(location, member.name) {
+                let error = TypeCheckError::DuplicateLocation {
+                    location,
+                    name: env.name(name),
+                    span: member.location.as_ref().map_or(member.span, |location| location.span()),
+                };
+                env.add_error(error);
+            }
+        }
+
+        // integers can't be interpolated, so flat is their default and the only valid interpolation
+        let is_flat = default_interpolation(ty) == untyped::Interpolation::Flat;
+        if is_flat && member.interpolation.is_some_and(|interpolation| interpolation != untyped::Interpolation::Flat) {
+            let error = TypeCheckError::InvalidInterpolation {
+                ty: env.display(ty),
+                span: member.span,
+            };
+            env.add_error(error);
+        }
+
+        members.push(typed::StructMember::new(member.name, ty, location, member.interpolation, member.span));
+    }
+
+    let ty = Ty::Struct(StructTy {
+        file: env.current_file(),
+        name: structure.name,
+    });
+
+    typed::StructKind::new(structure.name, members, ty, structure.span)
+}
+
+pub fn infer_impl(implementation: &untyped::ImplKind, env: &mut Env) -> typed::ImplKind {
+    let ty = Ty::Struct(StructTy {
+        file: env.current_file(),
+        name: implementation.name,
+    });
+
+    // a struct can have several impl blocks, a function name may only be used once across them
+    let module = env.db().get_hir(env.current_file());
+    let mut names: HashSet<Name> = module.items.iter()
+        .filter_map(|item| match item {
+            untyped::ItemKind::Impl(other) if other.name == implementation.name && other.span.start() < implementation.span.start() => Some(other),
+            _ => None,
+        })
+        .flat_map(|other| other.functions.iter().map(|function| function.signature.name))
+        .collect();
+
+    env.set_impl_target(Some(implementation.name));
+    let mut functions = Vec::new();
+    for function in &implementation.functions {
+        if !names.insert(function.signature.name) {
+            let error = TypeCheckError::DuplicateMethod {
+                name: env.name(function.signature.name),
+                ty: env.name(implementation.name),
+                span: function.signature.span,
+            };
+            env.add_error(error);
+        }
+
+        if function.signature.is_generic() {
+            let error = TypeCheckError::GenericMethod {
+                span: function.signature.span,
+            };
+            env.add_error(error);
+        }
+
+        env.push_scope();
+        declare_generic_parameters(&function.signature, env);
+        functions.push(infer_function(function, env));
+        env.set_generics(HashMap::new());
+        env.pop_scope();
+    }
+    env.set_impl_target(None);
+
+    typed::ImplKind::new(implementation.name, functions, ty, implementation.span)
+}
+
+pub fn infer_type_alias(type_alias: &untyped::TypeAliasKind, env: &mut Env) -> typed::TypeAliasKind {
+    // diagnostics in the aliased type are reported here, cycles are detected when expanding the alias
+    let ty = resolve_type(&type_alias.type_reference, env);
+    let ty = match env.db().resolve_type_alias(env.current_file(), type_alias.name) {
+        Ok(_) => ty,
+        Err(error) => {
+            env.add_error(error);
+            Ty::Error
+        },
+    };
+
+    typed::TypeAliasKind::new(type_alias.name, ty, type_alias.span)
+}
+
+pub fn infer_enum(enumeration: &untyped::EnumKind, env: &mut Env) -> typed::EnumKind {
+    let values = eval_discriminants(enumeration, env);
+
+    let mut names = HashSet::new();
+    let mut discriminants = HashMap::new();
+    let mut variants = Vec::new();
+    for (variant, value) in enumeration.variants.iter().zip(values) {
+        if !names.insert(variant.name) {
+            let error = TypeCheckError::DuplicateVariant {
+                name: env.name(variant.name),
+                span: variant.span,
+            };
+            env.add_error(error);
+        }
+
+        if let Some(value) = value {
+            if let Some(name) = discriminants.insert(value, variant.name) {
+                let error = TypeCheckError::DuplicateDiscriminant {
+                    value,
+                    name: env.name(name),
+                    span: variant.span,
+                };
+                env.add_error(error);
+            }
+        }
+
+        variants.push(typed::EnumVariant::new(variant.name, value, variant.span));
+    }
+
+    let ty = Ty::Enum(EnumTy {
+        file: env.current_file(),
+        name: enumeration.name,
+    });
+
+    typed::EnumKind::new(enumeration.name, variants, ty, enumeration.span)
+}
+
+/// Evaluates the discriminant of every variant, a variant without one takes the value after the previous variant
+fn eval_discriminants(enumeration: &untyped::EnumKind, env: &mut Env) -> Vec<Option<u32>> {
+    let mut values = Vec::new();
+    let mut next = Some(0u64);
+    for variant in &enumeration.variants {
+        let value = match &variant.discriminant {
+            Some(discriminant) => match eval_expression(env.db(), discriminant, Some(ScalarTy::Int { signed: false, width: 32 })) {
+                Ok(value) => {
+                    if value.as_u32().is_none() {
+                        let error = TypeCheckError::InvalidDiscriminant {
+                            value: value.display(),
+                            span: discriminant.span(),
+                        };
+                        env.add_error(error);
+                    }
+
+                    value.as_u32()
+                },
+                Err(error) => {
+                    env.add_error(TypeCheckError::ConstEval { error });
+                    None
+                },
+            },
+            None => match next {
+                Some(next) if next > u32::MAX as u64 => {
+                    let error = TypeCheckError::InvalidDiscriminant {
+                        value: next.to_string(),
+                        span: variant.span,
+                    };
+                    env.add_error(error);
+                    None
+                },
+                next => next.map(|next| next as u32),
+            },
+        };
+
+        next = value.map(|value| value as u64 + 1);
+        values.push(value);
+    }
+
+    values
+}
+
+pub fn infer_layout(layout: &untyped::LayoutKind, env: &mut Env) -> typed::LayoutKind {
+    let mut members = Vec::new();
+    let mut binding_indices = HashMap::new();
+    for member in &layout.members {
+        let ty = resolve_type(&member.type_reference, env);
+
+        if member.binding_kind != untyped::BindingKind::Storage {
+            check_sized(ty, member.type_reference.span(), env);
+        }
+
+        // textures and samplers need their own binding kinds, plain data can't use them
+        let valid = match member.binding_kind {
+            untyped::BindingKind::Uniform | untyped::BindingKind::Storage | untyped::BindingKind::PushConstant | untyped::BindingKind::Workgroup => !ty.is_opaque(),
+            untyped::BindingKind::Image => matches!(ty, Ty::Texture(_)),
+            untyped::BindingKind::Sampler => matches!(ty, Ty::Sampler { .. }),
+        };
+
+        if !valid && !ty.is_error() {
+            let error = TypeCheckError::InvalidBindingType {
+                binding: member.binding_kind.keyword(),
+                ty: env.display(ty),
+                span: member.type_reference.span(),
+            };
+            env.add_error(error);
+        }
+
+        if valid {
+            check_memory_layout(member, ty, env);
+        }
+
+        if !matches!(member.binding_kind, untyped::BindingKind::Storage | untyped::BindingKind::Workgroup) {
+            check_no_atomics(ty, member.type_reference.span(), env);
+        }
+
+        let has_binding_index = member.binding_kind.has_binding_index();
+        if let (false, Some(binding_index)) = (has_binding_index, &member.binding_index) {
+            let error = TypeCheckError::UnexpectedBindingIndex {
+                binding: member.binding_kind.keyword(),
+                span: binding_index.span(),
+            };
+            env.add_error(error);
+        }
+
+        let binding_index = match has_binding_index {
+            true => infer_binding_index(layout, member, env),
+            false => None,
+        };
+
+        if let Some(index) = binding_index {
+            if let Some(name) = binding_indices.insert(index, member.name) {
+                let error = TypeCheckError::DuplicateBindingIndex {
+                    index,
+                    name: env.name(name),
+                    span: member.binding_index.as_ref().map_or(member.span, |binding_index| binding_index.span()),
+                };
+                env.add_error(error);
+            }
+        }
+
+        let access = match member.binding_kind {
+            untyped::BindingKind::Storage => Some(member.access.unwrap_or(untyped::AccessMode::ReadWrite)),
+            _ => None,
+        };
+
+        let automatic_binding = member.binding_index.is_none() && has_binding_index;
+        members.push(typed::LayoutMember::new(member.name, binding_index, automatic_binding, member.binding_kind, access, ty, member.span));
+    }
+
+    assign_binding_indices(&mut members, &binding_indices);
+    check_binding_order(&members, env);
+
+    let set_index = infer_set_index(layout, env);
+    typed::LayoutKind::new(layout.name, set_index, members, layout.span)
+}
+
+/// Assigns members without `binding(...)` the lowest indices not used explicitly, in declaration order
+fn assign_binding_indices(members: &mut [typed::LayoutMember], explicit: &HashMap<u32, Name>) {
+    let mut next = 0;
+    for member in members.iter_mut().filter(|member| member.automatic_binding) {
+        while explicit.contains_key(&next) {
+            next += 1;
+        }
+
+        member.binding_index = Some(next);
+        next += 1;
+    }
+}
+
+/// Warns about members bound to a lower index than a member declared before them if either index was assigned
+/// automatically, e.g. an automatic binding after `binding(4)` is numbered from 0 again
+fn check_binding_order(members: &[typed::LayoutMember], env: &mut Env) {
+    let mut highest: Option<(&typed::LayoutMember, u32)> = None;
+
+    for member in members {
+        let index = match member.binding_index {
+            Some(index) => index,
+            None => continue,
+        };
+
+        match highest {
+            Some((previous, previous_index)) if index < previous_index => {
+                if member.automatic_binding || previous.automatic_binding {
+                    let warning = TypeCheckWarning::MixedBindingIndices {
+                        name: env.name(member.name),
+                        index,
+                        previous: env.name(previous.name),
+                        previous_index,
+                        span: member.span,
+                    };
+                    env.add_warning(warning);
+                }
+            },
+            _ => highest = Some((member, index)),
+        }
+    }
+}
+
+fn infer_set_index(layout: &untyped::LayoutKind, env: &mut Env) -> Option<u32> {
+    let value = match env.db().eval_set_index(env.current_file(), layout.name)? {
+        Ok(value) => value,
+        Err(error) => {
+            env.add_error(TypeCheckError::ConstEval { error });
+            return None;
+        },
+    };
+
+    if value.as_u32().is_none() {
+        let error = TypeCheckError::InvalidSetIndex {
+            value: value.display(),
+            span: layout.set_index.as_ref().map_or(layout.span, |set_index| set_index.span()),
+        };
+        env.add_error(error);
+    }
+
+    value.as_u32()
+}
+
+/// Assigns a set index to each layout argument of an entry point. Layouts without a valid explicit set index
+/// get the lowest index not used by another layout of the entry point, in the order of the arguments.
+pub fn assign_set_indices(db: &dyn HirDatabase, layouts: &[LayoutTy]) -> Vec<u32> {
+    let explicit: Vec<Option<u32>> = layouts.iter()
+        .map(|layout| db.eval_set_index(layout.file, layout.name)?.ok()?.as_u32())
+        .collect();
+
+    let mut used: HashSet<u32> = explicit.iter().flatten().copied().collect();
+    let mut next = 0;
+
+    explicit.into_iter()
+        .map(|set| set.unwrap_or_else(|| {
+            while used.contains(&next) {
+                next += 1;
+            }
+            used.insert(next);
+            next
+        }))
+        .collect()
+}
+
+/// Reports layouts of an entry point whose explicit set indices collide
+fn check_set_indices(arguments: &[typed::FunctionArgument], env: &mut Env) {
+    let layouts: Vec<(LayoutTy, TextRange)> = arguments.iter()
+        .filter_map(|argument| match argument.ty {
+            Ty::Layout(layout_ty) => Some((layout_ty, argument.span)),
+            _ => None,
+        })
+        .collect();
+
+    let layout_tys: Vec<LayoutTy> = layouts.iter().map(|(layout_ty, _)| *layout_ty).collect();
+    let sets = assign_set_indices(env.db(), &layout_tys);
+
+    let mut used = HashMap::new();
+    for ((layout_ty, span), set) in layouts.into_iter().zip(sets) {
+        if let Some(first) = used.insert(set, layout_ty) {
+            let error = TypeCheckError::DuplicateSetIndex {
+                set,
+                first: env.name(first.name),
+                second: env.name(layout_ty.name),
+                span,
+            };
+            env.add_error(error);
+        }
+    }
+}
+
+/// Reports entry points using more than one push constant across their layouts. The layouts are looked up in the
+/// untyped HIR, as a layout declared in the module of the entry point is still being type checked.
+fn check_push_constants(arguments: &[typed::FunctionArgument], env: &mut Env) {
+    let mut first: Option<String> = None;
+
+    for argument in arguments {
+        let layout_ty = match argument.ty {
+            Ty::Layout(layout_ty) => layout_ty,
+            _ => continue,
+        };
+
+        let module = env.db().get_hir(layout_ty.file);
+        let push_constants = module.items.iter()
+            .filter_map(|item| match item {
+                untyped::ItemKind::Layout(layout) if layout.name == layout_ty.name => Some(layout),
+                _ => None,
+            })
+            .flat_map(|layout| layout.members.iter())
+            .filter(|member| member.binding_kind == untyped::BindingKind::PushConstant);
+
+        for member in push_constants {
+            let name = format!("{}::{}", env.name(layout_ty.name), env.name(member.name));
+            match &first {
+                Some(first) => {
+                    let error = TypeCheckError::MultiplePushConstants {
+                        first: first.clone(),
+                        second: name,
+                        span: argument.span,
+                    };
+                    env.add_error(error);
+                },
+                None => first = Some(name),
+            }
+        }
+    }
+}
+
+/// Reports workgroup memory passed to an entry point that is not a compute shader, the layouts are looked up in
+/// the untyped HIR like in `check_push_constants`
+fn check_workgroup_memory(arguments: &[typed::FunctionArgument], env: &mut Env) {
+    for argument in arguments {
+        let layout_ty = match argument.ty {
+            Ty::Layout(layout_ty) => layout_ty,
+            _ => continue,
+        };
+
+        let module = env.db().get_hir(layout_ty.file);
+        let workgroup_members = module.items.iter()
+            .filter_map(|item| match item {
+                untyped::ItemKind::Layout(layout) if layout.name == layout_ty.name => Some(layout),
+                _ => None,
+            })
+            .flat_map(|layout| layout.members.iter())
+            .filter(|member| member.binding_kind == untyped::BindingKind::Workgroup);
+
+        for member in workgroup_members {
+            let error = TypeCheckError::WorkgroupOutsideCompute {
+                name: format!("{}::{}", env.name(layout_ty.name), env.name(member.name)),
+                span: argument.span,
+            };
+            env.add_error(error);
+        }
+    }
+}
+
+/// Reports the members of a uniform, storage or push constant binding that can't be laid out with its default rules
+/// and push constants exceeding the limit of the package
+fn check_memory_layout(member: &untyped::LayoutMember, ty: Ty, env: &mut Env) {
+    let rules = match LayoutRules::for_binding(member.binding_kind) {
+        Some(rules) => rules,
+        None => return,
+    };
+
+    let mut errors = Vec::new();
+    let layout = type_layout(env.db(), ty, rules, &env.name(member.name), &mut errors);
+
+    for error in errors {
+        env.add_error(TypeCheckError::MemoryLayout {
+            error,
+            rules: rules.name(),
+            span: member.type_reference.span(),
+        });
+    }
+
+    let limit = env.db().limits(env.current_file()).max_push_constant_size;
+    if member.binding_kind == untyped::BindingKind::PushConstant && layout.size > limit {
+        let error = TypeCheckError::PushConstantTooLarge {
+            ty: env.display(ty),
+            size: layout.size,
+            limit,
+            span: member.type_reference.span(),
+        };
+        env.add_error(error);
+    }
+}
+
+fn infer_location(structure: &untyped::StructKind, member: &untyped::StructMember, env: &mut Env) -> Option<u32> {
+    let value = match env.db().eval_location(env.current_file(), structure.name, member.name)? {
+        Ok(value) => value,
+        Err(error) => {
+            env.add_error(TypeCheckError::ConstEval { error });
+            return None;
+        },
+    };
+
+    if value.as_u32().is_none() {
+        let error = TypeCheckError::InvalidLocation {
+            value: value.display(),
+            span: member.location.as_ref().map_or(member.span, |location| location.span()),
+        };
+        env.add_error(error);
+    }
+
+    value.as_u32()
+}
+
+fn infer_binding_index(layout: &untyped::LayoutKind, member: &untyped::LayoutMember, env: &mut Env) -> Option<u32> {
+    let value = match env.db().eval_binding_index(env.current_file(), layout.name, member.name)? {
+        Ok(value) => value,
+        Err(error) => {
+            env.add_error(TypeCheckError::ConstEval { error });
+            return None;
+        },
+    };
+
+    if value.as_u32().is_none() {
+        let error = TypeCheckError::InvalidBindingIndex {
+            value: value.display(),
+            span: member.binding_index.as_ref().map_or(member.span, |binding_index| binding_index.span()),
+        };
+        env.add_error(error);
+    }
+
+    value.as_u32()
+}
+
+pub fn infer_const(constant: &untyped::ConstKind, env: &mut Env) -> typed::ConstKind {
+    let errors = env.error_count();
+
+    let ty = resolve_type(&constant.type_reference, env);
+    if !matches!(ty, Ty::Scalar(_)) && !ty.is_error() {
+        let error = TypeCheckError::InvalidConstType {
+            ty: env.display(ty),
+            span: constant.type_reference.span(),
+        };
+        env.add_error(error);
+    }
+
+    let expression = infer_expression_expecting(&constant.expression, Some(ty), env);
+    env.expect(ty, expression.ty(), expression.span());
+
+    // an override is no constant expression itself, but its default value has to be one
+    let value = if constant.override_id.is_some() {
+        let expected = match ty {
+            Ty::Scalar(scalar) => Some(scalar),
+            _ => None,
+        };
+        eval_expression(env.db(), &constant.expression, expected)
+    } else {
+        env.db().eval_const(env.current_file(), constant.name)
+    };
+
+    // evaluation errors are only reported for well typed constants, otherwise they repeat the type errors
+    let value = match value {
+        Ok(value) => Some(value),
+        Err(ConstEvalError::InvalidConst { .. }) => None,
+        Err(_) if env.error_count() != errors => None,
+        Err(error) => {
+            env.add_error(TypeCheckError::ConstEval { error });
+            None
+        },
+    };
+
+    let override_id = constant.override_id.as_ref().and_then(|override_id| infer_override_id(override_id, env));
+
+    typed::ConstKind::new(constant.name, ty, expression, value, override_id, constant.override_id.is_some(), constant.span)
+}
+
+fn infer_override_id(override_id: &untyped::ExpressionKind, env: &mut Env) -> Option<u32> {
+    let value = match eval_expression(env.db(), override_id, Some(ScalarTy::Int { signed: false, width: 32 })) {
+        Ok(value) => value,
+        Err(error) => {
+            env.add_error(TypeCheckError::ConstEval { error });
+            return None;
+        },
+    };
+
+    // WGSL limits ids to 16 bits, SPIR-V accepts any 32-bit id
+    match value.as_u32().filter(|id| *id <= u16::MAX as u32) {
+        Some(id) => Some(id),
+        None => {
+            let error = TypeCheckError::InvalidOverrideId {
+                value: value.display(),
+                span: override_id.span(),
+            };
+            env.add_error(error);
+            None
+        },
+    }
+}
+
+pub fn infer_function(function: &untyped::FunctionKind, env: &mut Env) -> typed::FunctionKind {
+    let mut arguments = Vec::new();
+    for argument in &function.signature.arguments {
+        let ty = resolve_type(&argument.type_reference, env);
+        check_sized(ty, argument.type_reference.span(), env);
+
+        // the bindings of a layout are provided by the pipeline, so only entry points can receive them
+        if let (Ty::Layout(_), None) = (ty, function.stage) {
+            let error = TypeCheckError::LayoutArgument {
+                ty: env.display(ty),
+                span: argument.type_reference.span(),
+            };
+            env.add_error(error);
+        }
+
+        if !matches!(ty, Ty::Layout(_)) {
+            check_no_atomics(ty, argument.type_reference.span(), env);
+        }
+
+        env.insert(argument.name, ty);
+        arguments.push(typed::FunctionArgument::new(
+            argument.name,
+            ty,
+            argument.span,
+        ));
+    }
+
+    let return_type = match &function.signature.return_type {
+        Some(return_type) => {
+            let ty = resolve_type(return_type, env);
+            check_sized(ty, return_type.span(), env);
+            check_no_atomics(ty, return_type.span(), env);
+            ty
+        },
+        None => Ty::Unit,
+    };
+
+    let generic_parameters = function.signature.generic_parameters.iter()
+        .filter_map(|parameter| env.lookup_generic(parameter.name))
+        .collect();
+
+    let signature = typed::FunctionSignature::new(
+        function.signature.name,
+        generic_parameters,
+        arguments,
+        return_type,
+        function.signature.span,
+    );
+
+    let block = infer_block(&function.block, Some(return_type), env);
+    let span = block.tail_span().unwrap_or(block.span);
+    env.expect(return_type, block.ty, span);
+
+    check_recursion(function, env);
+
+    if function.stage.is_some() && function.signature.is_generic() {
+        env.add_error(TypeCheckError::GenericEntryPoint { span: function.signature.span });
+    }
+
+    if function.stage.is_some() {
+        check_set_indices(&signature.arguments, env);
+        check_push_constants(&signature.arguments, env);
+    }
+
+    if function.stage.is_some_and(|stage| stage != untyped::ShaderStage::Compute) {
+        check_workgroup_memory(&signature.arguments, env);
+    }
+
+    typed::FunctionKind::new(signature, function.stage, block, function.span)
+}
+
+/// Infers a block, `expected` is the type expected of its value and only applies to the last statement
+pub fn infer_block(block: &untyped::Block, expected: Option<Ty>, env: &mut Env) -> typed::Block {
+    env.push_scope();
+
+    let mut statements = Vec::new();
+    let mut ty = Ty::Unit;
+    for (index, statement) in block.statements.iter().enumerate() {
+        let is_last = index + 1 == block.statements.len();
+        let statement = infer_statement(statement, expected.filter(|_| is_last), env);
+
+        // only an expression without a trailing semicolon in tail position gives the block a value
+        ty = match &statement {
+            typed::StatementKind::Expression(expression_statement) if !expression_statement.has_semicolon => expression_statement.ty,
+            _ => Ty::Unit,
+        };
+
+        statements.push(statement);
+    }
+
+    env.pop_scope();
+
+    typed::Block::new(statements, ty, block.span)
+}
+
+pub fn infer_statement(statement: &untyped::StatementKind, expected: Option<Ty>, env: &mut Env) -> typed::StatementKind {
+    match statement {
+        untyped::StatementKind::Let(let_statement) => {
+            let declared = let_statement.type_reference.as_ref().map(|type_reference| resolve_type(type_reference, env));
+            let expression = infer_expression_expecting(&let_statement.expression, declared, env);
+
+            let ty = match declared {
+                Some(declared) => {
+                    env.expect(declared, expression.ty(), expression.span());
+                    if declared.is_error() { expression.ty() } else { declared }
+                },
+                None => expression.ty(),
+            };
+
+            check_sized(ty, let_statement.span, env);
+            check_no_atomics(ty, let_statement.span, env);
+            env.insert(let_statement.name, ty);
+
+            typed::StatementKind::Let(typed::LetStatement::new(
+                let_statement.name,
+                expression,
+                ty,
+                let_statement.span,
+            ))
+        },
+        untyped::StatementKind::Expression(expression_statement) => {
+            let expected = expected.filter(|_| !expression_statement.has_semicolon);
+            let expression = infer_expression_expecting(&expression_statement.expression, expected, env);
+            let ty = expression.ty();
+
+            typed::StatementKind::Expression(typed::ExpressionStatement::new(
+                expression,
+                expression_statement.has_semicolon,
+                ty,
+                expression_statement.span,
+            ))
+        },
+        untyped::StatementKind::Assign(assign_statement) => {
+            let place = infer_expression(&assign_statement.place, env);
+            let value = infer_expression_expecting(&assign_statement.value, Some(place.ty()), env);
+
+            if check_writable(&place, env) {
+                if contains_atomic(place.ty(), env) {
+                    env.add_error(TypeCheckError::AtomicAssignment { span: assign_statement.span });
+                } else {
+                    env.expect(place.ty(), value.ty(), value.span());
+                }
+            }
+
+            typed::StatementKind::Assign(typed::AssignStatement::new(
+                place,
+                value,
+                assign_statement.span,
+            ))
+        },
+    }
+}
+
+/// Checks that a place is a read-write storage binding or workgroup memory, or a field or element of one.
+/// Arguments and `let` bindings are immutable.
+fn check_writable(place: &typed::ExpressionKind, env: &mut Env) -> bool {
+    if place.ty().is_error() {
+        return false;
+    }
+
+    let (layout_ty, name) = match place_binding(place) {
+        Some(binding) => binding,
+        None => {
+            env.add_error(TypeCheckError::InvalidAssignment { span: place.span() });
+            return false;
+        },
+    };
+
+    // looked up in the untyped HIR, the layout may be declared in the module being type checked
+    let module = env.db().get_hir(layout_ty.file);
+    let member = module.items.iter()
+        .find_map(|item| match item {
+            untyped::ItemKind::Layout(layout) if layout.name == layout_ty.name => layout.members.iter().find(|member| member.name == name),
+            _ => None,
+        });
+
+    let member = match member {
+        Some(member) => member,
+        None => return false,
+    };
+
+    let binding = match (member.binding_kind, member.access) {
+        (untyped::BindingKind::Workgroup, _) | (untyped::BindingKind::Storage, None | Some(untyped::AccessMode::ReadWrite)) => return true,
+        (untyped::BindingKind::Storage, Some(access)) => format!("storage({})", access.name()),
+        (binding_kind, _) => binding_kind.keyword().to_string(),
+    };
+
+    let error = TypeCheckError::ReadOnlyBinding {
+        name: format!("{}::{}", env.name(layout_ty.name), env.name(name)),
+        binding,
+        span: place.span(),
+    };
+    env.add_error(error);
+    false
+}
+
+/// The layout member a place expression like `buffer.particles[i].position` is rooted in
+fn place_binding(place: &typed::ExpressionKind) -> Option<(LayoutTy, Name)> {
+    match place {
+        typed::ExpressionKind::FieldAccessExpression(e) => match e.expression.ty() {
+            Ty::Layout(layout_ty) => Some((layout_ty, e.field)),
+            _ => place_binding(&e.expression),
+        },
+        typed::ExpressionKind::IndexExpression(e) => place_binding(&e.expression),
+        typed::ExpressionKind::GroupExpression(e) => place_binding(&e.inner),
+        _ => None,
+    }
+}
+
+pub fn infer_expression(expression: &untyped::ExpressionKind, env: &mut Env) -> typed::ExpressionKind {
+    infer_expression_expecting(expression, None, env)
+}
+
+/// Infers an expression whose type is known from its context, e.g. the declared type of a `let`.
+/// Only unsuffixed literals make use of the expected type, checking it is up to the caller.
+pub fn infer_expression_expecting(expression: &untyped::ExpressionKind, expected: Option<Ty>, env: &mut Env) -> typed::ExpressionKind {
+    match expression {
+        untyped::ExpressionKind::LiteralExpression(literal_expression) => {
+            typed::ExpressionKind::LiteralExpression(infer_literal_expression(literal_expression, expected, false, env))
+        },
+        untyped::ExpressionKind::BinaryExpression(binary_expression) => {
+            // an unsuffixed literal operand takes the element type of the other operand, e.g. `2.0` in `v * 2.0`
+            let (lhs, rhs) = if binary_expression.lhs.is_unsuffixed_literal() {
+                let rhs = infer_expression_expecting(&binary_expression.rhs, expected, env);
+                let lhs = infer_expression_expecting(&binary_expression.lhs, operand_expectation(&rhs, expected), env);
+                (lhs, rhs)
+            } else {
+                let lhs = infer_expression_expecting(&binary_expression.lhs, expected, env);
+                let rhs = infer_expression_expecting(&binary_expression.rhs, operand_expectation(&lhs, expected), env);
+                (lhs, rhs)
+            };
+            let operator = binary_expression.operator;
+
+            let ty = if lhs.ty().is_error() || rhs.ty().is_error() {
+                Ty::Error
+            } else if let Some(ty) = binary_operator_ty(operator, lhs.ty(), rhs.ty()) {
+                ty
+            } else {
+                let error = TypeCheckError::InvalidBinaryOperands {
+                    operator: operator.symbol(),
+                    lhs: env.display(lhs.ty()),
+                    rhs: env.display(rhs.ty()),
+                    span: binary_expression.span,
+                };
+                env.add_error(error);
+                Ty::Error
+            };
+
+            typed::ExpressionKind::BinaryExpression(typed::BinaryExpression::new(
+                Box::new(lhs),
+                Box::new(rhs),
+                operator,
+                ty,
+                binary_expression.span,
+            ))
+        },
+        untyped::ExpressionKind::UnaryExpression(unary_expression) => {
+            let operator = unary_expression.operator;
+            let operand = match (operator, unary_expression.operand.as_ref()) {
+                (UnaryOperator::FlipSign, untyped::ExpressionKind::LiteralExpression(literal_expression)) => {
+                    typed::ExpressionKind::LiteralExpression(infer_literal_expression(literal_expression, expected, true, env))
+                },
+                (_, operand) => infer_expression_expecting(operand, expected, env),
+            };
+
+            let ty = if operand.ty().is_error() {
+                Ty::Error
+            } else if let Some(ty) = unary_operator_ty(operator, operand.ty()) {
+                ty
+            } else {
+                let error = TypeCheckError::InvalidUnaryOperand {
+                    operator: operator.symbol(),
+                    operand: env.display(operand.ty()),
+                    span: unary_expression.span,
+                };
+                env.add_error(error);
+                Ty::Error
+            };
+
+            typed::ExpressionKind::UnaryExpression(typed::UnaryExpression::new(
+                Box::new(operand),
+                operator,
+                ty,
+                unary_expression.span,
+            ))
+        },
+        untyped::ExpressionKind::GroupExpression(group_expression) => {
+            let inner = infer_expression_expecting(&group_expression.inner, expected, env);
+            let ty = inner.ty();
+
+            typed::ExpressionKind::GroupExpression(typed::GroupExpression::new(
+                Box::new(inner),
+                ty,
+                group_expression.span,
+            ))
+        },
+        untyped::ExpressionKind::CallExpression(call_expression) => {
+            typed::ExpressionKind::CallExpression(infer_call_expression(call_expression, expected, env))
+        },
+        untyped::ExpressionKind::MethodCallExpression(method_call_expression) => {
+            typed::ExpressionKind::CallExpression(infer_method_call_expression(method_call_expression, env))
+        },
+        untyped::ExpressionKind::FieldAccessExpression(field_access_expression) => {
+            let base = infer_expression(&field_access_expression.expression, env);
+            let ty = infer_field_ty(base.ty(), field_access_expression.field, field_access_expression.span, env);
+
+            typed::ExpressionKind::FieldAccessExpression(typed::FieldAccessExpression::new(
+                Box::new(base),
+                field_access_expression.field,
+                ty,
+                field_access_expression.span,
+            ))
+        },
+        untyped::ExpressionKind::IndexExpression(index_expression) => {
+            let base = infer_expression(&index_expression.expression, env);
+            let index = infer_expression(&index_expression.index, env);
+
+            let index_ty = index.ty();
+            let is_integer = matches!(index_ty, Ty::Scalar(scalar) if scalar.is_integer());
+            if !is_integer && !index_ty.is_error() {
+                let error = TypeCheckError::InvalidIndex {
+                    ty: env.display(index_ty),
+                    span: index.span(),
+                };
+                env.add_error(error);
+            }
+
+            let ty = infer_index_ty(base.ty(), base.span(), env);
+
+            typed::ExpressionKind::IndexExpression(typed::IndexExpression::new(
+                Box::new(base),
+                Box::new(index),
+                ty,
+                index_expression.span,
+            ))
+        },
+        untyped::ExpressionKind::PathExpression(path_expression) => {
+            let mut variant = None;
+            let ty = match (env.lookup(path_expression.path), &path_expression.item) {
+                (Some(ty), _) => ty,
+                (None, Some(NamedItemKind::Symbol(symbol))) if symbol.kind == SymbolKind::Value => const_ty(symbol, env),
+                (None, Some(NamedItemKind::Symbol(symbol))) if symbol.kind == SymbolKind::Enum && path_expression.member.is_some() => {
+                    match infer_variant(symbol, path_expression, env) {
+                        Some((ty, value)) => {
+                            variant = value;
+                            ty
+                        },
+                        None => Ty::Error,
+                    }
+                },
+                (None, _) => {
+                    let error = TypeCheckError::UnresolvedName {
+                        name: env.name(path_expression.path),
+                        span: path_expression.span,
+                    };
+                    env.add_error(error);
+                    Ty::Error
+                },
+            };
+
+            typed::ExpressionKind::PathExpression(typed::PathExpression::new(
+                path_expression.path,
+                variant,
+                ty,
+                path_expression.span,
+            ))
+        },
+        untyped::ExpressionKind::StructExpression(struct_expression) => {
+            // TODO struct expressions are not lowered yet
+            typed::ExpressionKind::StructExpression(typed::StructExpression::new(Ty::Error, struct_expression.span))
+        },
+        untyped::ExpressionKind::BlockExpression(block_expression) => {
+            let block = infer_block(&block_expression.block, expected, env);
+            let ty = block.ty;
+
+            typed::ExpressionKind::BlockExpression(typed::BlockExpression::new(block, ty, block_expression.span))
+        },
+        untyped::ExpressionKind::MatchExpression(match_expression) => {
+            typed::ExpressionKind::MatchExpression(infer_match_expression(match_expression, expected, env))
+        },
+    }
+}
+
+/// Resolves the type and discriminant of an enum variant path like `LightKind::Point`
+fn infer_variant(symbol: &Symbol, path_expression: &untyped::PathExpression, env: &mut Env) -> Option<(Ty, Option<u32>)> {
+    let enum_ty = EnumTy {
+        file: symbol.file,
+        name: symbol.name,
+    };
+    let variant = path_expression.member?;
+
+    let value = enum_variants(enum_ty, env)?.into_iter().find(|(name, _)| *name == variant);
+    match value {
+        Some((_, value)) => Some((Ty::Enum(enum_ty), value)),
+        None => {
+            let error = TypeCheckError::UnknownVariant {
+                variant: env.name(variant),
+                ty: env.name(symbol.name),
+                span: path_expression.span,
+            };
+            env.add_error(error);
+            None
+        },
+    }
+}
+
+fn infer_match_expression(match_expression: &untyped::MatchExpression, expected: Option<Ty>, env: &mut Env) -> typed::MatchExpression {
+    let scrutinee = infer_expression(&match_expression.scrutinee, env);
+    let scrutinee_ty = scrutinee.ty();
+
+    // matches are lowered into switches, which only take integer selectors
+    let valid = matches!(scrutinee_ty, Ty::Enum(_) | Ty::Scalar(ScalarTy::Int { .. }) | Ty::Scalar(ScalarTy::Bool) | Ty::Error);
+    if !valid {
+        let error = TypeCheckError::InvalidMatchScrutinee {
+            ty: env.display(scrutinee_ty),
+            span: scrutinee.span(),
+        };
+        env.add_error(error);
+    }
+
+    // every value a scrutinee can take, `None` if there are too many to list
+    let values: Option<Vec<(String, i128)>> = match scrutinee_ty {
+        Ty::Enum(enum_ty) => enum_variants(enum_ty, env).map(|variants| {
+            let enum_name = env.name(enum_ty.name);
+            variants.into_iter()
+                .filter_map(|(name, value)| Some((format!("{}::{}", enum_name, env.name(name)), value? as i128)))
+                .collect()
+        }),
+        Ty::Scalar(ScalarTy::Bool) => Some(vec![("false".to_string(), 0), ("true".to_string(), 1)]),
+        _ => None,
+    };
+
+    let mut matched = HashSet::new();
+    let mut has_wildcard = false;
+    let mut ty = None;
+    let mut arms = Vec::new();
+    for arm in &match_expression.arms {
+        let mut patterns = Vec::new();
+        for pattern in &arm.patterns {
+            let pattern = infer_pattern(pattern, scrutinee_ty, env);
+
+            let exhausted = matches!(&values, Some(values) if values.iter().all(|(_, value)| matched.contains(value)));
+            let unreachable = match &pattern {
+                typed::Pattern::Error { .. } => false,
+                _ if has_wildcard => true,
+                typed::Pattern::Value { value, .. } => !matched.insert(*value),
+                typed::Pattern::Wildcard { span } => {
+                    // a defensive `_` after covering every variant is only worth a warning
+                    if exhausted {
+                        env.add_warning(TypeCheckWarning::UnreachableWildcard { span: *span });
+                    }
+
+                    has_wildcard = true;
+                    false
+                },
+            };
+
+            if unreachable {
+                env.add_error(TypeCheckError::UnreachablePattern { span: pattern.span() });
+            }
+
+            patterns.push(pattern);
+        }
+
+        // every arm has to evaluate to the type of the first one
+        let expression = infer_expression_expecting(&arm.expression, ty.or(expected), env);
+        match ty {
+            Some(ty) => env.expect(ty, expression.ty(), expression.span()),
+            None => ty = Some(expression.ty()),
+        }
+
+        arms.push(typed::MatchArm::new(patterns, expression, arm.span));
+    }
+
+    if !has_wildcard && !scrutinee_ty.is_error() {
+        let missing = match &values {
+            Some(values) => values.iter()
+                .filter(|(_, value)| !matched.contains(value))
+                .map(|(name, _)| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => "`_`".to_string(),
+        };
+
+        if !missing.is_empty() {
+            let error = TypeCheckError::NonExhaustiveMatch {
+                missing,
+                span: match_expression.span,
+            };
+            env.add_error(error);
+        }
+    }
+
+    typed::MatchExpression::new(
+        Box::new(scrutinee),
+        arms,
+        ty.unwrap_or(Ty::Unit),
+        match_expression.span,
+    )
+}
+
+/// Types a pattern against the scrutinee, a value pattern holds the discriminant or integer it matches
+fn infer_pattern(pattern: &untyped::Pattern, scrutinee: Ty, env: &mut Env) -> typed::Pattern {
+    match pattern {
+        untyped::Pattern::Wildcard { span } => typed::Pattern::Wildcard { span: *span },
+        untyped::Pattern::Literal(literal_pattern) => {
+            let literal_expression = untyped::LiteralExpression::new(literal_pattern.value, literal_pattern.suffix, literal_pattern.span);
+            let literal = infer_literal_expression(&literal_expression, Some(scrutinee), literal_pattern.negated, env);
+            env.expect(scrutinee, literal.ty, literal.span);
+
+            let value = match literal.value {
+                LiteralValue::Integer(value) if literal_pattern.negated => Some(-(value as i128)),
+                LiteralValue::Integer(value) => Some(value as i128),
+                LiteralValue::Boolean(value) => Some(value as i128),
+                LiteralValue::Float(_) => None,
+            };
+
+            match value {
+                Some(value) if literal.ty == scrutinee => typed::Pattern::Value { value, span: literal_pattern.span },
+                _ => typed::Pattern::Error { span: literal_pattern.span },
+            }
+        },
+        untyped::Pattern::Path(path) => {
+            let span = path.span;
+            let symbol = match &path.item {
+                Some(NamedItemKind::Symbol(symbol)) if symbol.kind == SymbolKind::Enum => symbol,
+                _ => {
+                    let error = TypeCheckError::UnresolvedName {
+                        name: env.name(path.path),
+                        span,
+                    };
+                    env.add_error(error);
+                    return typed::Pattern::Error { span };
+                },
+            };
+
+            match infer_variant(symbol, path, env) {
+                Some((ty, Some(value))) if ty == scrutinee => typed::Pattern::Value { value: value as i128, span },
+                Some((ty, _)) => {
+                    env.expect(scrutinee, ty, span);
+                    typed::Pattern::Error { span }
+                },
+                None => typed::Pattern::Error { span },
+            }
+        },
+    }
+}
+
+/// Types a literal. Unsuffixed literals take the element type of the expected type if it is of the same kind
+/// and default to `i32` and `f32` otherwise. `negated` is set for the operand of a flip sign, e.g. `-128i8`.
+fn infer_literal_expression(literal_expression: &untyped::LiteralExpression, expected: Option<Ty>, negated: bool, env: &mut Env) -> typed::LiteralExpression {
+    // inside a generic function a literal can take the element type of a parameter, its range is checked per instantiation
+    if let (Some(Ty::Generic(generic)), None) = (expected, literal_expression.suffix) {
+        let class = match literal_expression.value {
+            LiteralValue::Integer(_) => Some(TypeClass::Integer),
+            LiteralValue::Float(_) => Some(TypeClass::Float),
+            LiteralValue::Boolean(_) => None,
+        };
+
+        if class.is_some() && generic.element == class {
+            return typed::LiteralExpression::new(literal_expression.value, Ty::Generic(generic.element_ty()), literal_expression.span);
+        }
+    }
+
+    let expected = expected.and_then(Ty::element);
+    let scalar = match (literal_expression.value, literal_expression.suffix) {
+        (LiteralValue::Boolean(_), _) => ScalarTy::Bool,
+        (_, Some(LiteralSuffix::Int { signed, width })) => ScalarTy::Int { signed, width },
+        (_, Some(LiteralSuffix::Float { width })) => ScalarTy::Float { width },
+        (LiteralValue::Integer(_), None) => match expected {
+            Some(element @ ScalarTy::Int { .. }) => element,
+            _ => ScalarTy::Int { signed: true, width: 32 },
+        },
+        (LiteralValue::Float(_), None) => match expected {
+            Some(element @ ScalarTy::Float { .. }) => element,
+            _ => ScalarTy::Float { width: 32 },
+        },
+    };
+
+    if !scalar.contains(literal_expression.value, negated) {
+        let error = TypeCheckError::LiteralOutOfRange {
+            ty: scalar.display(),
+            span: literal_expression.span,
+        };
+        env.add_error(error);
+    }
+
+    typed::LiteralExpression::new(literal_expression.value, Ty::Scalar(scalar), literal_expression.span)
+}
+
+/// The type expected of an operand next to an already inferred one, falls back to the expectation of the whole expression
+fn operand_expectation(other: &typed::ExpressionKind, expected: Option<Ty>) -> Option<Ty> {
+    other.ty().element_ty().or(expected)
+}
+
+/// Infers call arguments, unsuffixed literals are inferred last to take the type of their parameter,
+/// or for constructors and intrinsics the element type of the other arguments, e.g. `0.0` in `max(x, 0.0)`
+/// or `1` in `atomic_add(counter, 1)`
+fn infer_arguments(arguments: &[untyped::ExpressionKind], parameters: Option<&[Ty]>, expected: Option<Ty>, env: &mut Env) -> Vec<typed::ExpressionKind> {
+    let parameter = |index: usize| parameters.and_then(|parameters| parameters.get(index).copied());
+
+    let mut inferred = Vec::new();
+    for (index, argument) in arguments.iter().enumerate() {
+        let argument = (!argument.is_unsuffixed_literal()).then(|| infer_expression_expecting(argument, parameter(index), env));
+        inferred.push(argument);
+    }
+
+    let element = inferred.iter()
+        .flatten()
+        .find_map(|argument| match argument.ty() {
+            Ty::Atomic(element) => Some(Ty::Scalar(element)),
+            ty => ty.element_ty(),
+        })
+        .or(expected);
+
+    inferred.into_iter()
+        .zip(arguments)
+        .enumerate()
+        .map(|(index, (inferred, argument))| match inferred {
+            Some(inferred) => inferred,
+            None => infer_expression_expecting(argument, parameter(index).or(element), env),
+        })
+        .collect()
+}
+
+fn infer_call_expression(call_expression: &untyped::CallExpression, expected: Option<Ty>, env: &mut Env) -> typed::CallExpression {
+    let signature = match call_expression.callee.as_ref() {
+        untyped::ExpressionKind::PathExpression(path) if env.lookup(path.path).is_none() => match (&path.item, path.member) {
+            (Some(NamedItemKind::Symbol(symbol)), None) if symbol.kind == SymbolKind::Function => function_signature_ty(symbol, env),
+            (Some(NamedItemKind::Symbol(symbol)), Some(member)) if symbol.kind == SymbolKind::Struct => {
+                let struct_ty = StructTy {
+                    file: symbol.file,
+                    name: symbol.name,
+                };
+                method_signature_ty(struct_ty, member, env)
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+
+    // the stated type of a constructor like `vec3<f32>(...)`, other callees don't take generic arguments
+    let constructor_ty = match call_expression.callee.as_ref() {
+        untyped::ExpressionKind::PathExpression(path) if env.lookup(path.path).is_none() => match (&path.item, &path.generic_path) {
+            (Some(NamedItemKind::Primitive(primitive)), Some(generic_path)) if primitive.kind.is_constructible() => {
+                Some(resolve_type_path(generic_path, env))
+            },
+            (_, Some(_)) => {
+                let error = TypeCheckError::UnexpectedGenericArguments {
+                    name: env.name(path.path),
+                    span: path.span,
+                };
+                env.add_error(error);
+                None
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+
+    // the parameters of a generic function are only known once its generic arguments are inferred from the arguments
+    let parameters = signature.as_ref()
+        .filter(|signature| signature.generics.is_empty())
+        .map(|signature| signature.parameters.as_slice());
+    let arguments = infer_arguments(&call_expression.arguments, parameters, constructor_ty.or(expected), env);
+
+    let mut generic_arguments = Vec::new();
+    let (callee, ty) = match call_expression.callee.as_ref() {
+        untyped::ExpressionKind::PathExpression(path) if env.lookup(path.path).is_none() => {
+            match path.item.clone() {
+                Some(NamedItemKind::Primitive(primitive)) if primitive.kind.is_constructible() => {
+                    let ty = infer_constructor_ty(&primitive, &arguments, call_expression.span, env);
+
+                    // the components must match the stated element type, e.g. `vec3<f32>(1u32, 2u32, 3u32)` is rejected
+                    let ty = match constructor_ty {
+                        Some(constructor_ty) if !constructor_ty.is_error() => {
+                            env.expect(constructor_ty, ty, call_expression.span);
+                            constructor_ty
+                        },
+                        _ => ty,
+                    };
+                    (typed::Callee::Constructor(ty), ty)
+                },
+                Some(NamedItemKind::Primitive(primitive)) => {
+                    let error = TypeCheckError::NotCallable {
+                        ty: env.name(primitive.name),
+                        span: path.span,
+                    };
+                    env.add_error(error);
+                    (typed::Callee::Unresolved, Ty::Error)
+                },
+                Some(NamedItemKind::Intrinsic(intrinsic)) => {
+                    let ty = infer_intrinsic_ty(&intrinsic, &arguments, call_expression.span, env);
+
+                    if let (IntrinsicKind::Atomic(function), Some(atomic)) = (&intrinsic.kind, arguments.first()) {
+                        if function.writes() && matches!(atomic.ty(), Ty::Atomic(_)) {
+                            check_writable(atomic, env);
+                        }
+                    }
+                    (typed::Callee::Intrinsic(intrinsic.clone()), ty)
+                },
+                Some(NamedItemKind::Symbol(symbol)) if symbol.kind == SymbolKind::Function => {
+                    let (generics, ty) = infer_function_call_ty(&symbol, signature, &arguments, call_expression.span, env);
+                    generic_arguments = generics;
+                    (typed::Callee::Function(symbol), ty)
+                },
+                Some(NamedItemKind::Symbol(symbol)) if symbol.kind == SymbolKind::Struct && path.member.is_some() => {
+                    let struct_ty = StructTy {
+                        file: symbol.file,
+                        name: symbol.name,
+                    };
+
+                    match (path.member, signature) {
+                        (Some(name), Some(signature)) => {
+                            check_member_visibility(struct_ty, name, path.span, env);
+                            let ty = infer_impl_function_call_ty(name, &signature.parameters, signature.return_type, &arguments, call_expression.span, env);
+                            (typed::Callee::Method(struct_ty, name), ty)
+                        },
+                        (name, _) => {
+                            let error = TypeCheckError::UnknownMethod {
+                                name: name.map(|name| env.name(name)).unwrap_or_default(),
+                                ty: env.name(symbol.name),
+                                span: path.span,
+                            };
+                            env.add_error(error);
+                            (typed::Callee::Unresolved, Ty::Error)
+                        },
+                    }
+                },
+                Some(NamedItemKind::Symbol(symbol)) => {
+                    let error = TypeCheckError::NotCallable {
+                        ty: env.name(symbol.name),
+                        span: path.span,
+                    };
+                    env.add_error(error);
+                    (typed::Callee::Unresolved, Ty::Error)
+                },
+                None => {
+                    let error = TypeCheckError::UnresolvedName {
+                        name: env.name(path.path),
+                        span: path.span,
+                    };
+                    env.add_error(error);
+                    (typed::Callee::Unresolved, Ty::Error)
+                },
+            }
+        },
+        callee => {
+            let callee = infer_expression(callee, env);
+            if !callee.ty().is_error() {
+                let error = TypeCheckError::NotCallable {
+                    ty: env.display(callee.ty()),
+                    span: callee.span(),
+                };
+                env.add_error(error);
+            }
+
+            (typed::Callee::Unresolved, Ty::Error)
+        },
+    };
+
+    typed::CallExpression::new(callee, generic_arguments, arguments, ty, call_expression.span)
+}
+
+/// Types a method call like `light.luminance()`, it calls the function of the impl block with the receiver as first argument
+fn infer_method_call_expression(method_call_expression: &untyped::MethodCallExpression, env: &mut Env) -> typed::CallExpression {
+    let receiver = infer_expression(&method_call_expression.receiver, env);
+    let name = method_call_expression.method;
+
+    let struct_ty = match receiver.ty() {
+        Ty::Struct(struct_ty) => Some(struct_ty),
+        _ => None,
+    };
+    let signature = struct_ty.and_then(|struct_ty| method_signature_ty(struct_ty, name, env));
+
+    let parameters = signature.as_ref()
+        .filter(|signature| signature.has_receiver)
+        .map(|signature| &signature.parameters[1..]);
+    let arguments = infer_arguments(&method_call_expression.arguments, parameters, None, env);
+
+    let (callee, ty) = match (struct_ty, signature) {
+        (Some(struct_ty), Some(signature)) if signature.has_receiver => {
+            check_member_visibility(struct_ty, name, method_call_expression.span, env);
+            let ty = infer_impl_function_call_ty(name, &signature.parameters[1..], signature.return_type, &arguments, method_call_expression.span, env);
+            (typed::Callee::Method(struct_ty, name), ty)
+        },
+        (Some(struct_ty), Some(_)) => {
+            let error = TypeCheckError::NotAMethod {
+                name: env.name(name),
+                ty: env.name(struct_ty.name),
+                span: method_call_expression.span,
+            };
+            env.add_error(error);
+            (typed::Callee::Unresolved, Ty::Error)
+        },
+        _ if receiver.ty().is_error() => (typed::Callee::Unresolved, Ty::Error),
+        _ => {
+            let error = TypeCheckError::UnknownMethod {
+                name: env.name(name),
+                ty: env.display(receiver.ty()),
+                span: method_call_expression.span,
+            };
+            env.add_error(error);
+            (typed::Callee::Unresolved, Ty::Error)
+        },
+    };
+
+    let arguments = std::iter::once(receiver).chain(arguments).collect();
+    typed::CallExpression::new(callee, Vec::new(), arguments, ty, method_call_expression.span)
+}
+
+/// Checks the arguments of a call to a function of an impl block, generic ones are reported at their declaration
+fn infer_impl_function_call_ty(name: Name, parameters: &[Ty], return_type: Ty, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> Ty {
+    if parameters.iter().chain(Some(&return_type)).any(|ty| matches!(ty, Ty::Generic(_))) {
+        return Ty::Error;
+    }
+
+    if parameters.len() != arguments.len() {
+        let error = TypeCheckError::ArgumentCount {
+            name: env.name(name),
+            expected: parameters.len(),
+            found: arguments.len(),
+            span,
+        };
+        env.add_error(error);
+        return return_type;
+    }
+
+    for (parameter, argument) in parameters.iter().zip(arguments) {
+        env.expect(*parameter, argument.ty(), argument.span());
+    }
+
+    return_type
+}
+
+/// Checks the arguments of a call to a user defined function against its signature,
+/// returns the generic arguments for a generic function and the type of the call
+fn infer_function_call_ty(symbol: &Symbol, signature: Option<FunctionSignatureTy>, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> (Vec<Ty>, Ty) {
+    let signature = match signature {
+        Some(signature) => signature,
+        None => return (Vec::new(), Ty::Error),
+    };
+
+    if signature.parameters.len() != arguments.len() {
+        let error = TypeCheckError::ArgumentCount {
+            name: env.name(symbol.name),
+            expected: signature.parameters.len(),
+            found: arguments.len(),
+            span,
+        };
+        env.add_error(error);
+
+        let return_type = if signature.generics.is_empty() { signature.return_type } else { Ty::Error };
+        return (Vec::new(), return_type);
+    }
+
+    if !signature.generics.is_empty() {
+        return infer_generic_call_ty(symbol, signature, arguments, span, env);
+    }
+
+    for (parameter, argument) in signature.parameters.into_iter().zip(arguments) {
+        env.expect(parameter, argument.ty(), argument.span());
+    }
+
+    (Vec::new(), signature.return_type)
+}
+
+/// Infers the generic arguments of a call from its arguments, e.g. `T = vec3<f32>` for `saturate(color)`,
+/// checks them against the bounds and type checks the instantiation unless it is generic itself
+fn infer_generic_call_ty(symbol: &Symbol, signature: FunctionSignatureTy, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> (Vec<Ty>, Ty) {
+    let generic_index = |ty: Ty| signature.generics.iter().position(|generic| Ty::Generic(*generic) == ty);
+
+    let mut substitution = vec![None; signature.generics.len()];
+    for (parameter, argument) in signature.parameters.iter().zip(arguments) {
+        match generic_index(*parameter) {
+            Some(index) => match substitution[index] {
+                Some(ty) => env.expect(ty, argument.ty(), argument.span()),
+                None => substitution[index] = Some(argument.ty()),
+            },
+            None => env.expect(*parameter, argument.ty(), argument.span()),
+        }
+    }
+
+    let function = env.name(symbol.name);
+    let mut generic_arguments = Vec::new();
+    for (generic, argument) in signature.generics.iter().zip(substitution) {
+        match argument {
+            Some(argument) => generic_arguments.push(argument),
+            None => {
+                let error = TypeCheckError::CannotInferGeneric {
+                    name: env.name(generic.name),
+                    function,
+                    span,
+                };
+                env.add_error(error);
+                return (Vec::new(), Ty::Error);
+            },
+        }
+    }
+
+    let instance = format!("{}<{}>", function, generic_arguments.iter().map(|ty| env.display(*ty)).collect::<Vec<_>>().join(", "));
+
+    let mut valid = true;
+    for (generic, argument) in signature.generics.iter().zip(&generic_arguments) {
+        let classes = generic.element.into_iter().chain(generic.components.map(TypeClass::Vector));
+        for class in classes {
+            if !class.contains(*argument) && !argument.is_error() {
+                let error = TypeCheckError::UnsatisfiedBound {
+                    instance: instance.clone(),
+                    ty: env.display(*argument),
+                    bound: class.display(),
+                    span,
+                };
+                env.add_error(error);
+                valid = false;
+            }
+        }
+    }
+
+    let return_type = match generic_index(signature.return_type) {
+        Some(index) => generic_arguments[index],
+        None => signature.return_type,
+    };
+
+    // calls inside a generic function are checked when the calling function is instantiated
+    let is_concrete = generic_arguments.iter().all(|ty| !matches!(ty, Ty::Generic(_) | Ty::Error));
+    if valid && is_concrete {
+        let function_instance = env.db().get_function_instance(symbol.file, symbol.name, generic_arguments.clone());
+        if let Some(error) = function_instance.and_then(|function_instance| function_instance.errors.into_iter().next()) {
+            let error = TypeCheckError::InstantiationFailed {
+                instance,
+                error: Box::new(error),
+                span,
+            };
+            env.add_error(error);
+        }
+    }
+
+    (generic_arguments, return_type)
+}
+
+/// Resolves the declared type of a constant in any file
+fn const_ty(symbol: &Symbol, env: &Env) -> Ty {
+    let db = env.db();
+    let module = db.get_hir(symbol.file);
+
+    let constant = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Const(constant) if constant.name == symbol.name => Some(constant),
+        _ => None,
+    });
+
+    // diagnostics for the type are reported when checking the declaring module
+    match constant {
+        Some(constant) => resolve_type(&constant.type_reference, &mut Env::new(db, symbol.file)),
+        None => Ty::Error,
+    }
+}
+
+/// Resolves the variants and their discriminants of an enum declared in any file
+fn enum_variants(enum_ty: EnumTy, env: &Env) -> Option<Vec<(Name, Option<u32>)>> {
+    let db = env.db();
+    let module = db.get_hir(enum_ty.file);
+
+    let enumeration = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Enum(enumeration) if enumeration.name == enum_ty.name => Some(enumeration),
+        _ => None,
+    })?;
+
+    // diagnostics for the discriminants are reported when checking the declaring module
+    let values = eval_discriminants(enumeration, &mut Env::new(db, enum_ty.file));
+    Some(enumeration.variants.iter().map(|variant| variant.name).zip(values).collect())
+}
+
+/// The generic parameters, argument and return types of a function
+struct FunctionSignatureTy {
+    generics: Vec<GenericTy>,
+    parameters: Vec<Ty>,
+
+    /// Whether the first parameter is the receiver of a method
+    has_receiver: bool,
+    return_type: Ty,
+}
+
+/// Resolves the argument and return types of a function declared in any file
+fn function_signature_ty(symbol: &Symbol, env: &Env) -> Option<FunctionSignatureTy> {
+    let db = env.db();
+    let module = db.get_hir(symbol.file);
+
+    let function = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Function(function) if function.signature.name == symbol.name => Some(function),
+        _ => None,
+    })?;
+
+    Some(signature_ty(&function.signature, symbol.file, env))
+}
+
+/// Resolves the argument and return types of a function in any impl block of a struct
+fn method_signature_ty(struct_ty: StructTy, name: Name, env: &Env) -> Option<FunctionSignatureTy> {
+    let db = env.db();
+    let module = db.get_hir(struct_ty.file);
+
+    let function = module.items.iter()
+        .filter_map(|item| match item {
+            untyped::ItemKind::Impl(implementation) if implementation.name == struct_ty.name => Some(implementation),
+            _ => None,
+        })
+        .find_map(|implementation| implementation.functions.iter().find(|function| function.signature.name == name))?;
+
+    Some(signature_ty(&function.signature, struct_ty.file, env))
+}
+
+fn signature_ty(signature: &untyped::FunctionSignature, file: FileId, env: &Env) -> FunctionSignatureTy {
+    // diagnostics for the signature are reported when checking the declaring module
+    let mut function_env = Env::new(env.db(), file);
+    let generics = declare_generic_parameters(signature, &mut function_env);
+    let parameters = signature.arguments.iter()
+        .map(|argument| resolve_type(&argument.type_reference, &mut function_env))
+        .collect();
+
+    let return_type = match &signature.return_type {
+        Some(return_type) => resolve_type(return_type, &mut function_env),
+        None => Ty::Unit,
+    };
+
+    FunctionSignatureTy {
+        generics,
+        parameters,
+        has_receiver: signature.has_receiver,
+        return_type,
+    }
+}
+
+/// Infers the type constructed by calling a primitive, e.g. `f32(1)`, `vec3(1.0, v.xy)` or `mat2x2(c0, c1)`.
+/// Vector and matrix constructors take their element type from the arguments.
+fn infer_constructor_ty(primitive: &Primitive, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> Ty {
+    if arguments.iter().any(|argument| argument.ty().is_error()) {
+        return Ty::Error;
+    }
+
+    if let Some(scalar) = primitive.kind.scalar() {
+        let ty = Ty::Scalar(scalar);
+        if arguments.len() != 1 {
+            env.add_error(TypeCheckError::ConstructorComponentCount {
+                ty: env.display(ty),
+                expected: 1,
+                found: arguments.len(),
+                span,
+            });
+        } else if !matches!(arguments[0].ty(), Ty::Scalar(_)) {
+            env.expect(ty, arguments[0].ty(), arguments[0].span());
+        }
+
+        return ty;
+    }
+
+    let first = match arguments.first() {
+        Some(first) => first,
+        None => {
+            let expected = match primitive.kind {
+                PrimitiveKind::Vector { components } => components,
+                PrimitiveKind::Matrix { columns, rows } => columns * rows,
+                _ => unreachable!(),
+            };
+
+            env.add_error(TypeCheckError::ConstructorComponentCount {
+                ty: env.name(primitive.name),
+                expected: expected as usize,
+                found: 0,
+                span,
+            });
+            return Ty::Error;
+        },
+    };
+
+    let element = match first.ty().element() {
+        Some(element) => element,
+        None => {
+            env.add_error(TypeCheckError::InvalidVectorElement {
+                name: env.name(primitive.name),
+                found: env.display(first.ty()),
+                span: first.span(),
+            });
+            return Ty::Error;
+        },
+    };
+
+    let ty = match primitive.kind {
+        PrimitiveKind::Vector { components } => Ty::Vector(element, components),
+        PrimitiveKind::Matrix { columns, rows } => {
+            if !element.is_float() {
+                env.add_error(TypeCheckError::InvalidVectorElement {
+                    name: env.name(primitive.name),
+                    found: element.display(),
+                    span,
+                });
+                return Ty::Error;
+            }
+
+            Ty::Matrix(element, columns, rows)
+        },
+        _ => unreachable!(),
+    };
+
+    let mut found = 0;
+    for argument in arguments {
+        let argument_ty = argument.ty();
+        let valid = match (ty, argument_ty) {
+            (_, Ty::Scalar(scalar)) => scalar == element,
+            (Ty::Vector(..), Ty::Vector(scalar, _)) => scalar == element,
+            (Ty::Matrix(_, _, rows), Ty::Vector(scalar, components)) => scalar == element && components == rows,
+            _ => false,
+        };
+
+        if !valid {
+            let expected = match ty {
+                Ty::Matrix(_, _, rows) => Ty::Vector(element, rows),
+                _ => Ty::Scalar(element),
+            };
+            env.expect(expected, argument_ty, argument.span());
+            return ty;
+        }
+
+        found += argument_ty.component_count().unwrap_or(0) as usize;
+    }
+
+    // a single scalar is splatted into every component
+    let splat = arguments.len() == 1 && matches!(arguments[0].ty(), Ty::Scalar(_));
+    let expected = ty.component_count().unwrap_or(0) as usize;
+    if !splat && found != expected {
+        env.add_error(TypeCheckError::ConstructorComponentCount {
+            ty: env.display(ty),
+            expected,
+            found,
+            span,
+        });
+    }
+
+    ty
+}
+
+/// Infers the element type of indexing into arrays, vectors (a component) and matrices (a column)
+fn infer_index_ty(base: Ty, span: TextRange, env: &mut Env) -> Ty {
+    match base {
+        Ty::Error => Ty::Error,
+        Ty::Array(array_ty) => env.db().lookup_intern_array_ty(array_ty).element,
+        Ty::Vector(element, _) => Ty::Scalar(element),
+        Ty::Matrix(element, _, rows) => Ty::Vector(element, rows),
+        _ => {
+            let error = TypeCheckError::NotIndexable {
+                ty: env.display(base),
+                span,
+            };
+            env.add_error(error);
+            Ty::Error
+        },
+    }
+}
+
+fn infer_field_ty(base: Ty, field: Name, span: TextRange, env: &mut Env) -> Ty {
+    let field_name = env.name(field);
+
+    let ty = match base {
+        Ty::Error => return Ty::Error,
+        Ty::Vector(element, components) => swizzle_ty(&field_name, element, components),
+        Ty::Struct(struct_ty) => struct_field_ty(struct_ty, field, env),
+        Ty::Layout(layout_ty) => layout_field_ty(layout_ty, field, env),
+        _ => None,
+    };
+
+    match ty {
+        Some(ty) => {
+            if let Ty::Struct(struct_ty) = base {
+                check_member_visibility(struct_ty, field, span, env);
+            }
+
+            ty
+        },
+        None => {
+            let error = TypeCheckError::UnknownField {
+                field: field_name,
+                ty: env.display(base),
+                span,
+            };
+            env.add_error(error);
+            Ty::Error
+        },
+    }
+}
+
+/// Reports a field or impl function of a struct that is not visible from the current module
+fn check_member_visibility(struct_ty: StructTy, name: Name, span: TextRange, env: &mut Env) {
+    let db = env.db();
+    let symbol = match db.get_symbols(struct_ty.file).find_by_name_and_kind(struct_ty.name, SymbolKind::Struct) {
+        Some(symbol) => symbol,
+        None => return,
+    };
+
+    let visibility = match symbol.member.iter().find(|member| member.name == name) {
+        Some(member) => member.visibility,
+        None => return,
+    };
+
+    if !visibility.is_visible_from(db, struct_ty.file, env.current_file()) {
+        let error = TypeCheckError::PrivateMember {
+            name: env.name(name),
+            ty: env.name(struct_ty.name),
+            scope: visibility.scope(),
+            span,
+        };
+        env.add_error(error);
+    }
+}
+
+/// Resolves vector swizzles like `v.x`, `v.zyx` or `c.rgba`
+fn swizzle_ty(field: &str, element: ScalarTy, components: u8) -> Option<Ty> {
+    if field.is_empty() || field.len() > 4 {
+        return None;
+    }
+
+    let valid = ["xyzw", "rgba"].iter().any(|set| {
+        field.chars().all(|c| set.find(c).map(|index| index < components as usize).unwrap_or(false))
+    });
+
+    if !valid {
+        return None;
+    }
+
+    match field.len() {
+        1 => Some(Ty::Scalar(element)),
+        len => Some(Ty::Vector(element, len as u8)),
+    }
+}
+
+/// The type of a binding accessed through a layout argument of an entry point, e.g. `material.albedo`
+fn layout_field_ty(layout_ty: LayoutTy, field: Name, env: &Env) -> Option<Ty> {
+    let db = env.db();
+    let module = db.get_hir(layout_ty.file);
+
+    let layout = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Layout(layout) if layout.name == layout_ty.name => Some(layout),
+        _ => None,
+    })?;
+
+    let member = layout.members.iter().find(|member| member.name == field)?;
+
+    // diagnostics for the member type are reported when checking the declaring module
+    let mut layout_env = Env::new(db, layout_ty.file);
+    Some(resolve_type(&member.type_reference, &mut layout_env))
+}
+
+fn struct_field_ty(struct_ty: StructTy, field: Name, env: &Env) -> Option<Ty> {
+    let db = env.db();
+    let module = db.get_hir(struct_ty.file);
+
+    let structure = module.items.iter().find_map(|item| match item {
+        untyped::ItemKind::Struct(structure) if structure.name == struct_ty.name => Some(structure),
+        _ => None,
+    })?;
+
+    let member = structure.members.iter().find(|member| member.name == field)?;
+
+    // diagnostics for the member type are reported when checking the declaring module
+    let mut struct_env = Env::new(db, struct_ty.file);
+    Some(resolve_type(&member.type_reference, &mut struct_env))
+}
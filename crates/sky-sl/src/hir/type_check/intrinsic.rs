@@ -0,0 +1,291 @@
+use super::{Env, ScalarTy, TextureKind, Ty, TypeCheckError, TypeClass};
+use crate::hir::{intrinsic::{AtomicFunction, GenericBound, Intrinsic, IntrinsicKind, Overload, ScalarClass, ShapeBound, SignatureTy, TextureFunction}, primitive::TextureDimension, typed};
+use rowan::TextRange;
+
+const F32: ScalarTy = ScalarTy::Float { width: 32 };
+const I32: ScalarTy = ScalarTy::Int { signed: true, width: 32 };
+
+/// Infers the return type of a call to a built-in function like `clamp(x, 0.0, 1.0)` or `sample(albedo, linear, uv)`
+pub fn infer_intrinsic_ty(intrinsic: &Intrinsic, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> Ty {
+    if arguments.iter().any(|argument| argument.ty().is_error()) {
+        return Ty::Error;
+    }
+
+    let name = env.name(intrinsic.name);
+    match &intrinsic.kind {
+        IntrinsicKind::Overloaded(overloads) => infer_overloaded_ty(name, overloads, arguments, span, env),
+        IntrinsicKind::Texture(function) => infer_texture_function_ty(name, *function, arguments, span, env),
+        IntrinsicKind::Atomic(function) => infer_atomic_function_ty(name, *function, arguments, span, env),
+    }
+}
+
+/// Picks the first overload the arguments match, `T` is taken from the first argument passed as `T`
+fn infer_overloaded_ty(name: String, overloads: &[Overload], arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> Ty {
+    for overload in overloads {
+        if let Some(ty) = match_overload(overload, arguments) {
+            return ty;
+        }
+    }
+
+    if overloads.iter().all(|overload| overload.parameters.len() != arguments.len()) {
+        let error = TypeCheckError::ArgumentCount {
+            name,
+            expected: overloads[0].parameters.len(),
+            found: arguments.len(),
+            span,
+        };
+        env.add_error(error);
+        return Ty::Error;
+    }
+
+    let error = TypeCheckError::NoMatchingOverload {
+        name,
+        arguments: arguments.iter().map(|argument| env.display(argument.ty())).collect::<Vec<_>>().join(", "),
+        span,
+    };
+    env.add_error(error);
+    Ty::Error
+}
+
+fn match_overload(overload: &Overload, arguments: &[typed::ExpressionKind]) -> Option<Ty> {
+    if overload.parameters.len() != arguments.len() {
+        return None;
+    }
+
+    let generic = overload.parameters.iter()
+        .zip(arguments)
+        .find(|(parameter, _)| parameter.ty == SignatureTy::Generic)
+        .map(|(_, argument)| argument.ty())?;
+
+    if !overload.bound.accepts(generic) {
+        return None;
+    }
+
+    for (parameter, argument) in overload.parameters.iter().zip(arguments) {
+        if parameter.ty.instantiate(generic)? != argument.ty() {
+            return None;
+        }
+    }
+
+    overload.return_ty.instantiate(generic)
+}
+
+impl GenericBound {
+    pub fn accepts(self, ty: Ty) -> bool {
+        // a generic parameter is accepted if its bounds guarantee the overload's bound for every instantiation
+        if let Ty::Generic(generic) = ty {
+            let shape = match self.shape {
+                ShapeBound::ScalarOrVector => true,
+                ShapeBound::Vector => generic.components.is_some(),
+                ShapeBound::Vector3 => generic.components == Some(3),
+            };
+
+            let class = match self.class {
+                ScalarClass::Float | ScalarClass::Signed => generic.element == Some(TypeClass::Float),
+                ScalarClass::Integer => generic.element == Some(TypeClass::Integer),
+                ScalarClass::Numeric => generic.element.is_some(),
+                ScalarClass::Bool => false,
+            };
+
+            return shape && class && !generic.is_element;
+        }
+
+        let shape = match self.shape {
+            ShapeBound::ScalarOrVector => matches!(ty, Ty::Scalar(_) | Ty::Vector(..)),
+            ShapeBound::Vector => matches!(ty, Ty::Vector(..)),
+            ShapeBound::Vector3 => matches!(ty, Ty::Vector(_, 3)),
+        };
+
+        let element = match ty.element() {
+            Some(element) => element,
+            None => return false,
+        };
+
+        let class = match self.class {
+            ScalarClass::Float => element.is_float(),
+            ScalarClass::Signed => element.is_numeric() && element.is_signed(),
+            ScalarClass::Integer => element.is_integer(),
+            ScalarClass::Numeric => element.is_numeric(),
+            ScalarClass::Bool => element == ScalarTy::Bool,
+        };
+
+        shape && class
+    }
+}
+
+impl SignatureTy {
+    /// The concrete type for the given generic type `T`
+    pub fn instantiate(self, generic: Ty) -> Option<Ty> {
+        match self {
+            SignatureTy::Generic => Some(generic),
+            SignatureTy::Element => generic.element_ty(),
+            SignatureTy::Boolean if matches!(generic, Ty::Generic(_)) => None,
+            SignatureTy::Boolean => Some(generic.with_element(ScalarTy::Bool)),
+        }
+    }
+}
+
+/// The first argument is the texture, it decides which other arguments are expected
+fn infer_texture_function_ty(name: String, function: TextureFunction, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> Ty {
+    let texture = match arguments.first().map(|argument| argument.ty()) {
+        Some(Ty::Texture(texture)) => texture,
+        Some(ty) => {
+            let error = TypeCheckError::InvalidIntrinsicArgument {
+                name,
+                ty: env.display(ty),
+                span: arguments[0].span(),
+            };
+            env.add_error(error);
+            return Ty::Error;
+        },
+        None => {
+            let error = TypeCheckError::ArgumentCount {
+                name,
+                expected: 1,
+                found: 0,
+                span,
+            };
+            env.add_error(error);
+            return Ty::Error;
+        },
+    };
+
+    let supported = match function {
+        TextureFunction::Sample | TextureFunction::SampleLevel => match texture.kind {
+            TextureKind::Sampled(element) => element.is_float(),
+            TextureKind::Depth => true,
+            TextureKind::Storage(_) => false,
+        },
+        TextureFunction::SampleCompare | TextureFunction::SampleCompareLevel => texture.kind == TextureKind::Depth,
+        TextureFunction::Load => texture.dimension != TextureDimension::Cube,
+        TextureFunction::Store => matches!(texture.kind, TextureKind::Storage(_)),
+        TextureFunction::Dimensions => true,
+    };
+
+    if !supported {
+        let error = TypeCheckError::InvalidIntrinsicArgument {
+            name,
+            ty: env.display(Ty::Texture(texture)),
+            span: arguments[0].span(),
+        };
+        env.add_error(error);
+        return Ty::Error;
+    }
+
+    // the expected types of all arguments after the texture
+    let mut parameters = Vec::new();
+    let ty = match function {
+        TextureFunction::Sample | TextureFunction::SampleLevel => {
+            parameters.push(Ty::Sampler { comparison: false });
+            parameters.push(texture.coordinate_ty(F32));
+            if texture.arrayed {
+                parameters.push(Ty::Scalar(I32));
+            }
+            if function == TextureFunction::SampleLevel {
+                parameters.push(Ty::Scalar(F32));
+            }
+
+            texture.texel_ty()
+        },
+        TextureFunction::SampleCompare | TextureFunction::SampleCompareLevel => {
+            parameters.push(Ty::Sampler { comparison: true });
+            parameters.push(texture.coordinate_ty(F32));
+            if texture.arrayed {
+                parameters.push(Ty::Scalar(I32));
+            }
+            parameters.push(Ty::Scalar(F32));
+
+            Ty::Scalar(F32)
+        },
+        TextureFunction::Load => {
+            parameters.push(texture.coordinate_ty(I32));
+            if texture.arrayed {
+                parameters.push(Ty::Scalar(I32));
+            }
+
+            // storage textures have no mip levels
+            if !matches!(texture.kind, TextureKind::Storage(_)) {
+                parameters.push(Ty::Scalar(I32));
+            }
+
+            texture.texel_ty()
+        },
+        TextureFunction::Store => {
+            parameters.push(texture.coordinate_ty(I32));
+            if texture.arrayed {
+                parameters.push(Ty::Scalar(I32));
+            }
+            parameters.push(texture.texel_ty());
+
+            Ty::Unit
+        },
+        TextureFunction::Dimensions => texture.dimensions_ty(),
+    };
+
+    if arguments.len() != parameters.len() + 1 {
+        let error = TypeCheckError::ArgumentCount {
+            name,
+            expected: parameters.len() + 1,
+            found: arguments.len(),
+            span,
+        };
+        env.add_error(error);
+        return ty;
+    }
+
+    for (argument, parameter) in arguments[1..].iter().zip(parameters) {
+        env.expect(parameter, argument.ty(), argument.span());
+    }
+
+    ty
+}
+
+/// The first argument is the atomic, the other arguments are values of its integer type
+fn infer_atomic_function_ty(name: String, function: AtomicFunction, arguments: &[typed::ExpressionKind], span: TextRange, env: &mut Env) -> Ty {
+    let element = match arguments.first().map(|argument| argument.ty()) {
+        Some(Ty::Atomic(element)) => element,
+        Some(ty) => {
+            let error = TypeCheckError::InvalidIntrinsicArgument {
+                name,
+                ty: env.display(ty),
+                span: arguments[0].span(),
+            };
+            env.add_error(error);
+            return Ty::Error;
+        },
+        None => {
+            let error = TypeCheckError::ArgumentCount {
+                name,
+                expected: 1,
+                found: 0,
+                span,
+            };
+            env.add_error(error);
+            return Ty::Error;
+        },
+    };
+
+    let (values, ty) = match function {
+        AtomicFunction::Load => (0, Ty::Scalar(element)),
+        AtomicFunction::Store => (1, Ty::Unit),
+        AtomicFunction::CompareExchange => (2, Ty::Scalar(element)),
+        _ => (1, Ty::Scalar(element)),
+    };
+
+    if arguments.len() != values + 1 {
+        let error = TypeCheckError::ArgumentCount {
+            name,
+            expected: values + 1,
+            found: arguments.len(),
+            span,
+        };
+        env.add_error(error);
+        return ty;
+    }
+
+    for argument in &arguments[1..] {
+        env.expect(Ty::Scalar(element), argument.ty(), argument.span());
+    }
+
+    ty
+}
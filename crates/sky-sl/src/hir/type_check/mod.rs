@@ -1,5 +1,17 @@
 mod env;
 mod error;
+mod infer;
+mod intrinsic;
+mod operator;
+mod recursion;
+mod resolve;
+mod ty;
 
 pub use env::*;
 pub use error::*;
+pub use infer::*;
+pub use intrinsic::*;
+pub use operator::*;
+pub use recursion::*;
+pub use resolve::*;
+pub use ty::*;
@@ -0,0 +1,175 @@
+use super::{ScalarTy, Ty, TypeClass};
+use crate::syn::cst::{BinaryOperator, UnaryOperator};
+
+/// Returns the result type of applying a binary operator, `None` if the operands are not supported
+pub fn binary_operator_ty(operator: BinaryOperator, lhs: Ty, rhs: Ty) -> Option<Ty> {
+    // enum values can only be compared with values of the same enum
+    if let Ty::Enum(_) = lhs {
+        let comparison = matches!(operator, BinaryOperator::Equals | BinaryOperator::NotEquals);
+        return (comparison && lhs == rhs).then_some(Ty::Scalar(ScalarTy::Bool));
+    }
+
+    if matches!(lhs, Ty::Generic(_)) || matches!(rhs, Ty::Generic(_)) {
+        return generic_binary_operator_ty(operator, lhs, rhs);
+    }
+
+    let element = lhs.element()?;
+    if rhs.element()? != element {
+        return None;
+    }
+
+    match operator {
+        BinaryOperator::Add | BinaryOperator::Subtract => {
+            if !element.is_numeric() {
+                return None;
+            }
+
+            match (lhs, rhs) {
+                (Ty::Matrix(..), Ty::Matrix(..)) if lhs == rhs => Some(lhs),
+                _ => broadcast(lhs, rhs),
+            }
+        },
+        BinaryOperator::Multiply => {
+            if !element.is_numeric() {
+                return None;
+            }
+
+            match (lhs, rhs) {
+                (Ty::Matrix(_, columns, rows), Ty::Vector(_, components)) if columns == components => Some(Ty::Vector(element, rows)),
+                (Ty::Vector(_, components), Ty::Matrix(_, columns, rows)) if rows == components => Some(Ty::Vector(element, columns)),
+                (Ty::Matrix(_, lhs_columns, lhs_rows), Ty::Matrix(_, rhs_columns, rhs_rows)) if lhs_columns == rhs_rows => Some(Ty::Matrix(element, rhs_columns, lhs_rows)),
+                (Ty::Matrix(..), Ty::Scalar(_)) => Some(lhs),
+                (Ty::Scalar(_), Ty::Matrix(..)) => Some(rhs),
+                _ => broadcast(lhs, rhs),
+            }
+        },
+        BinaryOperator::Divide => {
+            if !element.is_numeric() {
+                return None;
+            }
+
+            match (lhs, rhs) {
+                (Ty::Matrix(..), Ty::Scalar(_)) => Some(lhs),
+                _ => broadcast(lhs, rhs),
+            }
+        },
+        BinaryOperator::Remainder => {
+            if !element.is_numeric() {
+                return None;
+            }
+
+            broadcast(lhs, rhs)
+        },
+        BinaryOperator::Equals | BinaryOperator::NotEquals => {
+            if lhs != rhs || matches!(lhs, Ty::Matrix(..)) {
+                return None;
+            }
+
+            Some(lhs.with_element(ScalarTy::Bool))
+        },
+        BinaryOperator::GreaterThan
+        | BinaryOperator::GreatherOrEqualThan
+        | BinaryOperator::LessThan
+        | BinaryOperator::LessOrEqualThan => {
+            if lhs != rhs || !element.is_numeric() || matches!(lhs, Ty::Matrix(..)) {
+                return None;
+            }
+
+            Some(lhs.with_element(ScalarTy::Bool))
+        },
+        BinaryOperator::BitwiseAnd | BinaryOperator::BitwiseOr | BinaryOperator::BitwiseXOr => {
+            if element.is_float() || lhs != rhs || matches!(lhs, Ty::Matrix(..)) {
+                return None;
+            }
+
+            Some(lhs)
+        },
+        BinaryOperator::LeftShift | BinaryOperator::RightShift => {
+            if !element.is_integer() {
+                return None;
+            }
+
+            match (lhs, rhs) {
+                (Ty::Vector(..), Ty::Scalar(_)) => Some(lhs),
+                _ if lhs == rhs && !matches!(lhs, Ty::Matrix(..)) => Some(lhs),
+                _ => None,
+            }
+        },
+        BinaryOperator::Exponent => {
+            if !element.is_float() {
+                return None;
+            }
+
+            broadcast(lhs, rhs)
+        },
+    }
+}
+
+/// Returns the result type of applying a unary operator, `None` if the operand is not supported
+pub fn unary_operator_ty(operator: UnaryOperator, operand: Ty) -> Option<Ty> {
+    if let Ty::Generic(generic) = operand {
+        let supported = match operator {
+            UnaryOperator::KeepSign => generic.element.is_some(),
+            UnaryOperator::FlipSign => generic.element == Some(TypeClass::Float),
+            UnaryOperator::BitwiseNot => generic.element == Some(TypeClass::Integer),
+            UnaryOperator::BooleanNot => false,
+        };
+
+        return supported.then_some(operand);
+    }
+
+    let element = operand.element()?;
+
+    let supported = match operator {
+        UnaryOperator::BooleanNot => element == ScalarTy::Bool && !matches!(operand, Ty::Matrix(..)),
+        UnaryOperator::BitwiseNot => element.is_integer() && !matches!(operand, Ty::Matrix(..)),
+        UnaryOperator::KeepSign => element.is_numeric(),
+        UnaryOperator::FlipSign => element.is_numeric() && element.is_signed(),
+    };
+
+    if supported {
+        Some(operand)
+    } else {
+        None
+    }
+}
+
+/// Arithmetic on generic parameters, either between two values of the same parameter or with its element type,
+/// e.g. `x * 2.0` for `x: T` with `T: float`. Comparing elements of a parameter gives a `bool`.
+fn generic_binary_operator_ty(operator: BinaryOperator, lhs: Ty, rhs: Ty) -> Option<Ty> {
+    let (lhs, rhs) = match (lhs, rhs) {
+        (Ty::Generic(lhs), Ty::Generic(rhs)) => (lhs, rhs),
+        _ => return None,
+    };
+
+    if lhs.name != rhs.name || lhs.element.is_none() {
+        return None;
+    }
+
+    match operator {
+        // an element operand is applied to every component
+        BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => {
+            Some(Ty::Generic(if lhs.is_element { rhs } else { lhs }))
+        },
+        BinaryOperator::Equals
+        | BinaryOperator::NotEquals
+        | BinaryOperator::GreaterThan
+        | BinaryOperator::GreatherOrEqualThan
+        | BinaryOperator::LessThan
+        | BinaryOperator::LessOrEqualThan => {
+            (lhs.is_element && rhs.is_element).then_some(Ty::Scalar(ScalarTy::Bool))
+        },
+        _ => None,
+    }
+}
+
+/// Component-wise operation on scalars and vectors, where a scalar operand is applied to every vector component
+fn broadcast(lhs: Ty, rhs: Ty) -> Option<Ty> {
+    match (lhs, rhs) {
+        (Ty::Scalar(_), Ty::Scalar(_)) => Some(lhs),
+        (Ty::Vector(_, lhs_components), Ty::Vector(_, rhs_components)) if lhs_components == rhs_components => Some(lhs),
+        (Ty::Vector(..), Ty::Scalar(_)) => Some(lhs),
+        (Ty::Scalar(_), Ty::Vector(..)) => Some(rhs),
+        _ => None,
+    }
+}
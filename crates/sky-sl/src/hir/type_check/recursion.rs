@@ -0,0 +1,141 @@
+use super::{Env, TypeCheckError};
+use crate::{fs::FileId, hir::{HirDatabase, named::NamedItemKind, symbol::SymbolKind, untyped}, intern::Name};
+use rowan::TextRange;
+use std::collections::HashSet;
+
+/// A function identified by the file it is declared in, the struct of its impl block and its name
+type FunctionId = (FileId, Option<Name>, Name);
+
+/// The function the collected calls are made in, a method call on `self` calls a function of the same impl block
+#[derive(Clone, Copy)]
+struct Caller {
+    file: FileId,
+    impl_target: Option<Name>,
+    receiver: Name,
+}
+
+/// Reports calls that lead back to the calling function, GPU targets don't support recursion
+pub fn check_recursion(function: &untyped::FunctionKind, env: &mut Env) {
+    let current = (env.current_file(), env.impl_target(), function.signature.name);
+    let caller = Caller {
+        file: env.current_file(),
+        impl_target: env.impl_target(),
+        receiver: env.db().intern_name("self".to_string()),
+    };
+
+    let mut callees = Vec::new();
+    collect_block_callees(&function.block, caller, &mut callees);
+
+    for (callee, span) in callees {
+        let mut visited = HashSet::new();
+        if reaches(callee, current, &mut visited, env.db()) {
+            let error = TypeCheckError::RecursiveCall {
+                name: env.name(callee.2),
+                span,
+            };
+            env.add_error(error);
+        }
+    }
+}
+
+fn reaches(from: FunctionId, target: FunctionId, visited: &mut HashSet<FunctionId>, db: &dyn HirDatabase) -> bool {
+    if from == target {
+        return true;
+    }
+
+    if !visited.insert(from) {
+        return false;
+    }
+
+    let (file, impl_target, name) = from;
+    let module = db.get_hir(file);
+    let function = module.items.iter().find_map(|item| match (item, impl_target) {
+        (untyped::ItemKind::Function(function), None) if function.signature.name == name => Some(function),
+        (untyped::ItemKind::Impl(implementation), Some(target)) if implementation.name == target => {
+            implementation.functions.iter().find(|function| function.signature.name == name)
+        },
+        _ => None,
+    });
+
+    let caller = Caller {
+        file,
+        impl_target,
+        receiver: db.intern_name("self".to_string()),
+    };
+
+    let mut callees = Vec::new();
+    if let Some(function) = function {
+        collect_block_callees(&function.block, caller, &mut callees);
+    }
+
+    callees.into_iter().any(|(callee, _)| reaches(callee, target, visited, db))
+}
+
+fn collect_block_callees(block: &untyped::Block, caller: Caller, callees: &mut Vec<(FunctionId, TextRange)>) {
+    for statement in &block.statements {
+        match statement {
+            untyped::StatementKind::Let(let_statement) => collect_callees(&let_statement.expression, caller, callees),
+            untyped::StatementKind::Expression(expression_statement) => collect_callees(&expression_statement.expression, caller, callees),
+            untyped::StatementKind::Assign(assign_statement) => {
+                collect_callees(&assign_statement.place, caller, callees);
+                collect_callees(&assign_statement.value, caller, callees);
+            },
+        }
+    }
+}
+
+fn collect_callees(expression: &untyped::ExpressionKind, caller: Caller, callees: &mut Vec<(FunctionId, TextRange)>) {
+    match expression {
+        untyped::ExpressionKind::LiteralExpression(_)
+        | untyped::ExpressionKind::PathExpression(_)
+        | untyped::ExpressionKind::StructExpression(_) => {},
+        untyped::ExpressionKind::BinaryExpression(e) => {
+            collect_callees(&e.lhs, caller, callees);
+            collect_callees(&e.rhs, caller, callees);
+        },
+        untyped::ExpressionKind::UnaryExpression(e) => collect_callees(&e.operand, caller, callees),
+        untyped::ExpressionKind::GroupExpression(e) => collect_callees(&e.inner, caller, callees),
+        untyped::ExpressionKind::CallExpression(e) => {
+            if let untyped::ExpressionKind::PathExpression(path) = e.callee.as_ref() {
+                match (&path.item, path.member) {
+                    (Some(NamedItemKind::Symbol(symbol)), None) if symbol.kind == SymbolKind::Function => {
+                        callees.push(((symbol.file, None, symbol.name), e.span));
+                    },
+                    (Some(NamedItemKind::Symbol(symbol)), Some(member)) if symbol.kind == SymbolKind::Struct => {
+                        callees.push(((symbol.file, Some(symbol.name), member), e.span));
+                    },
+                    _ => {},
+                }
+            }
+
+            for argument in &e.arguments {
+                collect_callees(argument, caller, callees);
+            }
+        },
+        untyped::ExpressionKind::FieldAccessExpression(e) => collect_callees(&e.expression, caller, callees),
+        untyped::ExpressionKind::MethodCallExpression(e) => {
+            // only the type of `self` is known without type checking the receiver
+            match (e.receiver.as_ref(), caller.impl_target) {
+                (untyped::ExpressionKind::PathExpression(path), Some(target)) if path.path == caller.receiver => {
+                    callees.push(((caller.file, Some(target), e.method), e.span));
+                },
+                (receiver, _) => collect_callees(receiver, caller, callees),
+            }
+
+            for argument in &e.arguments {
+                collect_callees(argument, caller, callees);
+            }
+        },
+        untyped::ExpressionKind::IndexExpression(e) => {
+            collect_callees(&e.expression, caller, callees);
+            collect_callees(&e.index, caller, callees);
+        },
+        untyped::ExpressionKind::BlockExpression(e) => collect_block_callees(&e.block, caller, callees),
+        untyped::ExpressionKind::MatchExpression(e) => {
+            collect_callees(&e.scrutinee, caller, callees);
+            for arm in &e.arms {
+                collect_callees(&arm.expression, caller, callees);
+            }
+        },
+    }
+}
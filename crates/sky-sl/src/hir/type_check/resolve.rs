@@ -0,0 +1,335 @@
+use super::{ArrayTyData, EnumTy, Env, GenericTy, LayoutTy, ScalarTy, StructTy, TextureKind, TextureTy, Ty, TypeCheckError, TypeClass};
+use crate::hir::{memory_layout::LayoutRules, named::NamedItemKind, primitive::{PrimitiveKind, TexelFormat, TextureClass}, symbol::SymbolKind, untyped::{self, ItemPath, ItemPathSegment, TypeReference}};
+use rowan::TextRange;
+
+/// Resolves a type like `vec3<f32>` or `[Light; 4]`
+pub fn resolve_type(reference: &TypeReference, env: &mut Env) -> Ty {
+    match reference {
+        TypeReference::Path(path) => resolve_type_path(path, env),
+        TypeReference::Array(array) => {
+            let element = resolve_type(&array.element, env);
+            if element.is_error() {
+                return Ty::Error;
+            }
+
+            check_sized(element, array.element.span(), env);
+            check_not_opaque(element, array.element.span(), env);
+
+            Ty::Array(env.db().intern_array_ty(ArrayTyData {
+                element,
+                length: array.length,
+            }))
+        },
+        TypeReference::Generic(generic) => env.lookup_generic(generic.name).unwrap_or(Ty::Error),
+        TypeReference::Error(_) => Ty::Error,
+    }
+}
+
+/// Declares the generic parameters of a function to check it generically, see `resolve_generic_parameters`
+pub fn declare_generic_parameters(signature: &untyped::FunctionSignature, env: &mut Env) -> Vec<GenericTy> {
+    let generics = resolve_generic_parameters(signature, env);
+    env.set_generics(generics.iter().map(|generic| (generic.name, Ty::Generic(*generic))).collect());
+    generics
+}
+
+/// Resolves the bounds of the generic parameters of a function, e.g. `T: float + vec3`
+pub fn resolve_generic_parameters(signature: &untyped::FunctionSignature, env: &mut Env) -> Vec<GenericTy> {
+    let mut generics = Vec::new();
+    for parameter in &signature.generic_parameters {
+        let mut generic = GenericTy {
+            name: parameter.name,
+            element: None,
+            components: None,
+            is_element: false,
+        };
+
+        for bound in &parameter.bounds {
+            let name = env.name(bound.name);
+            let conflict = match TypeClass::from_name(&name) {
+                Some(class @ TypeClass::Float) | Some(class @ TypeClass::Integer) => matches!(generic.element.replace(class), Some(previous) if previous != class),
+                Some(TypeClass::Vector(components)) => matches!(generic.components.replace(components), Some(previous) if previous != components),
+                None => {
+                    env.add_error(TypeCheckError::UnknownTypeBound { name, span: bound.span });
+                    false
+                },
+            };
+
+            if conflict {
+                let error = TypeCheckError::ConflictingTypeBounds {
+                    name: env.name(parameter.name),
+                    span: bound.span,
+                };
+                env.add_error(error);
+            }
+        }
+
+        generics.push(generic);
+    }
+
+    generics
+}
+
+/// Returns true for runtime-sized arrays like `[f32]`, also when named through a type alias
+pub fn is_runtime_sized_array(ty: Ty, env: &Env) -> bool {
+    match ty {
+        Ty::Array(array_ty) => env.db().lookup_intern_array_ty(array_ty).length.is_none(),
+        _ => false,
+    }
+}
+
+/// Returns true for runtime-sized arrays and structs ending in one
+pub fn is_runtime_sized(ty: Ty, env: &Env) -> bool {
+    match ty {
+        Ty::Array(_) => is_runtime_sized_array(ty, env),
+        Ty::Struct(struct_ty) => {
+            let module = env.db().get_hir(struct_ty.file);
+            let last_member = module.items.iter().find_map(|item| match item {
+                untyped::ItemKind::Struct(structure) if structure.name == struct_ty.name => structure.members.last(),
+                _ => None,
+            });
+
+            // the member is resolved in the struct's own module, its errors are reported there
+            last_member.map(|member| {
+                let mut struct_env = Env::new(env.db(), struct_ty.file);
+                let member_ty = resolve_type(&member.type_reference, &mut struct_env);
+                is_runtime_sized_array(member_ty, env)
+            }).unwrap_or(false)
+        },
+        _ => false,
+    }
+}
+
+/// Reports a diagnostic if a runtime-sized type is used where the size must be known
+pub fn check_sized(ty: Ty, span: TextRange, env: &mut Env) {
+    if is_runtime_sized(ty, env) {
+        env.add_error(TypeCheckError::RuntimeSizedArray { span });
+    }
+}
+
+/// Reports a diagnostic if a texture or sampler is used where only plain data is allowed
+pub fn check_not_opaque(ty: Ty, span: TextRange, env: &mut Env) {
+    if ty.is_opaque() {
+        let error = TypeCheckError::OpaqueType {
+            ty: env.display(ty),
+            span,
+        };
+        env.add_error(error);
+    }
+}
+
+/// Returns true for atomics and arrays and structs containing them
+pub fn contains_atomic(ty: Ty, env: &Env) -> bool {
+    match ty {
+        Ty::Atomic(_) => true,
+        Ty::Array(array_ty) => contains_atomic(env.db().lookup_intern_array_ty(array_ty).element, env),
+        // the layout resolves the members in the struct's own module and stops at recursive structs
+        Ty::Struct(struct_ty) => env.db().struct_layout(struct_ty, LayoutRules::Std430).members.iter().any(|member| contains_atomic(member.ty, env)),
+        _ => false,
+    }
+}
+
+/// Reports a diagnostic if a type containing atomics is used outside of storage and workgroup memory
+pub fn check_no_atomics(ty: Ty, span: TextRange, env: &mut Env) {
+    if contains_atomic(ty, env) {
+        let error = TypeCheckError::AtomicOutsideSharedMemory {
+            ty: env.display(ty),
+            span,
+        };
+        env.add_error(error);
+    }
+}
+
+/// Resolves a type path like `f32`, `vec3<f32>` or `foo::Bar` to a type
+pub fn resolve_type_path(path: &ItemPath, env: &mut Env) -> Ty {
+    resolve_type_segment(path.last_segment(), env)
+}
+
+fn resolve_type_segment(segment: &ItemPathSegment, env: &mut Env) -> Ty {
+    let name = env.name(segment.name);
+
+    match &segment.item {
+        NamedItemKind::Primitive(primitive) => resolve_primitive(primitive.kind, segment, name, env),
+        NamedItemKind::Symbol(symbol) if symbol.kind == SymbolKind::Struct => {
+            if !expect_generic_arguments(segment, &name, 0, env) {
+                return Ty::Error;
+            }
+
+            Ty::Struct(StructTy {
+                file: symbol.file,
+                name: symbol.name,
+            })
+        },
+        NamedItemKind::Symbol(symbol) if symbol.kind == SymbolKind::Enum => {
+            if !expect_generic_arguments(segment, &name, 0, env) {
+                return Ty::Error;
+            }
+
+            Ty::Enum(EnumTy {
+                file: symbol.file,
+                name: symbol.name,
+            })
+        },
+        NamedItemKind::Symbol(symbol) if symbol.kind == SymbolKind::Layout => {
+            if !expect_generic_arguments(segment, &name, 0, env) {
+                return Ty::Error;
+            }
+
+            Ty::Layout(LayoutTy {
+                file: symbol.file,
+                name: symbol.name,
+            })
+        },
+        NamedItemKind::Symbol(symbol) if symbol.kind == SymbolKind::TypeAlias => {
+            if !expect_generic_arguments(segment, &name, 0, env) {
+                return Ty::Error;
+            }
+
+            // errors in the alias itself are reported when checking its module
+            env.db().resolve_type_alias(symbol.file, symbol.name).unwrap_or(Ty::Error)
+        },
+        NamedItemKind::Symbol(_) | NamedItemKind::Intrinsic(_) => {
+            env.add_error(TypeCheckError::NotAType { name, span: segment.span });
+            Ty::Error
+        },
+    }
+}
+
+fn resolve_primitive(kind: PrimitiveKind, segment: &ItemPathSegment, name: String, env: &mut Env) -> Ty {
+    match kind {
+        PrimitiveKind::Boolean | PrimitiveKind::Integer { .. } | PrimitiveKind::FloatingPoint { .. } => {
+            if !expect_generic_arguments(segment, &name, 0, env) {
+                return Ty::Error;
+            }
+
+            Ty::Scalar(kind.scalar().unwrap())
+        },
+        PrimitiveKind::Vector { components } => match resolve_element(segment, &name, env) {
+            Some(element) => Ty::Vector(element, components),
+            None => Ty::Error,
+        },
+        PrimitiveKind::Matrix { columns, rows } => match resolve_element(segment, &name, env) {
+            Some(element) if element.is_float() => Ty::Matrix(element, columns, rows),
+            Some(element) => {
+                invalid_element(&name, element, segment, env);
+                Ty::Error
+            },
+            None => Ty::Error,
+        },
+        PrimitiveKind::Texture { dimension, arrayed, class } => {
+            let kind = match class {
+                TextureClass::Sampled => match resolve_element(segment, &name, env) {
+                    // textures are sampled as 32 bit floats or integers
+                    Some(element @ ScalarTy::Int { width: 32, .. }) | Some(element @ ScalarTy::Float { width: 32 }) => {
+                        TextureKind::Sampled(element)
+                    },
+                    Some(element) => {
+                        invalid_element(&name, element, segment, env);
+                        return Ty::Error;
+                    },
+                    None => return Ty::Error,
+                },
+                TextureClass::Depth => {
+                    if !expect_generic_arguments(segment, &name, 0, env) {
+                        return Ty::Error;
+                    }
+
+                    TextureKind::Depth
+                },
+                TextureClass::Storage => match resolve_texel_format(segment, &name, env) {
+                    Some(format) => TextureKind::Storage(format),
+                    None => return Ty::Error,
+                },
+            };
+
+            Ty::Texture(TextureTy { dimension, arrayed, kind })
+        },
+        PrimitiveKind::Sampler { comparison } => {
+            if !expect_generic_arguments(segment, &name, 0, env) {
+                return Ty::Error;
+            }
+
+            Ty::Sampler { comparison }
+        },
+        PrimitiveKind::Atomic => match resolve_element(segment, &name, env) {
+            Some(element @ ScalarTy::Int { width: 32, .. }) => Ty::Atomic(element),
+            Some(element) => {
+                invalid_element(&name, element, segment, env);
+                Ty::Error
+            },
+            None => Ty::Error,
+        },
+        PrimitiveKind::TexelFormat(_) => {
+            env.add_error(TypeCheckError::NotAType { name, span: segment.span });
+            Ty::Error
+        },
+    }
+}
+
+/// Resolves the single scalar generic argument of a primitive like `vec3<f32>` or `texture_2d<f32>`
+fn resolve_element(segment: &ItemPathSegment, name: &str, env: &mut Env) -> Option<ScalarTy> {
+    if !expect_generic_arguments(segment, name, 1, env) {
+        return None;
+    }
+
+    let argument = &segment.generic_arguments[0];
+    match resolve_type_path(argument, env) {
+        Ty::Scalar(scalar) => Some(scalar),
+        Ty::Error => None,
+        found => {
+            let error = TypeCheckError::InvalidVectorElement {
+                name: name.to_string(),
+                found: env.display(found),
+                span: argument.span,
+            };
+            env.add_error(error);
+            None
+        },
+    }
+}
+
+fn invalid_element(name: &str, element: ScalarTy, segment: &ItemPathSegment, env: &mut Env) {
+    let error = TypeCheckError::InvalidVectorElement {
+        name: name.to_string(),
+        found: element.display(),
+        span: segment.generic_arguments[0].span,
+    };
+    env.add_error(error);
+}
+
+/// Resolves the texel format of a storage texture like `texture_storage_2d<rgba8unorm>`
+fn resolve_texel_format(segment: &ItemPathSegment, name: &str, env: &mut Env) -> Option<TexelFormat> {
+    if !expect_generic_arguments(segment, name, 1, env) {
+        return None;
+    }
+
+    let argument = segment.generic_arguments[0].last_segment();
+    if let NamedItemKind::Primitive(primitive) = &argument.item {
+        if let PrimitiveKind::TexelFormat(format) = primitive.kind {
+            return Some(format);
+        }
+    }
+
+    let error = TypeCheckError::ExpectedTexelFormat {
+        name: name.to_string(),
+        found: env.name(argument.name),
+        span: argument.span,
+    };
+    env.add_error(error);
+    None
+}
+
+fn expect_generic_arguments(segment: &ItemPathSegment, name: &str, expected: usize, env: &mut Env) -> bool {
+    let found = segment.generic_arguments.len();
+    if found == expected {
+        return true;
+    }
+
+    env.add_error(TypeCheckError::InvalidGenericArguments {
+        name: name.to_string(),
+        expected,
+        found,
+        span: segment.span,
+    });
+
+    false
+}
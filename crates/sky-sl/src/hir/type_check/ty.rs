@@ -0,0 +1,407 @@
+use crate::{fs::FileId, hir::{HirDatabase, primitive::{PrimitiveKind, TexelFormat, TextureClass, TextureDimension}}, intern::Name, syn::cst::LiteralValue};
+use salsa::{InternId, InternKey};
+
+/// A scalar type that can be used on its own or as the element of a vector or matrix
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ScalarTy {
+    Bool,
+    Int { signed: bool, width: u8 },
+    Float { width: u8 },
+}
+
+impl ScalarTy {
+    pub fn is_integer(self) -> bool {
+        matches!(self, ScalarTy::Int { .. })
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, ScalarTy::Float { .. })
+    }
+
+    pub fn is_numeric(self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    pub fn is_signed(self) -> bool {
+        match self {
+            ScalarTy::Int { signed, .. } => signed,
+            ScalarTy::Float { .. } => true,
+            ScalarTy::Bool => false,
+        }
+    }
+
+    /// Checks if a literal value can be represented, `negated` allows the magnitude of the smallest signed integer, e.g. `-128i8`
+    pub fn contains(self, value: LiteralValue, negated: bool) -> bool {
+        match (self, value) {
+            (ScalarTy::Int { signed: false, width }, LiteralValue::Integer(value)) => width >= 64 || value < 1 << width,
+            (ScalarTy::Int { signed: true, width }, LiteralValue::Integer(value)) => {
+                let limit = 1 << (width - 1);
+                value < limit || (negated && value == limit)
+            },
+            (ScalarTy::Float { width: 16 }, LiteralValue::Float(value)) => value.abs() <= 65504.0,
+            (ScalarTy::Float { width: 32 }, LiteralValue::Float(value)) => value.abs() <= f32::MAX as f64,
+            (ScalarTy::Float { .. }, LiteralValue::Float(value)) => value.is_finite(),
+            _ => true,
+        }
+    }
+
+    pub fn display(self) -> String {
+        match self {
+            ScalarTy::Bool => "bool".to_string(),
+            ScalarTy::Int { signed: true, width } => format!("i{}", width),
+            ScalarTy::Int { signed: false, width } => format!("u{}", width),
+            ScalarTy::Float { width } => format!("f{}", width),
+        }
+    }
+}
+
+/// A struct type, identified by the file it is declared in and its name
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct StructTy {
+    pub file: FileId,
+    pub name: Name,
+}
+
+/// The bindings of a `layout`, identified by the file it is declared in and its name.
+/// Passed to entry points, which access the bindings as fields
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LayoutTy {
+    pub file: FileId,
+    pub name: Name,
+}
+
+/// A C-like enum type, identified by the file it is declared in and its name.
+/// Values are represented as `u32` discriminants
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct EnumTy {
+    pub file: FileId,
+    pub name: Name,
+}
+
+/// A built-in class of types a generic parameter can be bound to, e.g. `T: float + vec3`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TypeClass {
+    /// Float scalars and vectors
+    Float,
+
+    /// Integer scalars and vectors
+    Integer,
+
+    /// Vectors with the given number of components
+    Vector(u8),
+}
+
+impl TypeClass {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "float" => Some(TypeClass::Float),
+            "integer" => Some(TypeClass::Integer),
+            "vec2" => Some(TypeClass::Vector(2)),
+            "vec3" => Some(TypeClass::Vector(3)),
+            "vec4" => Some(TypeClass::Vector(4)),
+            _ => None,
+        }
+    }
+
+    /// Checks if a type belongs to the class, a generic parameter does if its bounds include the class
+    pub fn contains(self, ty: Ty) -> bool {
+        match (self, ty) {
+            (_, Ty::Generic(generic)) => generic.satisfies(self),
+            (TypeClass::Float, Ty::Scalar(element)) | (TypeClass::Float, Ty::Vector(element, _)) => element.is_float(),
+            (TypeClass::Integer, Ty::Scalar(element)) | (TypeClass::Integer, Ty::Vector(element, _)) => element.is_integer(),
+            (TypeClass::Vector(components), Ty::Vector(_, found)) => components == found,
+            _ => false,
+        }
+    }
+
+    pub fn display(self) -> String {
+        match self {
+            TypeClass::Float => "float".to_string(),
+            TypeClass::Integer => "integer".to_string(),
+            TypeClass::Vector(components) => format!("vec{}", components),
+        }
+    }
+}
+
+/// A generic parameter while type checking the body of a generic function, e.g. `T` in `fn saturate<T: float>(x: T) -> T`.
+/// Only the operations its bounds guarantee can be applied to it, the function is checked again for every instantiation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct GenericTy {
+    pub name: Name,
+
+    /// `float` or `integer` if the parameter is bound to either
+    pub element: Option<TypeClass>,
+
+    /// The number of components if the parameter is bound to a vector class like `vec3`
+    pub components: Option<u8>,
+
+    /// Set for the unknown scalar element type of the parameter, e.g. the type of `0.0` in `max(x, 0.0)`
+    pub is_element: bool,
+}
+
+impl GenericTy {
+    pub fn satisfies(self, class: TypeClass) -> bool {
+        match class {
+            TypeClass::Float | TypeClass::Integer => self.element == Some(class),
+            TypeClass::Vector(components) => !self.is_element && self.components == Some(components),
+        }
+    }
+
+    /// The scalar element type of the parameter
+    pub fn element_ty(self) -> GenericTy {
+        GenericTy {
+            components: None,
+            is_element: true,
+            ..self
+        }
+    }
+}
+
+/// An interned array type, see `ArrayTyData`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ArrayTy(InternId);
+
+impl InternKey for ArrayTy {
+    fn from_intern_id(v: InternId) -> Self {
+        Self(v)
+    }
+
+    fn as_intern_id(&self) -> InternId {
+        self.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ArrayTyData {
+    pub element: Ty,
+
+    /// The number of elements, `None` for runtime-sized arrays
+    pub length: Option<u32>,
+}
+
+/// What a texture holds, see `TextureClass`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TextureKind {
+    Sampled(ScalarTy),
+    Depth,
+    Storage(TexelFormat),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TextureTy {
+    pub dimension: TextureDimension,
+    pub arrayed: bool,
+    pub kind: TextureKind,
+}
+
+impl TextureTy {
+    pub fn class(self) -> TextureClass {
+        match self.kind {
+            TextureKind::Sampled(_) => TextureClass::Sampled,
+            TextureKind::Depth => TextureClass::Depth,
+            TextureKind::Storage(_) => TextureClass::Storage,
+        }
+    }
+
+    /// The type of coordinates addressing a texel, `f32` or `i32` based, e.g. `vec2<f32>` for 2D textures.
+    /// Array layers are passed separately.
+    pub fn coordinate_ty(self, element: ScalarTy) -> Ty {
+        match self.dimension {
+            TextureDimension::D1 => Ty::Scalar(element),
+            TextureDimension::D2 => Ty::Vector(element, 2),
+            TextureDimension::D3 | TextureDimension::Cube => Ty::Vector(element, 3),
+        }
+    }
+
+    /// The type of a single texel, e.g. `vec4<f32>` or `f32` for depth textures
+    pub fn texel_ty(self) -> Ty {
+        match self.kind {
+            TextureKind::Sampled(element) => Ty::Vector(element, 4),
+            TextureKind::Depth => Ty::Scalar(ScalarTy::Float { width: 32 }),
+            TextureKind::Storage(format) => Ty::Vector(format.channel(), 4),
+        }
+    }
+
+    /// The type returned by `dimensions`, e.g. `vec2<u32>` for 2D and cube textures
+    pub fn dimensions_ty(self) -> Ty {
+        let element = ScalarTy::Int { signed: false, width: 32 };
+        match self.dimension {
+            TextureDimension::D1 => Ty::Scalar(element),
+            TextureDimension::D2 | TextureDimension::Cube => Ty::Vector(element, 2),
+            TextureDimension::D3 => Ty::Vector(element, 3),
+        }
+    }
+
+    pub fn display(self) -> String {
+        let suffix = if self.arrayed { "_array" } else { "" };
+        let name = format!("texture_{}{}{}", self.class().prefix(), self.dimension.name(), suffix);
+
+        match self.kind {
+            TextureKind::Sampled(element) => format!("{}<{}>", name, element.display()),
+            TextureKind::Depth => name,
+            TextureKind::Storage(format) => format!("{}<{}>", name, format.name()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Ty {
+    /// The unit type `()`, returned by functions without a return type
+    Unit,
+
+    Scalar(ScalarTy),
+
+    /// A vector of 2 to 4 scalar components, e.g. `vec3<f32>`
+    Vector(ScalarTy, u8),
+
+    /// A column-major matrix given as element type, columns and rows, e.g. `mat4x3<f32>`
+    Matrix(ScalarTy, u8, u8),
+
+    Struct(StructTy),
+
+    Enum(EnumTy),
+
+    /// A fixed-size array e.g. `[f32; 4]` or a runtime-sized array e.g. `[f32]`
+    Array(ArrayTy),
+
+    /// A texture, only usable as an `image` binding or function argument
+    Texture(TextureTy),
+
+    /// A sampler, only usable as a `sampler` binding or function argument
+    Sampler { comparison: bool },
+
+    /// A layout, only usable as an entry point argument
+    Layout(LayoutTy),
+
+    /// A `u32` or `i32` only accessed through atomic functions, e.g. `atomic<u32>`.
+    /// Only usable in storage and workgroup memory
+    Atomic(ScalarTy),
+
+    /// A generic parameter, only seen while checking the body of a generic function
+    Generic(GenericTy),
+
+    /// The type of an expression that failed to type check, compatible with every other type
+    /// so a single mistake does not cause a cascade of errors
+    Error,
+}
+
+impl Ty {
+    pub fn is_error(self) -> bool {
+        self == Ty::Error
+    }
+
+    /// Textures, samplers and layouts are opaque handles that cannot be stored in structs or arrays
+    pub fn is_opaque(self) -> bool {
+        matches!(self, Ty::Texture(_) | Ty::Sampler { .. } | Ty::Layout(_))
+    }
+
+    /// The scalar type of a scalar, vector or matrix
+    pub fn element(self) -> Option<ScalarTy> {
+        match self {
+            Ty::Scalar(scalar) | Ty::Vector(scalar, _) | Ty::Matrix(scalar, _, _) => Some(scalar),
+            _ => None,
+        }
+    }
+
+    /// The scalar element type like `element`, for a generic parameter its element type, e.g. the type expected of `0.0` in `max(x, 0.0)`
+    pub fn element_ty(self) -> Option<Ty> {
+        match self {
+            Ty::Generic(generic) => Some(Ty::Generic(generic.element_ty())),
+            ty => ty.element().map(Ty::Scalar),
+        }
+    }
+
+    /// Returns the same shape with a different element type, e.g. `vec3<bool>` for `vec3<f32>`
+    pub fn with_element(self, element: ScalarTy) -> Ty {
+        match self {
+            Ty::Scalar(_) => Ty::Scalar(element),
+            Ty::Vector(_, components) => Ty::Vector(element, components),
+            Ty::Matrix(_, columns, rows) => Ty::Matrix(element, columns, rows),
+            ty => ty,
+        }
+    }
+
+    /// The number of scalar components, e.g. 3 for `vec3<f32>` and 16 for `mat4x4<f32>`
+    pub fn component_count(self) -> Option<u8> {
+        match self {
+            Ty::Scalar(_) => Some(1),
+            Ty::Vector(_, components) => Some(components),
+            Ty::Matrix(_, columns, rows) => Some(columns * rows),
+            _ => None,
+        }
+    }
+
+    pub fn display(self, db: &dyn HirDatabase) -> String {
+        match self {
+            Ty::Unit => "()".to_string(),
+            Ty::Scalar(scalar) => scalar.display(),
+            Ty::Vector(scalar, components) => format!("vec{}<{}>", components, scalar.display()),
+            Ty::Matrix(scalar, columns, rows) => format!("mat{}x{}<{}>", columns, rows, scalar.display()),
+            Ty::Struct(struct_ty) => db.lookup_intern_name(struct_ty.name),
+            Ty::Enum(enum_ty) => db.lookup_intern_name(enum_ty.name),
+            Ty::Array(array_ty) => {
+                let array = db.lookup_intern_array_ty(array_ty);
+                match array.length {
+                    Some(length) => format!("[{}; {}]", array.element.display(db), length),
+                    None => format!("[{}]", array.element.display(db)),
+                }
+            },
+            Ty::Texture(texture_ty) => texture_ty.display(),
+            Ty::Sampler { comparison: false } => "sampler_state".to_string(),
+            Ty::Sampler { comparison: true } => "sampler_comparison_state".to_string(),
+            Ty::Layout(layout_ty) => db.lookup_intern_name(layout_ty.name),
+            Ty::Atomic(scalar) => format!("atomic<{}>", scalar.display()),
+            Ty::Generic(generic) if generic.is_element => format!("{}::Element", db.lookup_intern_name(generic.name)),
+            Ty::Generic(generic) => db.lookup_intern_name(generic.name),
+            Ty::Error => "{unknown}".to_string(),
+        }
+    }
+}
+
+impl PrimitiveKind {
+    /// The scalar type of a scalar primitive, `None` for all other primitives
+    pub fn scalar(self) -> Option<ScalarTy> {
+        match self {
+            PrimitiveKind::Boolean => Some(ScalarTy::Bool),
+            PrimitiveKind::Integer { signed, width } => Some(ScalarTy::Int { signed, width }),
+            PrimitiveKind::FloatingPoint { width } => Some(ScalarTy::Float { width }),
+            _ => None,
+        }
+    }
+
+    /// Scalars, vectors and matrices can be constructed by calling them, e.g. `vec2(0.0, 1.0)`
+    pub fn is_constructible(self) -> bool {
+        matches!(
+            self,
+            PrimitiveKind::Boolean
+                | PrimitiveKind::Integer { .. }
+                | PrimitiveKind::FloatingPoint { .. }
+                | PrimitiveKind::Vector { .. }
+                | PrimitiveKind::Matrix { .. }
+        )
+    }
+}
+
+impl TexelFormat {
+    /// The scalar type of each channel when reading or writing the texel in a shader
+    pub fn channel(self) -> ScalarTy {
+        match self {
+            TexelFormat::Rgba8Unorm
+            | TexelFormat::Rgba8Snorm
+            | TexelFormat::Rgba16Float
+            | TexelFormat::R32Float
+            | TexelFormat::Rg32Float
+            | TexelFormat::Rgba32Float => ScalarTy::Float { width: 32 },
+            TexelFormat::Rgba8Uint
+            | TexelFormat::Rgba16Uint
+            | TexelFormat::R32Uint
+            | TexelFormat::Rg32Uint
+            | TexelFormat::Rgba32Uint => ScalarTy::Int { signed: false, width: 32 },
+            TexelFormat::Rgba8Sint
+            | TexelFormat::Rgba16Sint
+            | TexelFormat::R32Sint
+            | TexelFormat::Rg32Sint
+            | TexelFormat::Rgba32Sint => ScalarTy::Int { signed: true, width: 32 },
+        }
+    }
+}
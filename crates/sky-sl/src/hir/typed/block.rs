@@ -5,17 +5,29 @@ use rowan::{TextRange, TextSize};
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Block {
     pub statements: Vec<StatementKind>,
+
+    /// The type of the tail expression, or `()` if the block does not end in one
+    pub ty: Ty,
     pub span: TextRange,
 }
 
 impl Block {
-    pub fn new(statements: Vec<StatementKind>, span: TextRange) -> Self {
+    pub fn new(statements: Vec<StatementKind>, ty: Ty, span: TextRange) -> Self {
         Self {
             statements,
+            ty,
             span,
         }
     }
 
+    /// The span of the tail expression the block evaluates to, if any
+    pub fn tail_span(&self) -> Option<TextRange> {
+        match self.statements.last() {
+            Some(StatementKind::Expression(statement)) if !statement.has_semicolon => Some(statement.expression.span()),
+            _ => None,
+        }
+    }
+
     pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
         for statement in &self.statements {
             if statement.span().contains(offset) {
@@ -0,0 +1,40 @@
+use super::ExpressionKind;
+use crate::{hir::{const_eval::ConstValue, type_check::Ty}, intern::Name};
+use rowan::{TextRange, TextSize};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ConstKind {
+    pub name: Name,
+    pub ty: Ty,
+    pub expression: ExpressionKind,
+
+    /// The evaluated value, `None` if the constant could not be evaluated. For overrides this is the default value.
+    pub value: Option<ConstValue>,
+
+    /// The id of a pipeline overridable constant, `None` for plain constants or if the id is invalid
+    pub override_id: Option<u32>,
+    pub is_override: bool,
+    pub span: TextRange,
+}
+
+impl ConstKind {
+    pub fn new(name: Name, ty: Ty, expression: ExpressionKind, value: Option<ConstValue>, override_id: Option<u32>, is_override: bool, span: TextRange) -> Self {
+        Self {
+            name,
+            ty,
+            expression,
+            value,
+            override_id,
+            is_override,
+            span,
+        }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        if self.expression.span().contains(offset) {
+            return self.expression.find_ty(offset);
+        }
+
+        Some(self.ty)
+    }
+}
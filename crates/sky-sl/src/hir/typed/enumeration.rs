@@ -0,0 +1,44 @@
+use crate::{hir::type_check::Ty, intern::Name};
+use rowan::{TextRange, TextSize};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct EnumKind {
+    pub name: Name,
+    pub variants: Vec<EnumVariant>,
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl EnumKind {
+    pub fn new(name: Name, variants: Vec<EnumVariant>, ty: Ty, span: TextRange) -> Self {
+        Self {
+            name,
+            variants,
+            ty,
+            span,
+        }
+    }
+
+    pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
+        Some(self.ty)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct EnumVariant {
+    pub name: Name,
+
+    /// The discriminant, `None` if it could not be evaluated
+    pub value: Option<u32>,
+    pub span: TextRange,
+}
+
+impl EnumVariant {
+    pub fn new(name: Name, value: Option<u32>, span: TextRange) -> Self {
+        Self {
+            name,
+            value,
+            span,
+        }
+    }
+}
@@ -1,16 +1,21 @@
-use crate::hir::type_check::Ty;
+use super::Block;
+use crate::{hir::{intrinsic::Intrinsic, symbol::Symbol, type_check::{StructTy, Ty}}, intern::Name, syn::cst::{BinaryOperator, LiteralValue, UnaryOperator}};
+use std::sync::Arc;
 use rowan::{TextRange, TextSize};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum ExpressionKind {
     LiteralExpression(LiteralExpression),
     BinaryExpression(BinaryExpression),
+    UnaryExpression(UnaryExpression),
     GroupExpression(GroupExpression),
     CallExpression(CallExpression),
     FieldAccessExpression(FieldAccessExpression),
     IndexExpression(IndexExpression),
     PathExpression(PathExpression),
     StructExpression(StructExpression),
+    BlockExpression(BlockExpression),
+    MatchExpression(MatchExpression),
 }
 
 impl ExpressionKind {
@@ -18,12 +23,15 @@ impl ExpressionKind {
         match self {
             ExpressionKind::LiteralExpression(e) => e.span,
             ExpressionKind::BinaryExpression(e) => e.span,
+            ExpressionKind::UnaryExpression(e) => e.span,
             ExpressionKind::GroupExpression(e) => e.span,
             ExpressionKind::CallExpression(e) => e.span,
             ExpressionKind::FieldAccessExpression(e) => e.span,
             ExpressionKind::IndexExpression(e) => e.span,
             ExpressionKind::PathExpression(e) => e.span,
             ExpressionKind::StructExpression(e) => e.span,
+            ExpressionKind::BlockExpression(e) => e.span,
+            ExpressionKind::MatchExpression(e) => e.span,
         }
     }
 
@@ -31,12 +39,15 @@ impl ExpressionKind {
         match self {
             ExpressionKind::LiteralExpression(e) => e.ty,
             ExpressionKind::BinaryExpression(e) => e.ty,
+            ExpressionKind::UnaryExpression(e) => e.ty,
             ExpressionKind::GroupExpression(e) => e.ty,
             ExpressionKind::CallExpression(e) => e.ty,
             ExpressionKind::FieldAccessExpression(e) => e.ty,
             ExpressionKind::IndexExpression(e) => e.ty,
             ExpressionKind::PathExpression(e) => e.ty,
             ExpressionKind::StructExpression(e) => e.ty,
+            ExpressionKind::BlockExpression(e) => e.ty,
+            ExpressionKind::MatchExpression(e) => e.ty,
         }
     }
 
@@ -44,25 +55,29 @@ impl ExpressionKind {
         match self {
             ExpressionKind::LiteralExpression(e) => e.find_ty(offset),
             ExpressionKind::BinaryExpression(e) => e.find_ty(offset),
+            ExpressionKind::UnaryExpression(e) => e.find_ty(offset),
             ExpressionKind::GroupExpression(e) => e.find_ty(offset),
             ExpressionKind::CallExpression(e) => e.find_ty(offset),
             ExpressionKind::FieldAccessExpression(e) => e.find_ty(offset),
             ExpressionKind::IndexExpression(e) => e.find_ty(offset),
             ExpressionKind::PathExpression(e) => e.find_ty(offset),
             ExpressionKind::StructExpression(e) => e.find_ty(offset),
+            ExpressionKind::BlockExpression(e) => e.find_ty(offset),
+            ExpressionKind::MatchExpression(e) => e.find_ty(offset),
         }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct LiteralExpression {
+    pub value: LiteralValue,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl LiteralExpression {
-    pub fn new(ty: Ty, span: TextRange) -> Self {
-        Self { ty, span }
+    pub fn new(value: LiteralValue, ty: Ty, span: TextRange) -> Self {
+        Self { value, ty, span }
     }
 
     pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
@@ -95,6 +110,7 @@ impl GroupExpression {
 pub struct BinaryExpression {
     pub lhs: Box<ExpressionKind>,
     pub rhs: Box<ExpressionKind>,
+    pub operator: BinaryOperator,
     pub ty: Ty,
     pub span: TextRange,
 }
@@ -103,10 +119,11 @@ impl BinaryExpression {
     pub fn new(
         lhs: Box<ExpressionKind>,
         rhs: Box<ExpressionKind>,
+        operator: BinaryOperator,
         ty: Ty,
         span: TextRange,
     ) -> Self {
-        Self { lhs, rhs, ty, span }
+        Self { lhs, rhs, operator, ty, span }
     }
 
     pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
@@ -122,16 +139,63 @@ impl BinaryExpression {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct UnaryExpression {
+    pub operand: Box<ExpressionKind>,
+    pub operator: UnaryOperator,
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl UnaryExpression {
+    pub fn new(operand: Box<ExpressionKind>, operator: UnaryOperator, ty: Ty, span: TextRange) -> Self {
+        Self { operand, operator, ty, span }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        if self.operand.span().contains(offset) {
+            return self.operand.find_ty(offset);
+        }
+
+        Some(self.ty)
+    }
+}
+
+/// What a call expression calls
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Callee {
+    /// A built-in type constructor or conversion, e.g. `vec3(1.0, 2.0, 3.0)` or `f32(1)`
+    Constructor(Ty),
+
+    /// A built-in function, e.g. `clamp(x, 0.0, 1.0)` or `sample(albedo, linear, uv)`
+    Intrinsic(Arc<Intrinsic>),
+
+    /// A function declared in this or another module
+    Function(Arc<Symbol>),
+
+    /// A function of an impl block, e.g. `Light::new()`, a method call like `light.luminance()` passes the receiver as first argument
+    Method(StructTy, Name),
+
+    /// A callee that could not be resolved
+    Unresolved,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct CallExpression {
+    pub callee: Callee,
+
+    /// The generic arguments a generic function is instantiated with, see `HirDatabase::get_function_instance`
+    pub generic_arguments: Vec<Ty>,
     pub arguments: Vec<ExpressionKind>,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl CallExpression {
-    pub fn new(arguments: Vec<ExpressionKind>, ty: Ty, span: TextRange) -> Self {
+    pub fn new(callee: Callee, generic_arguments: Vec<Ty>, arguments: Vec<ExpressionKind>, ty: Ty, span: TextRange) -> Self {
         Self {
+            callee,
+            generic_arguments,
             arguments,
             ty,
             span,
@@ -151,46 +215,65 @@ impl CallExpression {
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FieldAccessExpression {
+    pub expression: Box<ExpressionKind>,
+    pub field: Name,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl FieldAccessExpression {
-    pub fn new(ty: Ty, span: TextRange) -> Self {
-        Self { ty, span }
+    pub fn new(expression: Box<ExpressionKind>, field: Name, ty: Ty, span: TextRange) -> Self {
+        Self { expression, field, ty, span }
     }
 
-    pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        if self.expression.span().contains(offset) {
+            return self.expression.find_ty(offset);
+        }
+
         Some(self.ty)
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct IndexExpression {
+    pub expression: Box<ExpressionKind>,
+    pub index: Box<ExpressionKind>,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl IndexExpression {
-    pub fn new(ty: Ty, span: TextRange) -> Self {
-        Self { ty, span }
+    pub fn new(expression: Box<ExpressionKind>, index: Box<ExpressionKind>, ty: Ty, span: TextRange) -> Self {
+        Self { expression, index, ty, span }
     }
 
-    pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
-        todo!()
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        if self.expression.span().contains(offset) {
+            return self.expression.find_ty(offset);
+        }
+
+        if self.index.span().contains(offset) {
+            return self.index.find_ty(offset);
+        }
+
+        Some(self.ty)
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct PathExpression {
-    pub path: String,
+    pub path: Name,
+
+    /// The discriminant if the path names an enum variant
+    pub variant: Option<u32>,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl PathExpression {
-    pub fn new(path: String, ty: Ty, span: TextRange) -> Self {
-        Self { path, ty, span }
+    pub fn new(path: Name, variant: Option<u32>, ty: Ty, span: TextRange) -> Self {
+        Self { path, variant, ty, span }
     }
 
     pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
@@ -210,6 +293,91 @@ impl StructExpression {
     }
 
     pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
-        todo!()
+        Some(self.ty)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BlockExpression {
+    pub block: Block,
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl BlockExpression {
+    pub fn new(block: Block, ty: Ty, span: TextRange) -> Self {
+        Self { block, ty, span }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        self.block.find_ty(offset).or(Some(self.ty))
+    }
+}
+
+/// A match over an enum or integer, backends lower it into a switch on the scrutinee's value
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MatchExpression {
+    pub scrutinee: Box<ExpressionKind>,
+    pub arms: Vec<MatchArm>,
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl MatchExpression {
+    pub fn new(scrutinee: Box<ExpressionKind>, arms: Vec<MatchArm>, ty: Ty, span: TextRange) -> Self {
+        Self { scrutinee, arms, ty, span }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        if self.scrutinee.span().contains(offset) {
+            return self.scrutinee.find_ty(offset);
+        }
+
+        for arm in &self.arms {
+            if arm.expression.span().contains(offset) {
+                return arm.expression.find_ty(offset);
+            }
+
+            if arm.patterns.iter().any(|pattern| pattern.span().contains(offset)) {
+                return Some(self.scrutinee.ty());
+            }
+        }
+
+        Some(self.ty)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub expression: ExpressionKind,
+    pub span: TextRange,
+}
+
+impl MatchArm {
+    pub fn new(patterns: Vec<Pattern>, expression: ExpressionKind, span: TextRange) -> Self {
+        Self { patterns, expression, span }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Pattern {
+    /// A single value, the discriminant of an enum variant or an integer. Booleans match `0` and `1`
+    Value { value: i128, span: TextRange },
+
+    /// The wildcard `_`, the default case of a switch
+    Wildcard { span: TextRange },
+
+    /// A pattern that failed to type check
+    Error { span: TextRange },
+}
+
+impl Pattern {
+    pub fn span(&self) -> TextRange {
+        match self {
+            Pattern::Value { span, .. } => *span,
+            Pattern::Wildcard { span } => *span,
+            Pattern::Error { span } => *span,
+        }
     }
 }
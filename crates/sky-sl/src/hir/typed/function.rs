@@ -1,21 +1,21 @@
-use crate::hir::type_check::Ty;
+use crate::{hir::{type_check::{Ty, TypeCheckError}, untyped::ShaderStage}, intern::Name};
 use super::Block;
 use rowan::{TextRange, TextSize};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FunctionKind {
     pub signature: FunctionSignature,
+    pub stage: Option<ShaderStage>,
     pub block: Block,
-    pub ty: Ty,
     pub span: TextRange,
 }
 
 impl FunctionKind {
-    pub fn new(signature: FunctionSignature, block: Block, ty: Ty, span: TextRange) -> Self {
+    pub fn new(signature: FunctionSignature, stage: Option<ShaderStage>, block: Block, span: TextRange) -> Self {
         Self {
             signature,
+            stage,
             block,
-            ty,
             span,
         }
     }
@@ -28,7 +28,7 @@ impl FunctionKind {
                 }
             }
 
-            return Some(self.ty);
+            return Some(self.signature.return_type);
         }
 
         if self.block.span.contains(offset) {
@@ -41,16 +41,20 @@ impl FunctionKind {
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FunctionSignature {
-    pub name: String,
+    pub name: Name,
+
+    /// The generic parameters as `Ty::Generic`, or the generic arguments of an instantiation
+    pub generic_parameters: Vec<Ty>,
     pub arguments: Vec<FunctionArgument>,
     pub return_type: Ty,
     pub span: TextRange,
 }
 
 impl FunctionSignature {
-    pub fn new(name: String, arguments: Vec<FunctionArgument>, return_type: Ty, span: TextRange) -> Self {
+    pub fn new(name: Name, generic_parameters: Vec<Ty>, arguments: Vec<FunctionArgument>, return_type: Ty, span: TextRange) -> Self {
         Self {
             name,
+            generic_parameters,
             arguments,
             return_type,
             span,
@@ -60,13 +64,13 @@ impl FunctionSignature {
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FunctionArgument {
-    pub name: String,
+    pub name: Name,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl FunctionArgument {
-    pub fn new(name: String, ty: Ty, span: TextRange) -> Self {
+    pub fn new(name: Name, ty: Ty, span: TextRange) -> Self {
         Self {
             name,
             ty,
@@ -74,3 +78,18 @@ impl FunctionArgument {
         }
     }
 }
+
+/// A generic function checked with concrete generic arguments, code is generated for every instantiation
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct FunctionInstance {
+    pub function: FunctionKind,
+
+    /// Errors only this instantiation has, errors of the generic function are reported by its module
+    pub errors: Vec<TypeCheckError>,
+}
+
+impl FunctionInstance {
+    pub fn new(function: FunctionKind, errors: Vec<TypeCheckError>) -> Self {
+        Self { function, errors }
+    }
+}
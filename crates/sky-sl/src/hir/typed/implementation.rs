@@ -0,0 +1,32 @@
+use super::FunctionKind;
+use crate::{hir::type_check::Ty, intern::Name};
+use rowan::{TextRange, TextSize};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ImplKind {
+    pub name: Name,
+    pub functions: Vec<FunctionKind>,
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl ImplKind {
+    pub fn new(name: Name, functions: Vec<FunctionKind>, ty: Ty, span: TextRange) -> Self {
+        Self {
+            name,
+            functions,
+            ty,
+            span,
+        }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        for function in &self.functions {
+            if function.span.contains(offset) {
+                return function.find_ty(offset);
+            }
+        }
+
+        Some(self.ty)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{hir::{type_check::Ty, untyped::{AccessMode, BindingKind}}, intern::Name};
+use rowan::{TextRange, TextSize};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LayoutKind {
+    pub name: Name,
+
+    /// The explicit set index, entry points assign one to layouts without it
+    pub set_index: Option<u32>,
+    pub members: Vec<LayoutMember>,
+    pub span: TextRange,
+}
+
+impl LayoutKind {
+    pub fn new(name: Name, set_index: Option<u32>, members: Vec<LayoutMember>, span: TextRange) -> Self {
+        Self {
+            name,
+            set_index,
+            members,
+            span,
+        }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        for member in &self.members {
+            if member.span.contains(offset) {
+                return Some(member.ty);
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LayoutMember {
+    pub name: Name,
+
+    /// The explicit or automatically assigned binding index, `None` if the explicit index is invalid
+    pub binding_index: Option<u32>,
+
+    /// Whether the binding index was assigned automatically because the member has no `binding(...)`
+    pub automatic_binding: bool,
+    pub binding_kind: BindingKind,
+
+    /// The access mode of a storage binding, read and write unless declared `storage(read)`
+    pub access: Option<AccessMode>,
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl LayoutMember {
+    pub fn new(name: Name, binding_index: Option<u32>, automatic_binding: bool, binding_kind: BindingKind, access: Option<AccessMode>, ty: Ty, span: TextRange) -> Self {
+        Self {
+            name,
+            binding_index,
+            automatic_binding,
+            binding_kind,
+            access,
+            ty,
+            span,
+        }
+    }
+}
@@ -1,13 +1,23 @@
 mod block;
+mod constant;
+mod enumeration;
 mod expression;
 mod function;
+mod implementation;
+mod layout;
 mod module;
 mod statement;
 mod structure;
+mod type_alias;
 
 pub use block::*;
+pub use constant::*;
+pub use enumeration::*;
 pub use expression::*;
 pub use function::*;
+pub use implementation::*;
+pub use layout::*;
 pub use module::*;
 pub use statement::*;
 pub use structure::*;
+pub use type_alias::*;
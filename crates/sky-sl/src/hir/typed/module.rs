@@ -1,19 +1,21 @@
-use super::FunctionKind;
-use crate::hir::type_check::{TypeCheckError, Ty};
+use super::{ConstKind, EnumKind, FunctionKind, ImplKind, LayoutKind, StructKind, TypeAliasKind};
+use crate::hir::type_check::{TypeCheckError, TypeCheckWarning, Ty};
 use rowan::{TextRange, TextSize};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Module {
     pub items: Vec<ItemKind>,
     pub errors: Vec<TypeCheckError>,
+    pub warnings: Vec<TypeCheckWarning>,
     pub span: TextRange,
 }
 
 impl Module {
-    pub fn new(items: Vec<ItemKind>, errors: Vec<TypeCheckError>, span: TextRange) -> Self {
+    pub fn new(items: Vec<ItemKind>, errors: Vec<TypeCheckError>, warnings: Vec<TypeCheckWarning>, span: TextRange) -> Self {
         Self {
             items,
             errors,
+            warnings,
             span,
         }
     }
@@ -36,18 +38,36 @@ impl Module {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum ItemKind {
     Function(FunctionKind),
+    Struct(StructKind),
+    Layout(LayoutKind),
+    Const(ConstKind),
+    Enum(EnumKind),
+    Impl(ImplKind),
+    TypeAlias(TypeAliasKind),
 }
 
 impl ItemKind {
     pub fn span(&self) -> TextRange {
         match self {
             ItemKind::Function(function) => function.span,
+            ItemKind::Struct(structure) => structure.span,
+            ItemKind::Layout(layout) => layout.span,
+            ItemKind::Const(constant) => constant.span,
+            ItemKind::Enum(enumeration) => enumeration.span,
+            ItemKind::Impl(implementation) => implementation.span,
+            ItemKind::TypeAlias(type_alias) => type_alias.span,
         }
     }
 
     pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
         match self {
-            ItemKind::Function(function) => function.find_ty(offset)
+            ItemKind::Function(function) => function.find_ty(offset),
+            ItemKind::Struct(structure) => structure.find_ty(offset),
+            ItemKind::Layout(layout) => layout.find_ty(offset),
+            ItemKind::Const(constant) => constant.find_ty(offset),
+            ItemKind::Enum(enumeration) => enumeration.find_ty(offset),
+            ItemKind::Impl(implementation) => implementation.find_ty(offset),
+            ItemKind::TypeAlias(type_alias) => type_alias.find_ty(offset),
         }
     }
 }
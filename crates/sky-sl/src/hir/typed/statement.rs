@@ -1,11 +1,12 @@
 use super::ExpressionKind;
-use crate::hir::type_check::Ty;
+use crate::{hir::type_check::Ty, intern::Name};
 use rowan::{TextRange, TextSize};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum StatementKind {
     Let(LetStatement),
     Expression(ExpressionStatement),
+    Assign(AssignStatement),
 }
 
 impl StatementKind {
@@ -13,6 +14,7 @@ impl StatementKind {
         match self {
             StatementKind::Let(s) => s.span,
             StatementKind::Expression(s) => s.span,
+            StatementKind::Assign(s) => s.span,
         }
     }
 
@@ -20,20 +22,21 @@ impl StatementKind {
         match self {
             StatementKind::Let(s) => s.find_ty(offset),
             StatementKind::Expression(s) => s.find_ty(offset),
+            StatementKind::Assign(s) => s.find_ty(offset),
         }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct LetStatement {
-    pub name: String,
+    pub name: Name,
     pub expression: ExpressionKind,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl LetStatement {
-    pub fn new(name: String, expression: ExpressionKind, ty: Ty, span: TextRange) -> Self {
+    pub fn new(name: Name, expression: ExpressionKind, ty: Ty, span: TextRange) -> Self {
         Self {
             name,
             expression,
@@ -54,13 +57,14 @@ impl LetStatement {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ExpressionStatement {
     pub expression: ExpressionKind,
+    pub has_semicolon: bool,
     pub ty: Ty,
     pub span: TextRange,
 }
 
 impl ExpressionStatement {
-    pub fn new(expression: ExpressionKind, ty: Ty, span: TextRange) -> Self {
-        Self { expression, ty, span }
+    pub fn new(expression: ExpressionKind, has_semicolon: bool, ty: Ty, span: TextRange) -> Self {
+        Self { expression, has_semicolon, ty, span }
     }
 
     pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
@@ -71,3 +75,28 @@ impl ExpressionStatement {
         None
     }
 }
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AssignStatement {
+    pub place: ExpressionKind,
+    pub value: ExpressionKind,
+    pub span: TextRange,
+}
+
+impl AssignStatement {
+    pub fn new(place: ExpressionKind, value: ExpressionKind, span: TextRange) -> Self {
+        Self { place, value, span }
+    }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        if self.place.span().contains(offset) {
+            return self.place.find_ty(offset);
+        }
+
+        if self.value.span().contains(offset) {
+            return self.value.find_ty(offset);
+        }
+
+        None
+    }
+}
@@ -1,34 +1,53 @@
-use rowan::TextRange;
+use crate::{hir::{type_check::Ty, untyped::Interpolation}, intern::Name};
+use rowan::{TextRange, TextSize};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct StructKind {
-    pub name: String,
+    pub name: Name,
     pub members: Vec<StructMember>,
+    pub ty: Ty,
     pub span: TextRange,
 }
 
 impl StructKind {
-    pub fn new(name: String, members: Vec<StructMember>, span: TextRange) -> Self {
+    pub fn new(name: Name, members: Vec<StructMember>, ty: Ty, span: TextRange) -> Self {
         Self {
             name,
             members,
+            ty,
             span,
         }
     }
+
+    pub fn find_ty(&self, offset: TextSize) -> Option<Ty> {
+        for member in &self.members {
+            if member.span.contains(offset) {
+                return Some(member.ty);
+            }
+        }
+
+        Some(self.ty)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct StructMember {
-    pub name: String,
-    pub ty_name: String,
+    pub name: Name,
+    pub ty: Ty,
+
+    /// The explicit location, `None` if the member has no `location(...)` or it is invalid
+    pub location: Option<u32>,
+    pub interpolation: Option<Interpolation>,
     pub span: TextRange,
 }
 
 impl StructMember {
-    pub fn new(name: String, ty_name: String, span: TextRange) -> Self {
+    pub fn new(name: Name, ty: Ty, location: Option<u32>, interpolation: Option<Interpolation>, span: TextRange) -> Self {
         Self {
             name,
-            ty_name,
+            ty,
+            location,
+            interpolation,
             span,
         }
     }
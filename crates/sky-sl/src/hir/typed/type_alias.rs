@@ -0,0 +1,21 @@
+use crate::{hir::type_check::Ty, intern::Name};
+use rowan::{TextRange, TextSize};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TypeAliasKind {
+    pub name: Name,
+
+    /// The expanded type, aliases in the aliased type are resolved as well
+    pub ty: Ty,
+    pub span: TextRange,
+}
+
+impl TypeAliasKind {
+    pub fn new(name: Name, ty: Ty, span: TextRange) -> Self {
+        Self { name, ty, span }
+    }
+
+    pub fn find_ty(&self, _offset: TextSize) -> Option<Ty> {
+        Some(self.ty)
+    }
+}
@@ -1,4 +1,5 @@
 use super::StatementKind;
+use crate::{hir::named::NamedItemKind, text::Locate};
 use rowan::TextRange;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -15,3 +16,15 @@ impl Block {
         }
     }
 }
+
+impl Locate for Block {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        self.statements.iter().find_map(|statement| statement.locate(offset))
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
+use rowan::TextRange;
+
+use super::{ExpressionKind, TypeReference};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ConstKind {
+    pub name: Name,
+    pub item: NamedItemKind,
+    pub type_reference: TypeReference,
+    pub expression: ExpressionKind,
+
+    /// The id of a pipeline overridable constant, `expression` is its default value
+    pub override_id: Option<ExpressionKind>,
+    pub span: TextRange,
+}
+
+impl ConstKind {
+    pub fn new(name: Name, item: NamedItemKind, type_reference: TypeReference, expression: ExpressionKind, override_id: Option<ExpressionKind>, span: TextRange) -> Self {
+        Self {
+            name,
+            item,
+            type_reference,
+            expression,
+            override_id,
+            span,
+        }
+    }
+}
+
+impl Locate for ConstKind {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        self.type_reference.locate(offset)
+            .or_else(|| self.expression.locate(offset))
+            .or_else(|| self.override_id.as_ref().and_then(|override_id| override_id.locate(offset)))
+            .or_else(|| Some(self.item.clone()))
+    }
+}
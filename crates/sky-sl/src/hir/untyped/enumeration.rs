@@ -0,0 +1,57 @@
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
+use rowan::TextRange;
+
+use super::ExpressionKind;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct EnumKind {
+    pub name: Name,
+    pub item: NamedItemKind,
+    pub variants: Vec<EnumVariant>,
+    pub span: TextRange,
+}
+
+impl EnumKind {
+    pub fn new(name: Name, item: NamedItemKind, variants: Vec<EnumVariant>, span: TextRange) -> Self {
+        Self {
+            name,
+            item,
+            variants,
+            span,
+        }
+    }
+}
+
+impl Locate for EnumKind {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        self.variants.iter()
+            .filter_map(|variant| variant.discriminant.as_ref())
+            .find_map(|discriminant| discriminant.locate(offset))
+            .or_else(|| Some(self.item.clone()))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct EnumVariant {
+    pub name: Name,
+
+    /// The explicit discriminant, otherwise the variant takes the value after the previous variant
+    pub discriminant: Option<ExpressionKind>,
+    pub span: TextRange,
+}
+
+impl EnumVariant {
+    pub fn new(name: Name, discriminant: Option<ExpressionKind>, span: TextRange) -> Self {
+        Self {
+            name,
+            discriminant,
+            span,
+        }
+    }
+}
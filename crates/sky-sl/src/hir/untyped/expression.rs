@@ -1,27 +1,101 @@
 use rowan::TextRange;
 
-use crate::intern::Name;
+use super::{Block, ItemPath};
+use crate::{hir::named::NamedItemKind, intern::Name, syn::cst::{BinaryOperator, LiteralSuffix, LiteralValue, UnaryOperator}, text::Locate};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum ExpressionKind {
     LiteralExpression(LiteralExpression),
     BinaryExpression(BinaryExpression),
+    UnaryExpression(UnaryExpression),
     GroupExpression(GroupExpression),
     CallExpression(CallExpression),
     FieldAccessExpression(FieldAccessExpression),
+    MethodCallExpression(MethodCallExpression),
     IndexExpression(IndexExpression),
     PathExpression(PathExpression),
     StructExpression(StructExpression),
+    BlockExpression(BlockExpression),
+    MatchExpression(MatchExpression),
+}
+
+impl ExpressionKind {
+    pub fn span(&self) -> TextRange {
+        match self {
+            ExpressionKind::LiteralExpression(e) => e.span,
+            ExpressionKind::BinaryExpression(e) => e.span,
+            ExpressionKind::UnaryExpression(e) => e.span,
+            ExpressionKind::GroupExpression(e) => e.span,
+            ExpressionKind::CallExpression(e) => e.span,
+            ExpressionKind::FieldAccessExpression(e) => e.span,
+            ExpressionKind::MethodCallExpression(e) => e.span,
+            ExpressionKind::IndexExpression(e) => e.span,
+            ExpressionKind::PathExpression(e) => e.span,
+            ExpressionKind::StructExpression(e) => e.span,
+            ExpressionKind::BlockExpression(e) => e.span,
+            ExpressionKind::MatchExpression(e) => e.span,
+        }
+    }
+
+    /// Checks if the type of the expression is decided by its context, looking through groups and signs
+    pub fn is_unsuffixed_literal(&self) -> bool {
+        match self {
+            ExpressionKind::LiteralExpression(e) => e.suffix.is_none() && !matches!(e.value, LiteralValue::Boolean(_)),
+            ExpressionKind::GroupExpression(e) => e.inner.is_unsuffixed_literal(),
+            ExpressionKind::UnaryExpression(e) => e.operand.is_unsuffixed_literal(),
+            _ => false,
+        }
+    }
+}
+
+impl Locate for ExpressionKind {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span().contains(offset) {
+            return None;
+        }
+
+        match self {
+            ExpressionKind::LiteralExpression(_) | ExpressionKind::StructExpression(_) => None,
+            ExpressionKind::BinaryExpression(e) => e.lhs.locate(offset).or_else(|| e.rhs.locate(offset)),
+            ExpressionKind::UnaryExpression(e) => e.operand.locate(offset),
+            ExpressionKind::GroupExpression(e) => e.inner.locate(offset),
+            ExpressionKind::CallExpression(e) => {
+                e.callee.locate(offset).or_else(|| e.arguments.iter().find_map(|argument| argument.locate(offset)))
+            },
+            ExpressionKind::FieldAccessExpression(e) => e.expression.locate(offset),
+            ExpressionKind::MethodCallExpression(e) => {
+                e.receiver.locate(offset).or_else(|| e.arguments.iter().find_map(|argument| argument.locate(offset)))
+            },
+            ExpressionKind::IndexExpression(e) => e.expression.locate(offset).or_else(|| e.index.locate(offset)),
+            ExpressionKind::PathExpression(e) => e.item.clone(),
+            ExpressionKind::BlockExpression(e) => e.block.locate(offset),
+            ExpressionKind::MatchExpression(e) => e.scrutinee.locate(offset).or_else(|| {
+                e.arms.iter().find_map(|arm| {
+                    let pattern = arm.patterns.iter().find_map(|pattern| match pattern {
+                        Pattern::Path(path) if path.span.contains(offset) => path.item.clone(),
+                        _ => None,
+                    });
+                    pattern.or_else(|| arm.expression.locate(offset))
+                })
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct LiteralExpression {
+    pub value: LiteralValue,
+
+    /// The type suffix, e.g. the `u` in `3u`
+    pub suffix: Option<LiteralSuffix>,
     pub span: TextRange,
 }
 
 impl LiteralExpression {
-    pub fn new(span: TextRange) -> Self {
-        Self { span }
+    pub fn new(value: LiteralValue, suffix: Option<LiteralSuffix>, span: TextRange) -> Self {
+        Self { value, suffix, span }
     }
 }
 
@@ -41,58 +115,101 @@ impl GroupExpression {
 pub struct BinaryExpression {
     pub lhs: Box<ExpressionKind>,
     pub rhs: Box<ExpressionKind>,
+    pub operator: BinaryOperator,
     pub span: TextRange,
 }
 
 impl BinaryExpression {
-    pub fn new(lhs: Box<ExpressionKind>, rhs: Box<ExpressionKind>, span: TextRange) -> Self {
-        Self { lhs, rhs, span }
+    pub fn new(lhs: Box<ExpressionKind>, rhs: Box<ExpressionKind>, operator: BinaryOperator, span: TextRange) -> Self {
+        Self { lhs, rhs, operator, span }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct UnaryExpression {
+    pub operand: Box<ExpressionKind>,
+    pub operator: UnaryOperator,
+    pub span: TextRange,
+}
+
+impl UnaryExpression {
+    pub fn new(operand: Box<ExpressionKind>, operator: UnaryOperator, span: TextRange) -> Self {
+        Self { operand, operator, span }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct CallExpression {
+    pub callee: Box<ExpressionKind>,
     pub arguments: Vec<ExpressionKind>,
     pub span: TextRange,
 }
 
 impl CallExpression {
-    pub fn new(arguments: Vec<ExpressionKind>, span: TextRange) -> Self {
-        Self { arguments, span }
+    pub fn new(callee: Box<ExpressionKind>, arguments: Vec<ExpressionKind>, span: TextRange) -> Self {
+        Self { callee, arguments, span }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FieldAccessExpression {
+    pub expression: Box<ExpressionKind>,
+    pub field: Name,
     pub span: TextRange,
 }
 
 impl FieldAccessExpression {
-    pub fn new(span: TextRange) -> Self {
-        Self { span }
+    pub fn new(expression: Box<ExpressionKind>, field: Name, span: TextRange) -> Self {
+        Self { expression, field, span }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MethodCallExpression {
+    pub receiver: Box<ExpressionKind>,
+    pub method: Name,
+    pub arguments: Vec<ExpressionKind>,
+    pub span: TextRange,
+}
+
+impl MethodCallExpression {
+    pub fn new(receiver: Box<ExpressionKind>, method: Name, arguments: Vec<ExpressionKind>, span: TextRange) -> Self {
+        Self { receiver, method, arguments, span }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct IndexExpression {
+    pub expression: Box<ExpressionKind>,
+    pub index: Box<ExpressionKind>,
     pub span: TextRange,
 }
 
 impl IndexExpression {
-    pub fn new(span: TextRange) -> Self {
-        Self { span }
+    pub fn new(expression: Box<ExpressionKind>, index: Box<ExpressionKind>, span: TextRange) -> Self {
+        Self { expression, index, span }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct PathExpression {
     pub path: Name,
+
+    /// The item the path names in the module scope, a local variable with the same name shadows it
+    pub item: Option<NamedItemKind>,
+
+    /// The last segment if the path names a member of a type, like the enum variant `LightKind::Point`
+    /// or the associated function `Light::new`, `item` is the type then
+    pub member: Option<Name>,
+
+    /// The whole path if it has generic arguments, like `vec3<f32>` in `vec3<f32>(1.0, 2.0, 3.0)`
+    pub generic_path: Option<ItemPath>,
     pub span: TextRange,
 }
 
 impl PathExpression {
-    pub fn new(path: Name, span: TextRange) -> Self {
-        Self { path, span }
+    pub fn new(path: Name, item: Option<NamedItemKind>, member: Option<Name>, generic_path: Option<ItemPath>, span: TextRange) -> Self {
+        Self { path, item, member, generic_path, span }
     }
 }
 
@@ -106,3 +223,77 @@ impl StructExpression {
         Self { span }
     }
 }
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BlockExpression {
+    pub block: Block,
+    pub span: TextRange,
+}
+
+impl BlockExpression {
+    pub fn new(block: Block, span: TextRange) -> Self {
+        Self { block, span }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MatchExpression {
+    pub scrutinee: Box<ExpressionKind>,
+    pub arms: Vec<MatchArm>,
+    pub span: TextRange,
+}
+
+impl MatchExpression {
+    pub fn new(scrutinee: Box<ExpressionKind>, arms: Vec<MatchArm>, span: TextRange) -> Self {
+        Self { scrutinee, arms, span }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub expression: ExpressionKind,
+    pub span: TextRange,
+}
+
+impl MatchArm {
+    pub fn new(patterns: Vec<Pattern>, expression: ExpressionKind, span: TextRange) -> Self {
+        Self { patterns, expression, span }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Pattern {
+    /// An integer or boolean literal, e.g. `1` or `-1`
+    Literal(LiteralPattern),
+
+    /// An enum variant, e.g. `LightKind::Point`
+    Path(PathExpression),
+
+    /// The wildcard `_` matching every value
+    Wildcard { span: TextRange },
+}
+
+impl Pattern {
+    pub fn span(&self) -> TextRange {
+        match self {
+            Pattern::Literal(pattern) => pattern.span,
+            Pattern::Path(pattern) => pattern.span,
+            Pattern::Wildcard { span } => *span,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LiteralPattern {
+    pub value: LiteralValue,
+    pub suffix: Option<LiteralSuffix>,
+    pub negated: bool,
+    pub span: TextRange,
+}
+
+impl LiteralPattern {
+    pub fn new(value: LiteralValue, suffix: Option<LiteralSuffix>, negated: bool, span: TextRange) -> Self {
+        Self { value, suffix, negated, span }
+    }
+}
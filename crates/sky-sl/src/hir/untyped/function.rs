@@ -1,25 +1,56 @@
 use rowan::TextRange;
 use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
 
-use super::Block;
+use super::{Block, TypeReference};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FunctionKind {
     pub signature: FunctionSignature,
+
+    /// The stage of an entry point, `None` for functions that are only called by other functions
+    pub stage: Option<ShaderStage>,
     pub block: Block,
     pub span: TextRange,
 }
 
 impl FunctionKind {
-    pub fn new(signature: FunctionSignature, block: Block, span: TextRange) -> Self {
+    pub fn new(signature: FunctionSignature, stage: Option<ShaderStage>, block: Block, span: TextRange) -> Self {
         Self {
             signature,
+            stage,
             block,
             span,
         }
     }
 }
 
+/// The pipeline stage an entry point is run in, declared with e.g. `stage(vertex) fn main(...)`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Compute,
+}
+
+impl ShaderStage {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vertex" => Some(ShaderStage::Vertex),
+            "fragment" => Some(ShaderStage::Fragment),
+            "compute" => Some(ShaderStage::Compute),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShaderStage::Vertex => "vertex",
+            ShaderStage::Fragment => "fragment",
+            ShaderStage::Compute => "compute",
+        }
+    }
+}
+
 impl Locate for FunctionKind {
     type Item = NamedItemKind;
 
@@ -28,7 +59,7 @@ impl Locate for FunctionKind {
             return None;
         }
 
-        self.signature.locate(offset)
+        self.block.locate(offset).or_else(|| self.signature.locate(offset))
     }
 }
 
@@ -36,48 +67,98 @@ impl Locate for FunctionKind {
 pub struct FunctionSignature {
     pub name: Name,
     pub item: NamedItemKind,
+    pub generic_parameters: Vec<GenericParameter>,
     pub arguments: Vec<FunctionArgument>,
-    pub return_type: Option<NamedItemKind>,
+
+    /// Whether the first argument is `self`, the function is then a method of its impl block
+    pub has_receiver: bool,
+    pub return_type: Option<TypeReference>,
     pub span: TextRange,
 }
 
 impl FunctionSignature {
-    pub fn new(name: Name, item: NamedItemKind, arguments: Vec<FunctionArgument>, return_type: Option<NamedItemKind>, span: TextRange) -> Self {
+    pub fn new(name: Name, item: NamedItemKind, generic_parameters: Vec<GenericParameter>, arguments: Vec<FunctionArgument>, has_receiver: bool, return_type: Option<TypeReference>, span: TextRange) -> Self {
         Self {
             name,
             item,
+            generic_parameters,
             arguments,
+            has_receiver,
             return_type,
             span,
         }
     }
+
+    pub fn is_generic(&self) -> bool {
+        !self.generic_parameters.is_empty()
+    }
 }
 
 impl Locate for FunctionSignature {
     type Item = NamedItemKind;
 
     fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
-        if self.span.contains(offset) {
-            Some(self.item.clone())
-        } else {
-            None
+        if !self.span.contains(offset) {
+            return None;
         }
+
+        for argument in &self.arguments {
+            if let Some(item) = argument.type_reference.locate(offset) {
+                return Some(item);
+            }
+        }
+
+        if let Some(item) = self.return_type.as_ref().and_then(|return_type| return_type.locate(offset)) {
+            return Some(item);
+        }
+
+        Some(self.item.clone())
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FunctionArgument {
     pub name: Name,
-    pub item_type: NamedItemKind,
+    pub type_reference: TypeReference,
     pub span: TextRange,
 }
 
 impl FunctionArgument {
-    pub fn new(name: Name, item_type: NamedItemKind, span: TextRange) -> Self {
+    pub fn new(name: Name, type_reference: TypeReference, span: TextRange) -> Self {
         Self {
             name,
-            item_type,
+            type_reference,
             span,
         }
     }
 }
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct GenericParameter {
+    pub name: Name,
+    pub bounds: Vec<TypeBound>,
+    pub span: TextRange,
+}
+
+impl GenericParameter {
+    pub fn new(name: Name, bounds: Vec<TypeBound>, span: TextRange) -> Self {
+        Self {
+            name,
+            bounds,
+            span,
+        }
+    }
+}
+
+/// A bound as written in the source, e.g. `float` or `vec3`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TypeBound {
+    pub name: Name,
+    pub span: TextRange,
+}
+
+impl TypeBound {
+    pub fn new(name: Name, span: TextRange) -> Self {
+        Self { name, span }
+    }
+}
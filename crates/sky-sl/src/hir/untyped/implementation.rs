@@ -0,0 +1,43 @@
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
+use rowan::TextRange;
+
+use super::FunctionKind;
+
+/// An impl block, its functions are called as methods like `light.luminance()` or as associated functions like `Light::new()`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ImplKind {
+    /// The name of the implemented struct
+    pub name: Name,
+    pub item: NamedItemKind,
+    pub functions: Vec<FunctionKind>,
+    pub span: TextRange,
+}
+
+impl ImplKind {
+    pub fn new(name: Name, item: NamedItemKind, functions: Vec<FunctionKind>, span: TextRange) -> Self {
+        Self {
+            name,
+            item,
+            functions,
+            span,
+        }
+    }
+}
+
+impl Locate for ImplKind {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        for function in &self.functions {
+            if let Some(item) = function.locate(offset) {
+                return Some(item);
+            }
+        }
+
+        Some(self.item.clone())
+    }
+}
@@ -1,4 +1,4 @@
-use super::ItemPath;
+use super::{ExpressionKind, TypeReference};
 use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
 use rowan::TextRange;
 
@@ -7,15 +7,19 @@ use rowan::TextRange;
 pub struct LayoutKind {
     pub name: Name,
     pub item: NamedItemKind,
+
+    /// The expression in `layout(...)`, evaluated with `HirDatabase::eval_set_index`
+    pub set_index: Option<ExpressionKind>,
     pub members: Vec<LayoutMember>,
     pub span: TextRange,
 }
 
 impl LayoutKind {
-    pub fn new(name: Name, item: NamedItemKind, members: Vec<LayoutMember>, span: TextRange) -> Self {
+    pub fn new(name: Name, item: NamedItemKind, set_index: Option<ExpressionKind>, members: Vec<LayoutMember>, span: TextRange) -> Self {
         Self {
             name,
             item,
+            set_index,
             members,
             span,
         }
@@ -26,10 +30,75 @@ impl Locate for LayoutKind {
     type Item = NamedItemKind;
 
     fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
-        if self.span.contains(offset) {
-            Some(self.item.clone())
-        } else {
-            None
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        for member in &self.members {
+            if let Some(item) = member.type_reference.locate(offset) {
+                return Some(item);
+            }
+        }
+
+        Some(self.item.clone())
+    }
+}
+
+/// How a layout member is bound to the pipeline, e.g. the `storage` in `binding(0) storage particles: [Particle]`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BindingKind {
+    Uniform,
+    Storage,
+    Image,
+    Sampler,
+
+    /// A small block of data set directly when recording commands, not bound to a set or binding index
+    PushConstant,
+
+    /// Memory shared by the invocations of a compute workgroup, not bound to a set or binding index
+    Workgroup,
+}
+
+impl BindingKind {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            BindingKind::Uniform => "uniform",
+            BindingKind::Storage => "storage",
+            BindingKind::Image => "image",
+            BindingKind::Sampler => "sampler",
+            BindingKind::PushConstant => "push_constant",
+            BindingKind::Workgroup => "workgroup",
+        }
+    }
+
+    /// Whether the binding is backed by its own descriptor, push constants and workgroup memory are not
+    pub fn has_binding_index(self) -> bool {
+        !matches!(self, BindingKind::PushConstant | BindingKind::Workgroup)
+    }
+}
+
+/// How shaders may access a storage binding, e.g. the `read` in `storage(read) particles: [Particle]`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AccessMode {
+    Read,
+
+    /// The default if a storage binding has no access mode
+    ReadWrite,
+}
+
+impl AccessMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "read" => Some(AccessMode::Read),
+            "read_write" => Some(AccessMode::ReadWrite),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AccessMode::Read => "read",
+            AccessMode::ReadWrite => "read_write",
         }
     }
 }
@@ -37,15 +106,25 @@ impl Locate for LayoutKind {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct LayoutMember {
     pub name: Name,
-    pub type_path: ItemPath,
+
+    /// The expression in `binding(...)`, evaluated with `HirDatabase::eval_binding_index`
+    pub binding_index: Option<ExpressionKind>,
+    pub binding_kind: BindingKind,
+
+    /// The access mode in `storage(...)`, only storage bindings have one
+    pub access: Option<AccessMode>,
+    pub type_reference: TypeReference,
     pub span: TextRange,
 }
 
 impl LayoutMember {
-    pub fn new(name: Name, type_path: ItemPath, span: TextRange) -> Self {
+    pub fn new(name: Name, binding_index: Option<ExpressionKind>, binding_kind: BindingKind, access: Option<AccessMode>, type_reference: TypeReference, span: TextRange) -> Self {
         Self {
             name,
-            type_path,
+            binding_index,
+            binding_kind,
+            access,
+            type_reference,
             span,
         }
     }
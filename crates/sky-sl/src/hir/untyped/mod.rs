@@ -1,17 +1,27 @@
 mod block;
+mod constant;
+mod enumeration;
 mod expression;
 mod function;
+mod implementation;
 mod layout;
 mod module;
 mod path;
 mod statement;
 mod structure;
+mod ty;
+mod type_alias;
 
 pub use block::*;
+pub use constant::*;
+pub use enumeration::*;
 pub use expression::*;
 pub use function::*;
+pub use implementation::*;
 pub use layout::*;
 pub use module::*;
 pub use path::*;
 pub use statement::*;
 pub use structure::*;
+pub use ty::*;
+pub use type_alias::*;
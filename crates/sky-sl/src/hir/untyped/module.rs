@@ -1,4 +1,4 @@
-use super::{FunctionKind, LayoutKind, StructKind};
+use super::{ConstKind, EnumKind, FunctionKind, ImplKind, LayoutKind, StructKind, TypeAliasKind};
 use crate::{hir::{lower::LowerToHirError, named::NamedItemKind}, text::Locate};
 use rowan::TextRange;
 
@@ -41,6 +41,10 @@ pub enum ItemKind {
     Function(FunctionKind),
     Struct(StructKind),
     Layout(LayoutKind),
+    Const(ConstKind),
+    Enum(EnumKind),
+    Impl(ImplKind),
+    TypeAlias(TypeAliasKind),
 }
 
 impl Locate for ItemKind {
@@ -51,6 +55,10 @@ impl Locate for ItemKind {
             ItemKind::Function(f) => f.locate(offset),
             ItemKind::Struct(s) => s.locate(offset),
             ItemKind::Layout(l) => l.locate(offset),
+            ItemKind::Const(c) => c.locate(offset),
+            ItemKind::Enum(e) => e.locate(offset),
+            ItemKind::Impl(i) => i.locate(offset),
+            ItemKind::TypeAlias(t) => t.locate(offset),
         }
     }
 }
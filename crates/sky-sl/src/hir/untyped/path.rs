@@ -1,6 +1,6 @@
 use rowan::TextRange;
 
-use crate::{hir::named::NamedItemKind, intern::Name};
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ItemPath {
@@ -15,23 +15,69 @@ impl ItemPath {
             span,
         }
     }
+
+    pub fn last_segment(&self) -> &ItemPathSegment {
+        self.first_segment.last_segment()
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ItemPathSegment {
     pub name: Name,
     pub item: NamedItemKind,
+    pub generic_arguments: Vec<ItemPath>,
     pub next_segment: Option<Box<ItemPathSegment>>,
     pub span: TextRange,
 }
 
 impl ItemPathSegment {
-    pub fn new(name: Name, item: NamedItemKind, next_segment: Option<Box<ItemPathSegment>>, span: TextRange) -> Self {
+    pub fn new(name: Name, item: NamedItemKind, generic_arguments: Vec<ItemPath>, next_segment: Option<Box<ItemPathSegment>>, span: TextRange) -> Self {
         Self {
             name,
             item,
+            generic_arguments,
             next_segment,
             span,
         }
     }
+
+    /// Returns the segment the path ends in, e.g. `Baz` in `foo::bar::Baz`
+    pub fn last_segment(&self) -> &ItemPathSegment {
+        match &self.next_segment {
+            Some(next_segment) => next_segment.last_segment(),
+            None => self,
+        }
+    }
+}
+
+impl Locate for ItemPath {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if self.span.contains(offset) {
+            self.first_segment.locate(offset)
+        } else {
+            None
+        }
+    }
+}
+
+impl Locate for ItemPathSegment {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        if let Some(item) = self.next_segment.as_ref().and_then(|next| next.locate(offset)) {
+            return Some(item);
+        }
+
+        if let Some(item) = self.generic_arguments.iter().find_map(|argument| argument.locate(offset)) {
+            return Some(item);
+        }
+
+        Some(self.item.clone())
+    }
 }
@@ -1,25 +1,43 @@
-use crate::intern::Name;
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
 
-use super::ExpressionKind;
+use super::{ExpressionKind, TypeReference};
 use rowan::TextRange;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum StatementKind {
     Let(LetStatement),
     Expression(ExpressionStatement),
+    Assign(AssignStatement),
+}
+
+impl Locate for StatementKind {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        match self {
+            StatementKind::Let(let_statement) => {
+                let type_item = let_statement.type_reference.as_ref().and_then(|type_reference| type_reference.locate(offset));
+                type_item.or_else(|| let_statement.expression.locate(offset))
+            },
+            StatementKind::Expression(expression_statement) => expression_statement.expression.locate(offset),
+            StatementKind::Assign(assign_statement) => assign_statement.place.locate(offset).or_else(|| assign_statement.value.locate(offset)),
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct LetStatement {
     pub name: Name,
+    pub type_reference: Option<TypeReference>,
     pub expression: ExpressionKind,
     pub span: TextRange,
 }
 
 impl LetStatement {
-    pub fn new(name: Name, expression: ExpressionKind, span: TextRange) -> Self {
+    pub fn new(name: Name, type_reference: Option<TypeReference>, expression: ExpressionKind, span: TextRange) -> Self {
         Self {
             name,
+            type_reference,
             expression,
             span,
         }
@@ -29,11 +47,26 @@ impl LetStatement {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ExpressionStatement {
     pub expression: ExpressionKind,
+    pub has_semicolon: bool,
     pub span: TextRange,
 }
 
 impl ExpressionStatement {
-    pub fn new(expression: ExpressionKind, span: TextRange) -> Self {
-        Self { expression, span }
+    pub fn new(expression: ExpressionKind, has_semicolon: bool, span: TextRange) -> Self {
+        Self { expression, has_semicolon, span }
+    }
+}
+
+/// Writes a value to a place in storage or workgroup memory, e.g. `buffer.particles[i] = particle;`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AssignStatement {
+    pub place: ExpressionKind,
+    pub value: ExpressionKind,
+    pub span: TextRange,
+}
+
+impl AssignStatement {
+    pub fn new(place: ExpressionKind, value: ExpressionKind, span: TextRange) -> Self {
+        Self { place, value, span }
     }
 }
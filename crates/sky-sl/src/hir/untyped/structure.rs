@@ -1,7 +1,7 @@
 use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
 use rowan::TextRange;
 
-use super::ItemPath;
+use super::{ExpressionKind, TypeReference};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct StructKind {
@@ -26,27 +26,72 @@ impl Locate for StructKind {
     type Item = NamedItemKind;
 
     fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
-        if self.span.contains(offset) {
-            Some(self.item.clone())
-        } else {
-            None
+        if !self.span.contains(offset) {
+            return None;
         }
+
+        for member in &self.members {
+            if let Some(item) = member.type_reference.locate(offset) {
+                return Some(item);
+            }
+        }
+
+        Some(self.item.clone())
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct StructMember {
     pub name: Name,
-    pub item_path: ItemPath,
+
+    /// The expression in `location(...)`, evaluated with `HirDatabase::eval_location`
+    pub location: Option<ExpressionKind>,
+    pub interpolation: Option<Interpolation>,
+    pub type_reference: TypeReference,
     pub span: TextRange,
 }
 
 impl StructMember {
-    pub fn new(name: Name, item_path: ItemPath, span: TextRange) -> Self {
+    pub fn new(name: Name, location: Option<ExpressionKind>, interpolation: Option<Interpolation>, type_reference: TypeReference, span: TextRange) -> Self {
         Self {
             name,
-            item_path,
+            location,
+            interpolation,
+            type_reference,
             span,
         }
     }
 }
+
+/// How a member passed from the vertex to the fragment stage is interpolated across a primitive,
+/// declared with e.g. `interpolate(flat)`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Interpolation {
+    /// Perspective correct interpolation, the default for floating point members
+    Perspective,
+
+    /// Interpolation in screen space
+    Linear,
+
+    /// The value of the provoking vertex, the only interpolation integer members can use
+    Flat,
+}
+
+impl Interpolation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "perspective" => Some(Interpolation::Perspective),
+            "linear" => Some(Interpolation::Linear),
+            "flat" => Some(Interpolation::Flat),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Interpolation::Perspective => "perspective",
+            Interpolation::Linear => "linear",
+            Interpolation::Flat => "flat",
+        }
+    }
+}
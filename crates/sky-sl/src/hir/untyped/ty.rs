@@ -0,0 +1,70 @@
+use super::ItemPath;
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
+use rowan::TextRange;
+
+/// A type as written in the source, e.g. `vec3<f32>` or `[Particle; 16]`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum TypeReference {
+    Path(ItemPath),
+    Array(ArrayTypeReference),
+
+    /// A generic parameter of the enclosing function, e.g. `T` in `fn saturate<T: float>(x: T) -> T`
+    Generic(GenericTypeReference),
+
+    /// A type that could not be lowered, e.g. an array with an invalid length, it is already reported
+    Error(TextRange),
+}
+
+impl TypeReference {
+    pub fn span(&self) -> TextRange {
+        match self {
+            TypeReference::Path(path) => path.span,
+            TypeReference::Array(array) => array.span,
+            TypeReference::Generic(generic) => generic.span,
+            TypeReference::Error(span) => *span,
+        }
+    }
+}
+
+impl Locate for TypeReference {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        match self {
+            TypeReference::Path(path) => path.locate(offset),
+            TypeReference::Array(array) => array.element.locate(offset),
+            TypeReference::Generic(_) | TypeReference::Error(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ArrayTypeReference {
+    pub element: Box<TypeReference>,
+
+    /// The number of elements, `None` for runtime-sized arrays
+    pub length: Option<u32>,
+    pub span: TextRange,
+}
+
+impl ArrayTypeReference {
+    pub fn new(element: Box<TypeReference>, length: Option<u32>, span: TextRange) -> Self {
+        Self {
+            element,
+            length,
+            span,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct GenericTypeReference {
+    pub name: Name,
+    pub span: TextRange,
+}
+
+impl GenericTypeReference {
+    pub fn new(name: Name, span: TextRange) -> Self {
+        Self { name, span }
+    }
+}
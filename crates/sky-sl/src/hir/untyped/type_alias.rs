@@ -0,0 +1,37 @@
+use crate::{hir::named::NamedItemKind, intern::Name, text::Locate};
+use rowan::TextRange;
+
+use super::TypeReference;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TypeAliasKind {
+    pub name: Name,
+    pub item: NamedItemKind,
+
+    /// The aliased type, which may itself name another alias
+    pub type_reference: TypeReference,
+    pub span: TextRange,
+}
+
+impl TypeAliasKind {
+    pub fn new(name: Name, item: NamedItemKind, type_reference: TypeReference, span: TextRange) -> Self {
+        Self {
+            name,
+            item,
+            type_reference,
+            span,
+        }
+    }
+}
+
+impl Locate for TypeAliasKind {
+    type Item = NamedItemKind;
+
+    fn locate(&self, offset: rowan::TextSize) -> Option<Self::Item> {
+        if !self.span.contains(offset) {
+            return None;
+        }
+
+        self.type_reference.locate(offset).or_else(|| Some(self.item.clone()))
+    }
+}
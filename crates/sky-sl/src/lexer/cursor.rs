@@ -24,6 +24,12 @@ impl<'a> Cursor<'a> {
         self.chars().next()
     }
 
+    pub fn second(&self) -> Option<char> {
+        let mut chars = self.chars();
+        chars.next();
+        chars.next()
+    }
+
     pub fn chars(&self) -> Chars {
         let len: u32 = self.len.into();
         self.input[len as usize..].chars()
@@ -1,20 +1,75 @@
 use crate::syn::cst::SyntaxKind;
-use super::Cursor;
+use super::{Cursor, is_identifier_continue, is_identifier_start};
 
 pub fn is_num_literal_start(c: char) -> bool {
     ('0'..='9').contains(&c)
 }
 
+fn is_digit_or_separator(c: char) -> bool {
+    c.is_ascii_digit() || c == '_'
+}
+
+fn is_hex_digit_or_separator(c: char) -> bool {
+    c.is_ascii_hexdigit() || c == '_'
+}
+
 impl Cursor<'_> {
-    pub(super) fn num_literal(&mut self) -> SyntaxKind {
-        self.bump_while(is_num_literal_start);
+    /// Lexes number literals like `42`, `1_000`, `0xFF`, `0b1010`, `1.5e-3`, `3u` or `1.0f16`.
+    /// Malformed literals like `1e` or `0b102` are still lexed as one token and reported when lowering.
+    pub(super) fn num_literal(&mut self, first_char: char) -> SyntaxKind {
+        if first_char == '0' {
+            match self.first() {
+                Some('x') | Some('X') => {
+                    self.bump();
+                    self.bump_while(is_hex_digit_or_separator);
+                    self.literal_suffix();
+                    return SyntaxKind::IntLiteral;
+                },
+                Some('b') | Some('B') => {
+                    self.bump();
+                    self.bump_while(is_digit_or_separator);
+                    self.literal_suffix();
+                    return SyntaxKind::IntLiteral;
+                },
+                _ => {},
+            }
+        }
+
+        self.bump_while(is_digit_or_separator);
+
+        let mut is_float = false;
+
+        // `1.x` is a field access on `1` rather than a float
+        if self.first() == Some('.') && !matches!(self.second(), Some(c) if is_identifier_start(c) || c == '.') {
+            self.bump();
+            self.bump_while(is_digit_or_separator);
+            is_float = true;
+        }
 
-        if let Some('.') = self.first() {
+        if let Some('e') | Some('E') = self.first() {
             self.bump();
-            self.bump_while(is_num_literal_start);
+            if let Some('+') | Some('-') = self.first() {
+                self.bump();
+            }
+            self.bump_while(is_digit_or_separator);
+            is_float = true;
+        }
+
+        if self.literal_suffix() == Some('f') {
+            is_float = true;
+        }
+
+        if is_float {
             SyntaxKind::FloatLiteral
         } else {
             SyntaxKind::IntLiteral
         }
     }
+
+    /// Consumes a type suffix like `u32` or `f16` and returns its first character
+    fn literal_suffix(&mut self) -> Option<char> {
+        let first = self.first().filter(|c| is_identifier_start(*c))?;
+        self.bump_while(is_identifier_continue);
+        Some(first)
+    }
 }
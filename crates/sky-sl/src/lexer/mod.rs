@@ -37,9 +37,7 @@ impl Cursor<'_> {
             },
             c if c.is_whitespace() => self.whitespace(),
             c if is_identifier_start(c) => self.identifier(),
-            
-            // TODO number literals
-            c if is_num_literal_start(c) => self.num_literal(),
+            c if is_num_literal_start(c) => self.num_literal(c),
 
             ';' => SyntaxKind::Semicolon,
             ',' => SyntaxKind::Comma,
@@ -120,4 +118,33 @@ mod tests {
     fn it_works() {
         tokenize("/* */ hello world struct Foo { } fn bar() -> Baz { return null; }");
     }
+
+    #[test]
+    fn it_lexes_number_literals() {
+        let inputs = [
+            ("42", SyntaxKind::IntLiteral),
+            ("1_000_000", SyntaxKind::IntLiteral),
+            ("0xFF", SyntaxKind::IntLiteral),
+            ("0b1010", SyntaxKind::IntLiteral),
+            ("3u", SyntaxKind::IntLiteral),
+            ("255u8", SyntaxKind::IntLiteral),
+            ("1.", SyntaxKind::FloatLiteral),
+            ("1.5", SyntaxKind::FloatLiteral),
+            ("1e-3", SyntaxKind::FloatLiteral),
+            ("2.5E+10", SyntaxKind::FloatLiteral),
+            ("1.0f16", SyntaxKind::FloatLiteral),
+            ("1f", SyntaxKind::FloatLiteral),
+            ("1e", SyntaxKind::FloatLiteral),
+        ];
+
+        for (input, kind) in inputs.iter() {
+            let tokens = tokenize(input);
+            assert_eq!(tokens.len(), 1, "{}", input);
+            assert_eq!(tokens[0].kind(), *kind, "{}", input);
+        }
+
+        let tokens = tokenize("1.x");
+        assert_eq!(tokens[0].kind(), SyntaxKind::IntLiteral);
+        assert_eq!(tokens[0].len(), 1);
+    }
 }
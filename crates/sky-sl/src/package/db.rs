@@ -1,4 +1,4 @@
-use super::{Package, Manifest};
+use super::{Limits, Package, Manifest};
 use crate::fs::{FileDatabase, FileId, PathSegment};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -15,6 +15,12 @@ pub trait PackageDatabase: FileDatabase {
 
     /// Returns the file id corresponding to the source root
     fn source_root(&self, file: FileId) -> Option<FileId>;
+
+    /// Returns the source root of the package containing the given source file
+    fn package_root(&self, file: FileId) -> Option<FileId>;
+
+    /// Returns the limits of the package containing the given source file, the default limits outside of a package
+    fn limits(&self, file: FileId) -> Limits;
 }
 
 fn find_packages(db: &dyn PackageDatabase) -> Vec<Package> {
@@ -56,3 +62,31 @@ fn source_root(db: &dyn PackageDatabase, file: FileId) -> Option<FileId> {
         Some(target)
     }
 }
+
+fn package_root(db: &dyn PackageDatabase, file: FileId) -> Option<FileId> {
+    db.source_root(find_manifest(db, file)?)
+}
+
+fn limits(db: &dyn PackageDatabase, file: FileId) -> Limits {
+    match find_manifest(db, file) {
+        Some(manifest) => db.package(manifest).manifest.limits.clone(),
+        None => Limits::default(),
+    }
+}
+
+/// Finds the manifest in the directory of the file or the closest parent directory
+fn find_manifest(db: &dyn PackageDatabase, file: FileId) -> Option<FileId> {
+    let mut current = db.directory(file);
+
+    loop {
+        let directory_data = db.directory_data(current);
+        let manifest = directory_data.files()
+            .find(|file| db.lookup_file_data(**file).name == PACKAGE_MANIFEST_NAME);
+
+        if let Some(manifest) = manifest {
+            return Some(*manifest);
+        }
+
+        current = db.parent_directory(current)?;
+    }
+}
@@ -5,6 +5,9 @@ use std::str::FromStr;
 #[derive(Debug, Deserialize, PartialEq, Eq)]
 pub struct Manifest {
     pub package: PackageManifest,
+
+    #[serde(default)]
+    pub limits: Limits,
 }
 
 impl Manifest {
@@ -13,7 +16,8 @@ impl Manifest {
             package: PackageManifest {
                 name: "empty".to_string(),
                 path: None,
-            }
+            },
+            limits: Limits::default(),
         }
     }
 }
@@ -24,6 +28,22 @@ pub struct PackageManifest {
     pub path: Option<String>,
 }
 
+/// Device limits the shaders of a package are checked against, declared in the `[limits]` table of the manifest
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Hash)]
+#[serde(default)]
+pub struct Limits {
+    /// The size of push constants in bytes, 128 is the minimum every Vulkan device supports
+    pub max_push_constant_size: u32,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_push_constant_size: 128,
+        }
+    }
+}
+
 impl FromStr for Manifest {
     type Err = ManifestParseError;
 
@@ -1,5 +1,5 @@
 use crate::syn::cst::*;
-use super::{AstChildren, AstNode, NameOwner, PathOwner};
+use super::{AstChildren, AstNode, NameOwner, PathOwner, TypeOwner};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ArgumentList {
@@ -49,6 +49,7 @@ impl AstNode for Argument {
 
 impl NameOwner for Argument {}
 impl PathOwner for Argument {}
+impl TypeOwner for Argument {}
 
 pub trait ArgumentsOwner: AstNode {
     fn arguments(&self) -> AstChildren<Argument> {
@@ -0,0 +1,68 @@
+use super::{AstChildren, AstNode, ExpressionOwner, NameOwner, TypeIdentifierOwner, VisibilityOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConstDefinition {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ConstDefinition {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Const
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait ConstDefinitionOwner: AstNode {
+    fn const_definitions(&self) -> AstChildren<ConstDefinition> {
+        super::children(self)
+    }
+}
+
+impl ConstDefinition {
+    /// Whether this is a pipeline overridable constant declared with `override(id)`
+    pub fn is_override(&self) -> bool {
+        self.override_id().is_some()
+    }
+}
+
+impl ExpressionOwner for ConstDefinition {}
+impl NameOwner for ConstDefinition {}
+impl OverrideIdOwner for ConstDefinition {}
+impl TypeIdentifierOwner for ConstDefinition {}
+impl VisibilityOwner for ConstDefinition {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OverrideId {
+    syntax: SyntaxNode,
+}
+
+impl AstNode for OverrideId {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::OverrideId
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self> where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait OverrideIdOwner: AstNode {
+    fn override_id(&self) -> Option<OverrideId> {
+        super::child(self)
+    }
+}
+
+impl ExpressionOwner for OverrideId {}
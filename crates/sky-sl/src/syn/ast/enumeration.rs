@@ -0,0 +1,114 @@
+use super::{AstChildren, AstNode, ExpressionOwner, NameOwner, VisibilityOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EnumDefinition {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumDefinition {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Enum
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait EnumDefinitionOwner: AstNode {
+    fn enum_definitions(&self) -> AstChildren<EnumDefinition> {
+        super::children(self)
+    }
+}
+
+impl NameOwner for EnumDefinition {}
+impl EnumVariantListOwner for EnumDefinition {}
+impl VisibilityOwner for EnumDefinition {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EnumVariantList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumVariantList {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::EnumVariantList
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait EnumVariantListOwner: AstNode {
+    fn variant_list(&self) -> Option<EnumVariantList> {
+        super::child(self)
+    }
+}
+
+impl EnumVariantList {
+    pub fn variants(&self) -> AstChildren<EnumVariant> {
+        super::children(self)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EnumVariant {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumVariant {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::EnumVariant
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl NameOwner for EnumVariant {}
+
+impl EnumVariant {
+    pub fn discriminant(&self) -> Option<Discriminant> {
+        super::child(self)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Discriminant {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Discriminant {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Discriminant
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl ExpressionOwner for Discriminant {}
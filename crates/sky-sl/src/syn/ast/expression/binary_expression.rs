@@ -1,5 +1,5 @@
 use super::super::{AstNode, Expression, ExpressionsOwner};
-use crate::syn::cst::{SyntaxNode, SyntaxKind};
+use crate::syn::cst::{BinaryOperator, SyntaxNode, SyntaxKind, UnaryOperator};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct BinaryExpression {
@@ -34,47 +34,20 @@ impl AstNode for BinaryExpression {
 impl ExpressionsOwner for BinaryExpression {}
 impl OperatorOwner for BinaryExpression {}
 
-// TODO use cst::Operator ?
-pub enum OperatorKind {
-    /// Assignment operator
-    Assign,
-
-    /// Arithmetic Add operator
-    Add,
-
-    /// Arithmetic Subtract operator
-    Subtract,
-    
-    /// Arithmetic Multiply operator
-    Multiply,
-    
-    /// Arithmetic Divide operator
-    Divide,
-    
-    /// Remainder (modulo) operator
-    Remainder,
-
-    /// Logical AND operator
-    And,
-
-    /// Logical OR operator
-    Or,
-
-    /// Logical XOR operator
-    XOr,
-
-    // TODO left shift
-    // TODO right shift
-}
-
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Operator {
     pub(crate) syntax: SyntaxNode,
 }
 
 impl Operator {
-    pub fn kind(&self) -> OperatorKind {
-        todo!()
+    /// Returns the operator if this is the operator of a binary expression, e.g. the `**` in `a ** b`
+    pub fn binary_operator(&self) -> Option<BinaryOperator> {
+        BinaryOperator::from_symbol(&self.syntax.text().to_string())
+    }
+
+    /// Returns the operator if this is the operator of a unary expression, e.g. the `-` in `-a`
+    pub fn unary_operator(&self) -> Option<UnaryOperator> {
+        UnaryOperator::from_symbol(&self.syntax.text().to_string())
     }
 }
 
@@ -0,0 +1,24 @@
+use super::super::{AstNode, BlockDefinitionOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BlockExpression {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for BlockExpression {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::BlockExpression
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl BlockDefinitionOwner for BlockExpression {}
@@ -1,4 +1,4 @@
-use super::super::{AstNode, ExpressionOwner, ExpressionsOwner};
+use super::super::{AstChildren, AstNode, ExpressionOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -44,10 +44,38 @@ impl AstNode for CallArgumentList {
     }
 }
 
-impl ExpressionsOwner for CallArgumentList {}
+impl CallArgumentOwner for CallArgumentList {}
 
 pub trait CallArgumentListOwner: AstNode {
     fn arguments(&self) -> Option<CallArgumentList> {
         super::super::child(self)
     }
 }
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CallArgument {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for CallArgument {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::CallArgument
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl ExpressionOwner for CallArgument {}
+
+pub trait CallArgumentOwner: AstNode {
+    fn call_arguments(&self) -> AstChildren<CallArgument> {
+        super::super::children(self)
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::{AstNode, ExpressionsOwner};
+use super::super::{AstNode, ExpressionOwner, ExpressionsOwner, NameOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -21,4 +21,6 @@ impl AstNode for FieldAccessExpression {
     }
 }
 
+impl ExpressionOwner for FieldAccessExpression {}
 impl ExpressionsOwner for FieldAccessExpression {}
+impl NameOwner for FieldAccessExpression {}
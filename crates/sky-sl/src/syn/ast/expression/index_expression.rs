@@ -1,4 +1,4 @@
-use super::super::{AstNode, ExpressionsOwner};
+use super::super::{AstNode, ExpressionOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -21,4 +21,34 @@ impl AstNode for IndexExpression {
     }
 }
 
-impl ExpressionsOwner for IndexExpression {}
+/// The indexed expression, e.g. the `a` in `a[0]`
+impl ExpressionOwner for IndexExpression {}
+impl IndexerOwner for IndexExpression {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Indexer {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Indexer {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Indexer
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait IndexerOwner: AstNode {
+    fn indexer(&self) -> Option<Indexer> {
+        super::super::child(self)
+    }
+}
+
+impl ExpressionOwner for Indexer {}
@@ -1,5 +1,5 @@
 use super::AstNode;
-use crate::syn::cst::{SyntaxNode, SyntaxKind};
+use crate::syn::cst::{parse_literal, Literal, LiteralError, SyntaxNode, SyntaxKind, SyntaxToken};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct LiteralExpression {
@@ -20,3 +20,16 @@ impl AstNode for LiteralExpression {
         &self.syntax
     }
 }
+
+impl LiteralExpression {
+    /// Returns the literal token, e.g. `true`, `42` or `1.0`
+    pub fn literal(&self) -> Option<SyntaxToken> {
+        self.syntax.first_token()
+    }
+
+    /// Decodes the literal token, e.g. `0xFF` into the integer 255
+    pub fn value(&self) -> Result<Literal, LiteralError> {
+        let literal = self.literal().ok_or(LiteralError::NotALiteral)?;
+        parse_literal(&literal)
+    }
+}
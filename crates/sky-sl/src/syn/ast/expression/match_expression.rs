@@ -0,0 +1,161 @@
+use super::super::{AstChildren, AstNode, ExpressionOwner, PathOwner};
+use super::LiteralExpression;
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatchExpression {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchExpression {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::MatchExpression
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl MatchExpression {
+    /// The matched value, e.g. `kind` in `match kind { .. }`
+    pub fn scrutinee(&self) -> Option<super::Expression> {
+        super::super::child(self)
+    }
+
+    pub fn arms(&self) -> AstChildren<MatchArm> {
+        self.syntax
+            .children()
+            .find(|child| child.kind() == SyntaxKind::MatchArmList)
+            .map(|arm_list| AstChildren::new(&arm_list))
+            .unwrap_or_else(|| AstChildren::new(&self.syntax))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatchArm {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchArm {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::MatchArm
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl MatchArm {
+    /// The alternative patterns of the arm, e.g. `1` and `2` in `1 | 2 => a`
+    pub fn patterns(&self) -> AstChildren<Pattern> {
+        super::super::children(self)
+    }
+}
+
+// the expression the arm evaluates to
+impl ExpressionOwner for MatchArm {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Pattern {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Pattern {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        matches!(kind, SyntaxKind::LiteralPattern | SyntaxKind::PathPattern | SyntaxKind::WildcardPattern)
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl Pattern {
+    pub fn kind(&self) -> PatternKind {
+        match self.syntax.kind() {
+            SyntaxKind::LiteralPattern => PatternKind::Literal(LiteralPattern { syntax: self.syntax.clone() }),
+            SyntaxKind::PathPattern => PatternKind::Path(PathPattern { syntax: self.syntax.clone() }),
+            SyntaxKind::WildcardPattern => PatternKind::Wildcard,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PatternKind {
+    Literal(LiteralPattern),
+    Path(PathPattern),
+    Wildcard,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LiteralPattern {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl LiteralPattern {
+    pub fn is_negated(&self) -> bool {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|child| child.into_token())
+            .any(|token| token.kind() == SyntaxKind::Minus)
+    }
+
+    pub fn literal(&self) -> Option<LiteralExpression> {
+        super::super::child(self)
+    }
+}
+
+impl AstNode for LiteralPattern {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::LiteralPattern
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PathPattern {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PathPattern {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::PathPattern
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl PathOwner for PathPattern {}
@@ -0,0 +1,27 @@
+use super::super::{AstNode, ExpressionOwner, NameOwner};
+use super::CallArgumentListOwner;
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MethodCallExpression {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MethodCallExpression {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::MethodCallExpression
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl ExpressionOwner for MethodCallExpression {}
+impl NameOwner for MethodCallExpression {}
+impl CallArgumentListOwner for MethodCallExpression {}
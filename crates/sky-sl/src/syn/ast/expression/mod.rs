@@ -2,22 +2,30 @@ use super::{AstChildren, AstNode};
 use crate::syn::cst::{SyntaxKind, SyntaxNode};
 
 mod binary_expression;
+mod block_expression;
 mod call_expression;
 mod field_access_expression;
 mod group_expression;
 mod index_expression;
 mod literal_expression;
+mod match_expression;
+mod method_call_expression;
 mod path_expression;
 mod struct_expression;
+mod unary_expression;
 
 pub use binary_expression::*;
+pub use block_expression::*;
 pub use call_expression::*;
 pub use field_access_expression::*;
 pub use group_expression::*;
 pub use index_expression::*;
 pub use literal_expression::*;
+pub use match_expression::*;
+pub use method_call_expression::*;
 pub use path_expression::*;
 pub use struct_expression::*;
+pub use unary_expression::*;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Expression {
@@ -30,11 +38,15 @@ impl AstNode for Expression {
             SyntaxKind::LiteralExpression
             | SyntaxKind::GroupExpression
             | SyntaxKind::BinaryExpression
+            | SyntaxKind::UnaryExpression
             | SyntaxKind::CallExpression
             | SyntaxKind::FieldAccessExpression
+            | SyntaxKind::MethodCallExpression
             | SyntaxKind::IndexExpression
             | SyntaxKind::StructExpression
-            | SyntaxKind::PathExpression => true,
+            | SyntaxKind::PathExpression
+            | SyntaxKind::BlockExpression
+            | SyntaxKind::MatchExpression => true,
             _ => false,
         }
     }
@@ -63,12 +75,18 @@ impl Expression {
             SyntaxKind::BinaryExpression => ExpressionKind::BinaryExpression(
                 BinaryExpression::cast_from(self.syntax().clone()).unwrap(),
             ),
+            SyntaxKind::UnaryExpression => ExpressionKind::UnaryExpression(
+                UnaryExpression::cast_from(self.syntax().clone()).unwrap(),
+            ),
             SyntaxKind::CallExpression => ExpressionKind::CallExpression(
                 CallExpression::cast_from(self.syntax().clone()).unwrap(),
             ),
             SyntaxKind::FieldAccessExpression => ExpressionKind::FieldAccessExpression(
                 FieldAccessExpression::cast_from(self.syntax().clone()).unwrap(),
             ),
+            SyntaxKind::MethodCallExpression => ExpressionKind::MethodCallExpression(
+                MethodCallExpression::cast_from(self.syntax().clone()).unwrap(),
+            ),
             SyntaxKind::IndexExpression => ExpressionKind::IndexExpression(
                 IndexExpression::cast_from(self.syntax().clone()).unwrap(),
             ),
@@ -78,6 +96,12 @@ impl Expression {
             SyntaxKind::StructExpression => ExpressionKind::StructExpression(
                 StructExpression::cast_from(self.syntax().clone()).unwrap(),
             ),
+            SyntaxKind::BlockExpression => ExpressionKind::BlockExpression(
+                BlockExpression::cast_from(self.syntax().clone()).unwrap(),
+            ),
+            SyntaxKind::MatchExpression => ExpressionKind::MatchExpression(
+                MatchExpression::cast_from(self.syntax().clone()).unwrap(),
+            ),
             _ => unreachable!(),
         }
     }
@@ -88,11 +112,15 @@ pub enum ExpressionKind {
     LiteralExpression(LiteralExpression),
     GroupExpression(GroupExpression),
     BinaryExpression(BinaryExpression),
+    UnaryExpression(UnaryExpression),
     CallExpression(CallExpression),
     FieldAccessExpression(FieldAccessExpression),
+    MethodCallExpression(MethodCallExpression),
     IndexExpression(IndexExpression),
     PathExpression(PathExpression),
     StructExpression(StructExpression),
+    BlockExpression(BlockExpression),
+    MatchExpression(MatchExpression),
 }
 
 pub trait ExpressionOwner: AstNode {
@@ -1,4 +1,4 @@
-use super::AstNode;
+use super::super::{AstNode, PathOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -20,3 +20,5 @@ impl AstNode for PathExpression {
         &self.syntax
     }
 }
+
+impl PathOwner for PathExpression {}
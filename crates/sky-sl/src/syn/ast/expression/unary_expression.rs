@@ -0,0 +1,25 @@
+use super::super::{AstNode, ExpressionOwner, OperatorOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnaryExpression {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for UnaryExpression {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::UnaryExpression
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl ExpressionOwner for UnaryExpression {}
+impl OperatorOwner for UnaryExpression {}
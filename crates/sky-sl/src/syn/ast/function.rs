@@ -1,5 +1,5 @@
-use super::{ArgumentListOwner, AstChildren, AstNode, BlockDefinitionOwner, NameOwner, ReturnTypeOwner};
-use crate::syn::cst::{SyntaxNode, SyntaxKind};
+use super::{ArgumentListOwner, AstChildren, AstNode, BlockDefinitionOwner, NameOwner, ReturnTypeOwner, VisibilityOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind, SyntaxToken};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FunctionDefinition {
@@ -29,6 +29,44 @@ pub trait FunctionDefinitionOwner: AstNode {
 
 impl FunctionSignatureOwner for FunctionDefinition {}
 impl BlockDefinitionOwner for FunctionDefinition {}
+impl VisibilityOwner for FunctionDefinition {}
+impl StageOwner for FunctionDefinition {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Stage {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Stage {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Stage
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl Stage {
+    /// The name of the stage, e.g. `fragment` in `stage(fragment)`
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|child| child.into_token())
+            .find(|token| token.kind() == SyntaxKind::Identifier)
+    }
+}
+
+pub trait StageOwner: AstNode {
+    fn stage(&self) -> Option<Stage> {
+        super::child(self)
+    }
+}
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FunctionSignature {
@@ -59,3 +97,86 @@ pub trait FunctionSignatureOwner: AstNode {
 impl NameOwner for FunctionSignature {}
 impl ArgumentListOwner for FunctionSignature {}
 impl ReturnTypeOwner for FunctionSignature {}
+impl GenericParameterListOwner for FunctionSignature {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GenericParameterList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for GenericParameterList {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::GenericParameterList
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl GenericParameterList {
+    pub fn parameters(&self) -> AstChildren<GenericParameter> {
+        super::children(self)
+    }
+}
+
+pub trait GenericParameterListOwner: AstNode {
+    fn generic_parameter_list(&self) -> Option<GenericParameterList> {
+        super::child(self)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GenericParameter {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for GenericParameter {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::GenericParameter
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl GenericParameter {
+    pub fn bounds(&self) -> AstChildren<TypeBound> {
+        super::children(self)
+    }
+}
+
+impl NameOwner for GenericParameter {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TypeBound {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeBound {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::TypeBound
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl NameOwner for TypeBound {}
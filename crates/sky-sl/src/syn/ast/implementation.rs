@@ -0,0 +1,63 @@
+use super::{AstChildren, AstNode, FunctionDefinition, NameOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImplDefinition {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ImplDefinition {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Impl
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait ImplDefinitionOwner: AstNode {
+    fn impl_definitions(&self) -> AstChildren<ImplDefinition> {
+        super::children(self)
+    }
+}
+
+impl NameOwner for ImplDefinition {}
+impl ImplItemListOwner for ImplDefinition {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImplItemList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ImplItemList {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::ImplItemList
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait ImplItemListOwner: AstNode {
+    fn item_list(&self) -> Option<ImplItemList> {
+        super::child(self)
+    }
+}
+
+impl ImplItemList {
+    pub fn functions(&self) -> AstChildren<FunctionDefinition> {
+        super::children(self)
+    }
+}
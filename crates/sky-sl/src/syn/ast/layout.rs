@@ -1,5 +1,5 @@
-use super::{AstChildren, AstNode, ExpressionOwner, NameOwner, PathOwner};
-use crate::syn::cst::{SyntaxNode, SyntaxKind};
+use super::{AstChildren, AstNode, ExpressionOwner, NameOwner, PathOwner, TypeOwner, VisibilityOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind, SyntaxToken};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct LayoutDefinition {
@@ -29,6 +29,35 @@ pub trait LayoutDefinitionOwner: AstNode {
 
 impl NameOwner for LayoutDefinition {}
 impl LayoutMemberListOwner for LayoutDefinition {}
+impl VisibilityOwner for LayoutDefinition {}
+impl SetIndexOwner for LayoutDefinition {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SetIndex {
+    syntax: SyntaxNode,
+}
+
+impl AstNode for SetIndex {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::SetIndex
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self> where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait SetIndexOwner: AstNode {
+    fn set_index(&self) -> Option<SetIndex> {
+        super::child(self)
+    }
+}
+
+impl ExpressionOwner for SetIndex {}
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct LayoutMemberList {
@@ -78,8 +107,10 @@ impl AstNode for LayoutMember {
 
 impl NameOwner for LayoutMember {}
 impl PathOwner for LayoutMember {}
+impl TypeOwner for LayoutMember {}
 impl BindingIndexOwner for LayoutMember {}
 impl BindingKindOwner for LayoutMember {}
+impl AccessModeOwner for LayoutMember {}
 
 pub trait LayoutMemberOwner: AstNode {
     fn layout_member(&self) -> AstChildren<LayoutMember> {
@@ -133,8 +164,54 @@ impl AstNode for BindingKind {
     }
 }
 
+impl BindingKind {
+    /// The binding keyword, e.g. `SyntaxKind::UniformKeyword`
+    pub fn keyword(&self) -> Option<SyntaxKind> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|child| child.into_token())
+            .map(|token| token.kind())
+            .find(|kind| kind.is_keyword())
+    }
+}
+
 pub trait BindingKindOwner: AstNode {
     fn binding_kind(&self) -> Option<BindingKind> {
         super::child(self)
     }
 }
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AccessMode {
+    syntax: SyntaxNode,
+}
+
+impl AstNode for AccessMode {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::AccessMode
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self> where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl AccessMode {
+    /// The name of the access mode, e.g. `read` in `storage(read)`
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|child| child.into_token())
+            .find(|token| token.kind() == SyntaxKind::Identifier)
+    }
+}
+
+pub trait AccessModeOwner: AstNode {
+    fn access_mode(&self) -> Option<AccessMode> {
+        super::child(self)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::syn::cst::*;
-use super::{AstChildren, AstNode, NameOwner, PathOwner};
+use super::{AstChildren, AstNode, ExpressionOwner, NameOwner, PathOwner, TypeOwner, VisibilityOwner};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct MemberList {
@@ -49,9 +49,75 @@ impl AstNode for Member {
 
 impl NameOwner for Member {}
 impl PathOwner for Member {}
+impl TypeOwner for Member {}
+impl VisibilityOwner for Member {}
+impl LocationOwner for Member {}
+impl InterpolationOwner for Member {}
 
 pub trait MemberOwner: AstNode {
     fn member(&self) -> AstChildren<Member> {
         super::children(self)
     }
 }
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Location {
+    syntax: SyntaxNode,
+}
+
+impl AstNode for Location {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Location
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self> where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait LocationOwner: AstNode {
+    fn location(&self) -> Option<Location> {
+        super::child(self)
+    }
+}
+
+impl ExpressionOwner for Location {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Interpolation {
+    syntax: SyntaxNode,
+}
+
+impl AstNode for Interpolation {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Interpolation
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self> where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl Interpolation {
+    /// The name of the interpolation, e.g. `flat` in `interpolate(flat)`
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|child| child.into_token())
+            .find(|token| token.kind() == SyntaxKind::Identifier)
+    }
+}
+
+pub trait InterpolationOwner: AstNode {
+    fn interpolation(&self) -> Option<Interpolation> {
+        super::child(self)
+    }
+}
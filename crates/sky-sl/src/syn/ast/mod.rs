@@ -6,8 +6,11 @@ mod statement;
 
 mod arguments;
 mod block;
+mod constant;
+mod enumeration;
 mod function;
 mod identifier;
+mod implementation;
 mod layout;
 mod member;
 mod module_declaration;
@@ -17,16 +20,22 @@ mod path;
 mod return_type;
 mod root;
 mod structure;
+mod ty;
+mod type_alias;
 mod type_identifier;
 mod use_declaration;
+mod visibility;
 
 pub use expression::*;
 pub use statement::*;
 
 pub use arguments::*;
 pub use block::*;
+pub use constant::*;
+pub use enumeration::*;
 pub use function::*;
 pub use identifier::*;
+pub use implementation::*;
 pub use layout::*;
 pub use member::*;
 pub use module_declaration::*;
@@ -36,8 +45,11 @@ pub use path::*;
 pub use return_type::*;
 pub use root::*;
 pub use structure::*;
+pub use ty::*;
+pub use type_alias::*;
 pub use type_identifier::*;
 pub use use_declaration::*;
+pub use visibility::*;
 
 pub trait AstNode: Clone {
     fn can_cast_from(kind: SyntaxKind) -> bool;
@@ -1,6 +1,6 @@
 use super::{
-    AstChildren, AstNode, FunctionDefinition, LayoutDefinition, ModuleDeclaration,
-    StructDefinition, UseDeclaration,
+    AstChildren, AstNode, ConstDefinition, EnumDefinition, FunctionDefinition, ImplDefinition, LayoutDefinition, ModuleDeclaration,
+    StructDefinition, TypeAliasDefinition, UseDeclaration,
 };
 use crate::syn::cst::*;
 
@@ -16,7 +16,11 @@ impl AstNode for ModuleItem {
             | SyntaxKind::Struct
             | SyntaxKind::ModuleDeclaration
             | SyntaxKind::UseDeclaration
-            | SyntaxKind::Layout => true,
+            | SyntaxKind::Layout
+            | SyntaxKind::Const
+            | SyntaxKind::Enum
+            | SyntaxKind::Impl
+            | SyntaxKind::TypeAlias => true,
             _ => false,
         }
     }
@@ -51,6 +55,18 @@ impl ModuleItem {
             SyntaxKind::Layout => ModuleItemKind::LayoutDefinition(
                 LayoutDefinition::cast_from(self.syntax.clone()).unwrap(),
             ),
+            SyntaxKind::Const => ModuleItemKind::ConstDefinition(
+                ConstDefinition::cast_from(self.syntax.clone()).unwrap(),
+            ),
+            SyntaxKind::Enum => ModuleItemKind::EnumDefinition(
+                EnumDefinition::cast_from(self.syntax.clone()).unwrap(),
+            ),
+            SyntaxKind::Impl => ModuleItemKind::ImplDefinition(
+                ImplDefinition::cast_from(self.syntax.clone()).unwrap(),
+            ),
+            SyntaxKind::TypeAlias => ModuleItemKind::TypeAliasDefinition(
+                TypeAliasDefinition::cast_from(self.syntax.clone()).unwrap(),
+            ),
             _ => unreachable!(),
         }
     }
@@ -63,6 +79,10 @@ pub enum ModuleItemKind {
     FunctionDefinition(FunctionDefinition),
     StructDefinition(StructDefinition),
     LayoutDefinition(LayoutDefinition),
+    ConstDefinition(ConstDefinition),
+    EnumDefinition(EnumDefinition),
+    ImplDefinition(ImplDefinition),
+    TypeAliasDefinition(TypeAliasDefinition),
 }
 
 pub trait ModuleItemOwner: AstNode {
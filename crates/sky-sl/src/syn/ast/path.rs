@@ -1,4 +1,4 @@
-use super::{AstNode, NameOwner};
+use super::{AstChildren, AstNode, NameOwner};
 use crate::syn::cst::{SyntaxKind, SyntaxNode};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -61,3 +61,40 @@ pub trait PathSegmentOwner: AstNode {
 
 impl NameOwner for PathSegment {}
 impl PathSegmentOwner for PathSegment {}
+impl GenericArgumentListOwner for PathSegment {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GenericArgumentList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for GenericArgumentList {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::GenericArgumentList
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait GenericArgumentListOwner: AstNode {
+    fn generic_argument_list(&self) -> Option<GenericArgumentList> {
+        super::child(self)
+    }
+}
+
+impl PathsOwner for GenericArgumentList {}
+
+pub trait PathsOwner: AstNode {
+    fn paths(&self) -> AstChildren<Path> {
+        super::children(self)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::syn::cst::*;
-use super::{AstNode, PathOwner};
+use super::{AstNode, PathOwner, TypeOwner};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ReturnType {
@@ -28,3 +28,4 @@ pub trait ReturnTypeOwner: AstNode {
 }
 
 impl PathOwner for ReturnType {}
+impl TypeOwner for ReturnType {}
@@ -1,4 +1,4 @@
-use super::{AstNode, FunctionDefinitionOwner, LayoutDefinitionOwner, ModuleDeclarationOwner, ModuleItemOwner, StructDefinitionOwner, UseDeclarationOwner};
+use super::{AstNode, ConstDefinitionOwner, EnumDefinitionOwner, FunctionDefinitionOwner, ImplDefinitionOwner, LayoutDefinitionOwner, ModuleDeclarationOwner, ModuleItemOwner, StructDefinitionOwner, TypeAliasDefinitionOwner, UseDeclarationOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -27,3 +27,7 @@ impl StructDefinitionOwner for Root {}
 impl ModuleDeclarationOwner for Root {}
 impl UseDeclarationOwner for Root {}
 impl LayoutDefinitionOwner for Root {}
+impl ConstDefinitionOwner for Root {}
+impl EnumDefinitionOwner for Root {}
+impl ImplDefinitionOwner for Root {}
+impl TypeAliasDefinitionOwner for Root {}
@@ -0,0 +1,36 @@
+use super::super::{AstNode, Expression, ExpressionsOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AssignStatement {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AssignStatement {
+    /// The place written to, e.g. `buffer.count` in `buffer.count = 0;`
+    pub fn place(&self) -> Option<Expression> {
+        self.expressions().nth(0)
+    }
+
+    /// The value written to the place
+    pub fn value(&self) -> Option<Expression> {
+        self.expressions().nth(1)
+    }
+}
+
+impl AstNode for AssignStatement {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::AssignStatement
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl ExpressionsOwner for AssignStatement {}
@@ -27,4 +27,13 @@ pub trait ExpressionStatementsOwner: AstNode {
     }
 }
 
+impl ExpressionStatement {
+    /// Returns true if the statement is terminated by a `;`
+    pub fn has_semicolon(&self) -> bool {
+        self.syntax
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::Semicolon)
+    }
+}
+
 impl ExpressionOwner for ExpressionStatement {}
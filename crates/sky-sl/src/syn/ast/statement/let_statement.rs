@@ -1,4 +1,4 @@
-use super::super::{AstNode, AstChildren, ExpressionOwner, IdentifierOwner};
+use super::super::{AstNode, AstChildren, ExpressionOwner, IdentifierOwner, NameOwner, TypeIdentifierOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -29,3 +29,5 @@ pub trait LetStatementsOwner: AstNode {
 
 impl ExpressionOwner for LetStatement {}
 impl IdentifierOwner for LetStatement {}
+impl NameOwner for LetStatement {}
+impl TypeIdentifierOwner for LetStatement {}
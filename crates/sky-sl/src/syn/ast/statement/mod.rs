@@ -1,6 +1,8 @@
+mod assign_statement;
 mod expression_statement;
 mod let_statement;
 
+pub use assign_statement::*;
 pub use expression_statement::*;
 pub use let_statement::*;
 
@@ -15,7 +17,7 @@ pub struct Statement {
 impl AstNode for Statement {
     fn can_cast_from(kind: SyntaxKind) -> bool {
         match kind {
-            SyntaxKind::LetStatement | SyntaxKind::ExpressionStatement => true,
+            SyntaxKind::LetStatement | SyntaxKind::ExpressionStatement | SyntaxKind::AssignStatement => true,
             _ => false,
         }
     }
@@ -35,6 +37,7 @@ impl Statement {
         match self.syntax().kind() {
             SyntaxKind::LetStatement => StatementKind::Let(LetStatement::cast_from(self.syntax().clone()).unwrap()),
             SyntaxKind::ExpressionStatement => StatementKind::Expression(ExpressionStatement::cast_from(self.syntax().clone()).unwrap()),
+            SyntaxKind::AssignStatement => StatementKind::Assign(AssignStatement::cast_from(self.syntax().clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -44,6 +47,7 @@ impl Statement {
 pub enum StatementKind {
     Let(LetStatement),
     Expression(ExpressionStatement),
+    Assign(AssignStatement),
 }
 
 pub trait StatementsOwner: AstNode {
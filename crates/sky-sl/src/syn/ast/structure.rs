@@ -1,4 +1,4 @@
-use super::{AstChildren, AstNode, MemberListOwner, NameOwner};
+use super::{AstChildren, AstNode, MemberListOwner, NameOwner, VisibilityOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -29,3 +29,4 @@ pub trait StructDefinitionOwner: AstNode {
 
 impl NameOwner for StructDefinition {}
 impl MemberListOwner for StructDefinition {}
+impl VisibilityOwner for StructDefinition {}
@@ -0,0 +1,71 @@
+use super::{AstNode, ExpressionOwner, Path};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+/// Any type, e.g. `vec3<f32>` or `[f32; 4]`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Type {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Type {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        matches!(kind, SyntaxKind::Path | SyntaxKind::ArrayType)
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl Type {
+    pub fn kind(&self) -> TypeKind {
+        match self.syntax().kind() {
+            SyntaxKind::Path => TypeKind::Path(Path::cast_from(self.syntax().clone()).unwrap()),
+            SyntaxKind::ArrayType => TypeKind::Array(ArrayType::cast_from(self.syntax().clone()).unwrap()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TypeKind {
+    Path(Path),
+    Array(ArrayType),
+}
+
+pub trait TypeOwner: AstNode {
+    fn ty(&self) -> Option<Type> {
+        super::child(self)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ArrayType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ArrayType {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::ArrayType
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+/// The element type
+impl TypeOwner for ArrayType {}
+
+/// The length, missing for runtime-sized arrays
+impl ExpressionOwner for ArrayType {}
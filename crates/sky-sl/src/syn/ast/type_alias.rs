@@ -0,0 +1,32 @@
+use super::{AstChildren, AstNode, NameOwner, TypeOwner, VisibilityOwner};
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TypeAliasDefinition {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeAliasDefinition {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::TypeAlias
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+pub trait TypeAliasDefinitionOwner: AstNode {
+    fn type_alias_definitions(&self) -> AstChildren<TypeAliasDefinition> {
+        super::children(self)
+    }
+}
+
+impl NameOwner for TypeAliasDefinition {}
+impl TypeOwner for TypeAliasDefinition {}
+impl VisibilityOwner for TypeAliasDefinition {}
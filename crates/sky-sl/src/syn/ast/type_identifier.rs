@@ -1,4 +1,4 @@
-use super::AstNode;
+use super::{AstNode, PathOwner, TypeOwner};
 use crate::syn::cst::{SyntaxNode, SyntaxKind};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -26,3 +26,6 @@ pub trait TypeIdentifierOwner: AstNode {
         super::child(self)
     }
 }
+
+impl PathOwner for TypeIdentifier {}
+impl TypeOwner for TypeIdentifier {}
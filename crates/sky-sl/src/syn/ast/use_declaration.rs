@@ -54,3 +54,95 @@ pub trait UseTreeOwner: AstNode {
         super::child(self)
     }
 }
+
+impl UseTree {
+    pub fn segment(&self) -> Option<UseSegment> {
+        super::child(self)
+    }
+
+    /// The `*` in `use foo::*;`
+    pub fn use_all(&self) -> Option<UseAll> {
+        super::child(self)
+    }
+
+    /// The `{bar, baz}` in `use foo::{bar, baz};`
+    pub fn use_group(&self) -> Option<UseGroup> {
+        super::child(self)
+    }
+}
+
+/// The nested tree, e.g. `bar` in `foo::bar`
+impl UseTreeOwner for UseTree {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UseSegment {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for UseSegment {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::UseSegment
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl UseSegment {
+    /// Returns true for the `package` keyword referring to the root module of the package
+    pub fn is_package(&self) -> bool {
+        self.syntax.children_with_tokens().any(|child| child.kind() == SyntaxKind::PackageKeyword)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UseAll {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for UseAll {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::UseAll
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UseGroup {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for UseGroup {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::UseGroup
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl UseGroup {
+    pub fn use_trees(&self) -> AstChildren<UseTree> {
+        super::children(self)
+    }
+}
@@ -0,0 +1,35 @@
+use super::AstNode;
+use crate::syn::cst::{SyntaxNode, SyntaxKind};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Visibility {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Visibility {
+    fn can_cast_from(kind: SyntaxKind) -> bool {
+        kind == SyntaxKind::Visibility
+    }
+
+    fn cast_from(syntax: SyntaxNode) -> Option<Self>
+        where Self: Sized {
+        Self::can_cast_from(syntax.kind()).then(|| Self { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+
+impl Visibility {
+    /// Whether the visibility is restricted to the package with `pub(package)`
+    pub fn is_package(&self) -> bool {
+        self.syntax.children_with_tokens().any(|child| child.kind() == SyntaxKind::PackageKeyword)
+    }
+}
+
+pub trait VisibilityOwner: AstNode {
+    fn visibility(&self) -> Option<Visibility> {
+        super::child(self)
+    }
+}
@@ -0,0 +1,205 @@
+use super::{SyntaxKind, SyntaxToken};
+use thiserror::Error;
+
+/// The type suffix of a number literal, e.g. the `u` in `3u` or the `f16` in `1.0f16`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LiteralSuffix {
+    Int { signed: bool, width: u8 },
+    Float { width: u8 },
+}
+
+impl LiteralSuffix {
+    /// Parses a suffix, the short forms `u`, `i` and `f` are 32 bit wide
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        let suffix = match suffix {
+            "u" => LiteralSuffix::Int { signed: false, width: 32 },
+            "i" => LiteralSuffix::Int { signed: true, width: 32 },
+            "f" => LiteralSuffix::Float { width: 32 },
+            "u8" => LiteralSuffix::Int { signed: false, width: 8 },
+            "u16" => LiteralSuffix::Int { signed: false, width: 16 },
+            "u32" => LiteralSuffix::Int { signed: false, width: 32 },
+            "u64" => LiteralSuffix::Int { signed: false, width: 64 },
+            "i8" => LiteralSuffix::Int { signed: true, width: 8 },
+            "i16" => LiteralSuffix::Int { signed: true, width: 16 },
+            "i32" => LiteralSuffix::Int { signed: true, width: 32 },
+            "i64" => LiteralSuffix::Int { signed: true, width: 64 },
+            "f16" => LiteralSuffix::Float { width: 16 },
+            "f32" => LiteralSuffix::Float { width: 32 },
+            "f64" => LiteralSuffix::Float { width: 64 },
+            _ => return None,
+        };
+
+        Some(suffix)
+    }
+
+    pub fn display(self) -> String {
+        match self {
+            LiteralSuffix::Int { signed: true, width } => format!("i{}", width),
+            LiteralSuffix::Int { signed: false, width } => format!("u{}", width),
+            LiteralSuffix::Float { width } => format!("f{}", width),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LiteralValue {
+    Boolean(bool),
+    Integer(u64),
+    Float(f64),
+}
+
+// literals are never NaN, so equality is total
+impl Eq for LiteralValue {}
+
+/// The decoded value of a literal token
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Literal {
+    pub value: LiteralValue,
+    pub suffix: Option<LiteralSuffix>,
+}
+
+#[derive(Error, Debug, Eq, PartialEq, Clone)]
+pub enum LiteralError {
+
+    #[error("Number literal has no digits")]
+    MissingDigits,
+
+    #[error("Exponent has no digits")]
+    EmptyExponent,
+
+    #[error("Invalid digit `{digit}` in a base {radix} literal")]
+    InvalidDigit { digit: char, radix: u32 },
+
+    #[error("Invalid suffix `{suffix}` for number literal")]
+    InvalidSuffix { suffix: String },
+
+    #[error("Literal out of range for `{ty}`")]
+    OutOfRange { ty: String },
+
+    #[error("Expected a literal")]
+    NotALiteral,
+}
+
+/// Decodes a `true`, `false`, integer or float literal token
+pub fn parse_literal(token: &SyntaxToken) -> Result<Literal, LiteralError> {
+    match token.kind() {
+        SyntaxKind::TrueKeyword => Ok(Literal { value: LiteralValue::Boolean(true), suffix: None }),
+        SyntaxKind::FalseKeyword => Ok(Literal { value: LiteralValue::Boolean(false), suffix: None }),
+        SyntaxKind::IntLiteral | SyntaxKind::FloatLiteral => parse_number_literal(token.text()),
+        _ => Err(LiteralError::NotALiteral),
+    }
+}
+
+/// Decodes number literals like `0xFF`, `1_000`, `1.5e-3` or `3u`
+pub fn parse_number_literal(text: &str) -> Result<Literal, LiteralError> {
+    let (radix, body) = match text.get(..2) {
+        Some("0x") | Some("0X") => (16, &text[2..]),
+        Some("0b") | Some("0B") => (2, &text[2..]),
+        _ => (10, text),
+    };
+
+    // binary literals take all decimal digits so `0b102` is reported as an invalid digit instead of a suffix
+    let digits_end = match radix {
+        10 => decimal_end(body)?,
+        16 => body.find(|c: char| !(c.is_ascii_hexdigit() || c == '_')).unwrap_or(body.len()),
+        _ => body.find(|c: char| !(c.is_ascii_digit() || c == '_')).unwrap_or(body.len()),
+    };
+
+    let (digits, suffix) = body.split_at(digits_end);
+    let digits = digits.replace('_', "");
+    if digits.is_empty() {
+        return Err(LiteralError::MissingDigits);
+    }
+
+    let suffix = match suffix {
+        "" => None,
+        suffix => Some(LiteralSuffix::from_suffix(suffix).ok_or_else(|| LiteralError::InvalidSuffix { suffix: suffix.to_string() })?),
+    };
+
+    let is_float = radix == 10 && digits.contains(&['.', 'e', 'E'][..]);
+    match suffix {
+        Some(suffix @ LiteralSuffix::Int { .. }) if is_float => {
+            return Err(LiteralError::InvalidSuffix { suffix: suffix.display() });
+        },
+        Some(suffix @ LiteralSuffix::Float { .. }) if radix != 10 => {
+            return Err(LiteralError::InvalidSuffix { suffix: suffix.display() });
+        },
+        _ => {},
+    }
+
+    if let Some(digit) = digits.chars().find(|c| c.to_digit(radix).is_none()).filter(|_| radix != 10) {
+        return Err(LiteralError::InvalidDigit { digit, radix });
+    }
+
+    if is_float || matches!(suffix, Some(LiteralSuffix::Float { .. })) {
+        let value: f64 = digits.parse().map_err(|_| LiteralError::MissingDigits)?;
+
+        // unsuffixed literals are checked against their type once it is inferred, like integers
+        let max = match suffix {
+            Some(LiteralSuffix::Float { width: 16 }) => 65504.0,
+            Some(LiteralSuffix::Float { width: 32 }) => f32::MAX as f64,
+            _ => f64::MAX,
+        };
+
+        if !value.is_finite() || value > max {
+            let ty = suffix.map(LiteralSuffix::display).unwrap_or_else(|| "f64".to_string());
+            return Err(LiteralError::OutOfRange { ty });
+        }
+
+        return Ok(Literal { value: LiteralValue::Float(value), suffix });
+    }
+
+    let out_of_range = || {
+        let ty = suffix.map(LiteralSuffix::display).unwrap_or_else(|| "u64".to_string());
+        LiteralError::OutOfRange { ty }
+    };
+
+    let value = u64::from_str_radix(&digits, radix).map_err(|_| out_of_range())?;
+
+    // signed literals may be one past the maximum so the minimum can be written negated, e.g. `-128i8`
+    let max = match suffix {
+        Some(LiteralSuffix::Int { signed: false, width }) if width < 64 => (1u64 << width) - 1,
+        Some(LiteralSuffix::Int { signed: true, width }) => 1u64 << (width - 1),
+        _ => u64::MAX,
+    };
+
+    if value > max {
+        return Err(out_of_range());
+    }
+
+    Ok(Literal { value: LiteralValue::Integer(value), suffix })
+}
+
+/// Finds the end of the digits, fraction and exponent of a decimal literal
+fn decimal_end(body: &str) -> Result<usize, LiteralError> {
+    let bytes = body.as_bytes();
+    let mut end = 0;
+
+    let skip_digits = |end: &mut usize| {
+        while *end < bytes.len() && (bytes[*end].is_ascii_digit() || bytes[*end] == b'_') {
+            *end += 1;
+        }
+    };
+
+    skip_digits(&mut end);
+
+    if end < bytes.len() && bytes[end] == b'.' {
+        end += 1;
+        skip_digits(&mut end);
+    }
+
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        end += 1;
+        if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+            end += 1;
+        }
+
+        let exponent_start = end;
+        skip_digits(&mut end);
+        if !body[exponent_start..end].contains(|c: char| c.is_ascii_digit()) {
+            return Err(LiteralError::EmptyExponent);
+        }
+    }
+
+    Ok(end)
+}
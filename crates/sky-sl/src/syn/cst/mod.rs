@@ -1,10 +1,12 @@
 use std::num::NonZeroU32;
 
 mod line_index;
+mod literal;
 mod syntax_kind;
 mod operator;
 
 pub use line_index::*;
+pub use literal::*;
 pub use syntax_kind::*;
 pub use operator::*;
 
@@ -53,6 +53,51 @@ pub enum BinaryOperator {
 }
 
 impl BinaryOperator {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "==" => Some(BinaryOperator::Equals),
+            "!=" => Some(BinaryOperator::NotEquals),
+            ">" => Some(BinaryOperator::GreaterThan),
+            ">=" => Some(BinaryOperator::GreatherOrEqualThan),
+            "<" => Some(BinaryOperator::LessThan),
+            "<=" => Some(BinaryOperator::LessOrEqualThan),
+            "+" => Some(BinaryOperator::Add),
+            "-" => Some(BinaryOperator::Subtract),
+            "*" => Some(BinaryOperator::Multiply),
+            "/" => Some(BinaryOperator::Divide),
+            "%" => Some(BinaryOperator::Remainder),
+            "&" => Some(BinaryOperator::BitwiseAnd),
+            "|" => Some(BinaryOperator::BitwiseOr),
+            "^" => Some(BinaryOperator::BitwiseXOr),
+            "<<" => Some(BinaryOperator::LeftShift),
+            ">>" => Some(BinaryOperator::RightShift),
+            "**" => Some(BinaryOperator::Exponent),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOperator::Equals => "==",
+            BinaryOperator::NotEquals => "!=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreatherOrEqualThan => ">=",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessOrEqualThan => "<=",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+            BinaryOperator::BitwiseAnd => "&",
+            BinaryOperator::BitwiseOr => "|",
+            BinaryOperator::BitwiseXOr => "^",
+            BinaryOperator::LeftShift => "<<",
+            BinaryOperator::RightShift => ">>",
+            BinaryOperator::Exponent => "**",
+        }
+    }
+
     pub fn precedence(&self) -> u8 {
         match self {
             BinaryOperator::Equals => 1,
@@ -113,6 +158,27 @@ pub enum UnaryOperator {
     FlipSign,
 }
 
+impl UnaryOperator {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "!" => Some(UnaryOperator::BooleanNot),
+            "~" => Some(UnaryOperator::BitwiseNot),
+            "+" => Some(UnaryOperator::KeepSign),
+            "-" => Some(UnaryOperator::FlipSign),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOperator::BooleanNot => "!",
+            UnaryOperator::BitwiseNot => "~",
+            UnaryOperator::KeepSign => "+",
+            UnaryOperator::FlipSign => "-",
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Associativity {
     Left,
@@ -17,6 +17,9 @@ pub enum SyntaxKind {
 
     Name,
 
+    /// The visibility of an item or struct member, e.g. `pub` or `pub(package)`
+    Visibility,
+
     /// A layout
     Layout,
 
@@ -32,6 +35,33 @@ pub enum SyntaxKind {
     /// A binding kind
     BindingKind,
 
+    /// The access mode of a storage binding, e.g. the `(read)` in `storage(read) particles: [Particle]`
+    AccessMode,
+
+    /// The descriptor set or bind group of a layout, e.g. the `(1)` in `layout(1) Scene { ... }`
+    SetIndex,
+
+    /// A constant e.g. `const MAX_LIGHTS: u32 = 16;` or `override(0) SHADOW_QUALITY: u32 = 2;`
+    Const,
+
+    /// The id of a pipeline overridable constant
+    OverrideId,
+
+    /// An enum e.g. `enum LightKind { Point, Spot = 4 }`
+    Enum,
+
+    /// An enum variant list
+    EnumVariantList,
+
+    /// A variant of an EnumVariantList
+    EnumVariant,
+
+    /// An explicit discriminant of an enum variant, e.g. the `= 4` in `Spot = 4`
+    Discriminant,
+
+    /// A type alias e.g. `type Color = vec4<f32>;`
+    TypeAlias,
+
     /// A struct
     Struct,
 
@@ -41,9 +71,24 @@ pub enum SyntaxKind {
     /// A struct member
     Member,
 
+    /// The location of a stage interface member, e.g. the `location(1)` in `location(1) color: vec3<f32>`
+    Location,
+
+    /// The interpolation of a stage interface member, e.g. `interpolate(flat)`
+    Interpolation,
+
+    /// An impl block e.g. `impl Light { fn new() -> Light { ... } }`
+    Impl,
+
+    /// The functions of an impl block
+    ImplItemList,
+
     /// A function
     Fn,
 
+    /// The shader stage of an entry point, e.g. `stage(fragment)`
+    Stage,
+
     /// A function signature e.g. `fn example(argument: ArgumentType) -> ReturnType`
     FnSignature,
 
@@ -62,6 +107,21 @@ pub enum SyntaxKind {
     /// A path segment e.g. `foo`
     PathSegment,
 
+    /// A list of generic arguments on a path segment e.g. the `<f32>` in `vec3<f32>`
+    GenericArgumentList,
+
+    /// A list of generic parameters on a function e.g. the `<T: float>` in `fn saturate<T: float>(x: T) -> T`
+    GenericParameterList,
+
+    /// A single generic parameter with optional bounds e.g. `T: float + vec3`
+    GenericParameter,
+
+    /// A bound restricting a generic parameter to a built-in type class e.g. `float`
+    TypeBound,
+
+    /// An array type, either fixed-size e.g. `[f32; 4]` or runtime-sized e.g. `[f32]`
+    ArrayType,
+
     /// Block
     Block,
 
@@ -71,6 +131,9 @@ pub enum SyntaxKind {
     /// A statement consisting of a single expression e.g. `my_fn();`
     ExpressionStatement,
 
+    /// An assignment to a place in memory e.g. `buffer.particles[i] = particle;`
+    AssignStatement,
+
     /// A grouping expression e.g. `(a + b)`
     GroupExpression,
 
@@ -80,6 +143,9 @@ pub enum SyntaxKind {
     /// A postfix field access expression e.g. `a.b`
     FieldAccessExpression,
 
+    /// A postfix method call expression e.g. `a.b(c)`
+    MethodCallExpression,
+
     /// A literal expression
     LiteralExpression,
 
@@ -89,7 +155,7 @@ pub enum SyntaxKind {
     // A unary expression with only one operand
     UnaryExpression,
 
-    /// A postfix call expression e.g. `a()` or `a.b()`
+    /// A postfix call expression e.g. `a()` or `a::b()`
     CallExpression,
 
     /// A list of expressions used in a call e.g. `(1.0, 2.0)`
@@ -107,6 +173,27 @@ pub enum SyntaxKind {
     /// A struct expression e.g. `MyStruct { a: a, b: b }`
     StructExpression,
 
+    /// A block used as an expression, e.g. the body of a match arm
+    BlockExpression,
+
+    /// A match expression e.g. `match kind { LightKind::Point => 1.0, _ => 0.0 }`
+    MatchExpression,
+
+    /// The list of arms of a match expression
+    MatchArmList,
+
+    /// A single match arm e.g. `1 | 2 => a`
+    MatchArm,
+
+    /// A literal pattern e.g. `1` or `-1`
+    LiteralPattern,
+
+    /// A pattern naming an enum variant e.g. `LightKind::Point`
+    PathPattern,
+
+    /// The wildcard pattern `_`
+    WildcardPattern,
+
     /// A list of initializers for a struct expression e.g. `{ a: 1.0, b: 2.0 }`
     StructExpressionFields,
 
@@ -176,6 +263,42 @@ pub enum SyntaxKind {
     /// the "sampler" keyword
     SamplerKeyword,
 
+    /// the "push_constant" keyword
+    PushConstantKeyword,
+
+    /// the "workgroup" keyword
+    WorkgroupKeyword,
+
+    /// the "const" keyword
+    ConstKeyword,
+
+    /// the "override" keyword
+    OverrideKeyword,
+
+    /// the "enum" keyword
+    EnumKeyword,
+
+    /// the "match" keyword
+    MatchKeyword,
+
+    /// the "impl" keyword
+    ImplKeyword,
+
+    /// the "pub" keyword
+    PubKeyword,
+
+    /// the "type" keyword
+    TypeKeyword,
+
+    /// the "stage" keyword
+    StageKeyword,
+
+    /// the "location" keyword
+    LocationKeyword,
+
+    /// the "interpolate" keyword
+    InterpolateKeyword,
+
     /// any type-identifier (TODO remove when we have paths)
     TypeIdentifier,
 
@@ -292,7 +415,9 @@ impl SyntaxKind {
         match self {
             StructKeyword | FnKeyword | UseKeyword | ModKeyword | LetKeyword | TrueKeyword
             | FalseKeyword | IfKeyword | ElseKeyword | LoopKeyword | WhileKeyword | ForKeyword
-            | LayoutKeyword | BindingKeyword | UniformKeyword | StorageKeyword | ImageKeyword | SamplerKeyword => true,
+            | LayoutKeyword | BindingKeyword | UniformKeyword | StorageKeyword | ImageKeyword | SamplerKeyword | PushConstantKeyword | WorkgroupKeyword
+            | ConstKeyword | OverrideKeyword | EnumKeyword | MatchKeyword | ImplKeyword | PubKeyword | TypeKeyword
+            | StageKeyword | LocationKeyword | InterpolateKeyword => true,
             _ => false,
         }
     }
@@ -378,6 +503,18 @@ impl SyntaxKind {
             "storage" => Some(StorageKeyword),
             "image" => Some(ImageKeyword),
             "sampler" => Some(SamplerKeyword),
+            "push_constant" => Some(PushConstantKeyword),
+            "workgroup" => Some(WorkgroupKeyword),
+            "const" => Some(ConstKeyword),
+            "override" => Some(OverrideKeyword),
+            "enum" => Some(EnumKeyword),
+            "match" => Some(MatchKeyword),
+            "impl" => Some(ImplKeyword),
+            "pub" => Some(PubKeyword),
+            "type" => Some(TypeKeyword),
+            "stage" => Some(StageKeyword),
+            "location" => Some(LocationKeyword),
+            "interpolate" => Some(InterpolateKeyword),
             _ => None,
         }
     }
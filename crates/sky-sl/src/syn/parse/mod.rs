@@ -30,6 +30,8 @@ macro_rules! t {
     ['}'] => {$crate::syn::cst::SyntaxKind::CloseBrace};
     ['('] => {$crate::syn::cst::SyntaxKind::OpenParen};
     [')'] => {$crate::syn::cst::SyntaxKind::CloseParen};
+    ['['] => {$crate::syn::cst::SyntaxKind::OpenBracket};
+    [']'] => {$crate::syn::cst::SyntaxKind::CloseBracket};
     [<] => {$crate::syn::cst::SyntaxKind::LessThan};
     [>] => {$crate::syn::cst::SyntaxKind::GreatherThan};
     [mod] => {$crate::syn::cst::SyntaxKind::ModKeyword};
@@ -55,6 +57,18 @@ macro_rules! t {
     [storage] => {$crate::syn::cst::SyntaxKind::StorageKeyword};
     [image] => {$crate::syn::cst::SyntaxKind::ImageKeyword};
     [sampler] => {$crate::syn::cst::SyntaxKind::SamplerKeyword};
+    [push_constant] => {$crate::syn::cst::SyntaxKind::PushConstantKeyword};
+    [workgroup] => {$crate::syn::cst::SyntaxKind::WorkgroupKeyword};
+    [const] => {$crate::syn::cst::SyntaxKind::ConstKeyword};
+    [override] => {$crate::syn::cst::SyntaxKind::OverrideKeyword};
+    [enum] => {$crate::syn::cst::SyntaxKind::EnumKeyword};
+    [match] => {$crate::syn::cst::SyntaxKind::MatchKeyword};
+    [impl] => {$crate::syn::cst::SyntaxKind::ImplKeyword};
+    [pub] => {$crate::syn::cst::SyntaxKind::PubKeyword};
+    [type] => {$crate::syn::cst::SyntaxKind::TypeKeyword};
+    [stage] => {$crate::syn::cst::SyntaxKind::StageKeyword};
+    [location] => {$crate::syn::cst::SyntaxKind::LocationKeyword};
+    [interpolate] => {$crate::syn::cst::SyntaxKind::InterpolateKeyword};
 }
 
 pub fn parse<'a>(token: &'a [Token], input: &'a str) -> ParseResult {
@@ -67,32 +81,101 @@ pub fn parse<'a>(token: &'a [Token], input: &'a str) -> ParseResult {
 fn parse_module(parser: &mut Parser) {
     parser.node(SyntaxKind::Module, |parser| {
         while !parser.eof() {
-            match parser.current() {
+            match peek_item_keyword(parser) {
                 t if t.is_whitespace() => {
                     parser.consume(SyntaxKind::Whitespace);
                 }
                 SyntaxKind::ModKeyword => parse_module_declaration(parser),
                 SyntaxKind::UseKeyword => parse_use_declaration(parser),
                 SyntaxKind::StructKeyword => parse_struct_declaration(parser),
-                SyntaxKind::FnKeyword => parse_function_declaration(parser),
+                SyntaxKind::EnumKeyword => parse_enum_declaration(parser),
+                SyntaxKind::ImplKeyword => parse_impl_declaration(parser),
+                SyntaxKind::FnKeyword | SyntaxKind::StageKeyword => parse_function_declaration(parser),
                 SyntaxKind::LayoutKeyword => parse_layout_declaration(parser),
-                _ => parser.skip(&[t![mod], t![use], t![fn], t![struct], t![layout]]),
+                SyntaxKind::ConstKeyword | SyntaxKind::OverrideKeyword => parse_const_declaration(parser),
+                SyntaxKind::TypeKeyword => parse_type_alias_declaration(parser),
+                _ => parser.skip(&[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]),
             }
         }
     });
 }
 
+/// The keyword of the item at the current token, looking past its visibility for items that can have one.
+/// A visibility in front of anything else is returned as is, so it gets skipped.
+fn peek_item_keyword(parser: &Parser) -> SyntaxKind {
+    if !parser.is_at(t![pub]) {
+        return parser.current();
+    }
+
+    let mut n = 1;
+    let skip_whitespace = |n: &mut usize| {
+        while parser.nth(*n).map(|kind| kind.is_whitespace()).unwrap_or(false) {
+            *n += 1;
+        }
+    };
+
+    skip_whitespace(&mut n);
+    if parser.nth(n) == Some(t!['(']) {
+        while !matches!(parser.nth(n), Some(t![')']) | None) {
+            n += 1;
+        }
+
+        n += 1;
+        skip_whitespace(&mut n);
+    }
+
+    match parser.nth(n) {
+        Some(kind @ (t![fn] | t![struct] | t![enum] | t![layout] | t![const] | t![override] | t![type] | t![stage])) => kind,
+        _ => parser.current(),
+    }
+}
+
+/// parses an optional visibility, e.g. `pub` or `pub(package)`
+fn parse_visibility(parser: &mut Parser) {
+    if !parser.is_at(t![pub]) {
+        return;
+    }
+
+    parser.node(SyntaxKind::Visibility, |parser| {
+        parser.consume(t![pub]);
+        parser.ws0();
+
+        if parser.consume_if(t!['(']) {
+            parser.ws0();
+            parser.expect(t![package], &[t![')'], t![ident], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+            parser.ws0();
+            parser.expect(t![')'], &[t![ident], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+        }
+    });
+    parser.ws0();
+}
+
 fn parse_layout_declaration(parser: &mut Parser) {
     parser.node(SyntaxKind::Layout, |parser| {
+        parse_visibility(parser);
+
         // parse layout keyword
         parser.consume(t![layout]);
         parser.ws0();
 
+        // parse the optional set index
+        if parser.is_at(t!['(']) {
+            parser.begin_node(SyntaxKind::SetIndex);
+            parser.consume(t!['(']);
+            parser.ws0();
+
+            parse_expression(parser);
+
+            parser.expect(t![')'], &[t![ident], t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+            parser.end_node();
+            parser.ws0();
+        }
+
         // parse the struct name
         parser.begin_node(SyntaxKind::Name);
         parser.expect(
             t![ident],
-            &[t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout]],
+            &[t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
         );
         parser.end_node();
         parser.ws0();
@@ -100,7 +183,7 @@ fn parse_layout_declaration(parser: &mut Parser) {
         // parse open brace
         parser.expect(
             SyntaxKind::OpenBrace,
-            &[SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout]],
+            &[SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
         );
         parser.ws0();
 
@@ -109,7 +192,7 @@ fn parse_layout_declaration(parser: &mut Parser) {
         // parse close brace
         parser.expect(
             SyntaxKind::CloseBrace,
-            &[t![mod], t![use], t![fn], t![struct], t![layout]],
+            &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
         );
     });
 }
@@ -117,30 +200,37 @@ fn parse_layout_declaration(parser: &mut Parser) {
 fn parse_layout_member_list(parser: &mut Parser) {
     parser.node(SyntaxKind::LayoutMemberList, |parser| {
         loop {
-            if !parser.is_at(SyntaxKind::BindingKeyword) {
+            if !parser.is_at_any(&[SyntaxKind::BindingKeyword, t![uniform], t![storage], t![image], t![sampler], t![push_constant], t![workgroup]]) {
                 break;
             }
 
             parser.begin_node(SyntaxKind::LayoutMember);
 
-            parser.consume(SyntaxKind::BindingKeyword);
-            parser.ws0();
+            // the binding index is assigned automatically if omitted
+            if parser.consume_if(SyntaxKind::BindingKeyword) {
+                parser.ws0();
 
-            parser.begin_node(SyntaxKind::BindingIndex);
-            parser.expect(SyntaxKind::OpenParen, &[SyntaxKind::CloseParen, SyntaxKind::IntLiteral, t![mod], t![use], t![fn], t![struct], t![layout]]);
-            parser.ws0();
+                parser.begin_node(SyntaxKind::BindingIndex);
+                parser.expect(SyntaxKind::OpenParen, &[SyntaxKind::CloseParen, SyntaxKind::IntLiteral, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+                parser.ws0();
 
-            parse_expression(parser);
-            
-            parser.expect(SyntaxKind::CloseParen, &[t![mod], t![use], t![fn], t![struct], t![layout]]);
-            parser.end_node();
-            parser.ws0();
+                parse_expression(parser);
+
+                parser.expect(SyntaxKind::CloseParen, &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+                parser.end_node();
+                parser.ws0();
+            }
 
             parser.begin_node(SyntaxKind::BindingKind);
-            parser.expect_any(&[t![uniform], t![storage], t![image], t![sampler]], &[t![mod], t![use], t![fn], t![struct], t![layout]]);
+            parser.expect_any(&[t![uniform], t![storage], t![image], t![sampler], t![push_constant], t![workgroup]], &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
             parser.ws0();
             parser.end_node();
 
+            // the access mode of storage bindings, read and write if omitted
+            if parser.is_at(t!['(']) {
+                parse_access_mode(parser);
+            }
+
             // parse the member name
             parser.begin_node(SyntaxKind::Name);
             parser.expect(
@@ -154,6 +244,13 @@ fn parse_layout_member_list(parser: &mut Parser) {
                     t![fn],
                     t![struct],
                     t![layout],
+                    t![const],
+                    t![override],
+                    t![enum],
+                    t![impl],
+                    t![pub],
+                    t![type],
+                    t![stage],
                 ],
             );
             parser.end_node();
@@ -170,12 +267,19 @@ fn parse_layout_member_list(parser: &mut Parser) {
                     t![fn],
                     t![struct],
                     t![layout],
+                    t![const],
+                    t![override],
+                    t![enum],
+                    t![impl],
+                    t![pub],
+                    t![type],
+                    t![stage],
                 ],
             );
             parser.ws0();
 
             // parse the type
-            parse_item_path(parser);
+            parse_type(parser);
             parser.ws0();
 
             parser.end_node();
@@ -192,19 +296,99 @@ fn parse_layout_member_list(parser: &mut Parser) {
     });
 }
 
+/// parses the access mode of a storage binding, e.g. `(read)`
+fn parse_access_mode(parser: &mut Parser) {
+    parser.node(SyntaxKind::AccessMode, |parser| {
+        parser.consume(t!['(']);
+        parser.ws0();
+        parser.expect(t![ident], &[t![')'], t![:], t![,], t!['}']]);
+        parser.ws0();
+        parser.expect(t![')'], &[t![ident], t![:], t![,], t!['}']]);
+    });
+    parser.ws0();
+}
+
 /// parses a module declaration, e.g. `mod module_name;`
+/// parses a constant, e.g. `const MAX_LIGHTS: u32 = 16;`
+fn parse_const_declaration(parser: &mut Parser) {
+    parser.node(SyntaxKind::Const, |parser| {
+        parse_visibility(parser);
+
+        if parser.consume_if(t![override]) {
+            parser.ws0();
+
+            parser.begin_node(SyntaxKind::OverrideId);
+            parser.expect(t!['('], &[t![')'], t![ident], t![:], t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+            parser.ws0();
+
+            parse_expression(parser);
+
+            parser.expect(t![')'], &[t![ident], t![:], t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+            parser.end_node();
+            parser.ws0();
+        } else {
+            parser.consume(t![const]);
+            parser.ws1();
+        }
+
+        parser.begin_node(SyntaxKind::Name);
+        parser.expect(t![ident], &[t![:], t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+        parser.end_node();
+        parser.ws0();
+
+        parser.expect(t![:], &[t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+        parser.ws0();
+
+        parser.begin_node(SyntaxKind::TypeIdentifier);
+        parse_type(parser);
+        parser.end_node();
+        parser.ws0();
+
+        parser.expect(t![=], &[t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+        parser.ws0();
+
+        parse_expression(parser);
+        parser.ws0();
+
+        parser.expect(t![;], &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    });
+}
+
+/// parses a type alias, e.g. `type Color = vec4<f32>;`
+fn parse_type_alias_declaration(parser: &mut Parser) {
+    parser.node(SyntaxKind::TypeAlias, |parser| {
+        parse_visibility(parser);
+
+        parser.consume(t![type]);
+        parser.ws1();
+
+        parser.begin_node(SyntaxKind::Name);
+        parser.expect(t![ident], &[t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+        parser.end_node();
+        parser.ws0();
+
+        parser.expect(t![=], &[t![ident], t!['['], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+        parser.ws0();
+
+        parse_type(parser);
+        parser.ws0();
+
+        parser.expect(t![;], &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    });
+}
+
 fn parse_module_declaration(parser: &mut Parser) {
     parser.node(SyntaxKind::ModuleDeclaration, |parser| {
         parser.consume(t![mod]);
         parser.ws1();
 
         parser.begin_node(SyntaxKind::Name);
-        parser.expect(t![ident], &[t![;], t![mod], t![use], t![fn], t![struct], t![layout]]);
+        parser.expect(t![ident], &[t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
         parser.end_node();
 
         parser.ws0();
 
-        parser.expect(t![;], &[t![mod], t![use], t![fn], t![struct], t![layout]]);
+        parser.expect(t![;], &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     });
 }
 
@@ -216,7 +400,7 @@ fn parse_use_declaration(parser: &mut Parser) {
 
         parse_use_tree(parser);
 
-        parser.expect(t![;], &[t![mod], t![use], t![fn], t![struct], t![layout]]);
+        parser.expect(t![;], &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     });
 }
 
@@ -238,6 +422,13 @@ fn parse_use_tree(parser: &mut Parser) {
                 t![fn],
                 t![struct],
                 t![layout],
+                t![const],
+                t![override],
+                t![enum],
+                t![impl],
+                t![pub],
+                t![type],
+                t![stage],
             ],
         );
         parser.expect(
@@ -251,6 +442,13 @@ fn parse_use_tree(parser: &mut Parser) {
                 t![fn],
                 t![struct],
                 t![layout],
+                t![const],
+                t![override],
+                t![enum],
+                t![impl],
+                t![pub],
+                t![type],
+                t![stage],
             ],
         );
 
@@ -293,7 +491,7 @@ fn parse_use_group(parser: &mut Parser) {
 
     parser.expect(
         t!['}'],
-        &[t![:], t![;], t![mod], t![use], t![fn], t![struct], t![layout]],
+        &[t![:], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
     );
     parser.end_node();
 }
@@ -314,6 +512,13 @@ fn parse_use_segment(parser: &mut Parser) {
             t![fn],
             t![struct],
             t![layout],
+            t![const],
+            t![override],
+            t![enum],
+            t![impl],
+            t![pub],
+            t![type],
+            t![stage],
         ],
     );
     parser.end_node();
@@ -322,6 +527,8 @@ fn parse_use_segment(parser: &mut Parser) {
 /// parses an entire struct declaration, e.g. `struct MyStruct { member: MemberType, }`
 fn parse_struct_declaration(parser: &mut Parser) {
     parser.node(SyntaxKind::Struct, |parser| {
+        parse_visibility(parser);
+
         // parse struct keyword
         parser.consume(t![struct]);
         parser.ws0();
@@ -330,7 +537,7 @@ fn parse_struct_declaration(parser: &mut Parser) {
         parser.begin_node(SyntaxKind::Name);
         parser.expect(
             t![ident],
-            &[t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout]],
+            &[t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
         );
         parser.end_node();
         parser.ws0();
@@ -338,7 +545,7 @@ fn parse_struct_declaration(parser: &mut Parser) {
         // parse open brace
         parser.expect(
             SyntaxKind::OpenBrace,
-            &[SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout]],
+            &[SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
         );
         parser.ws0();
 
@@ -347,7 +554,7 @@ fn parse_struct_declaration(parser: &mut Parser) {
         // parse close brace
         parser.expect(
             SyntaxKind::CloseBrace,
-            &[t![mod], t![use], t![fn], t![struct], t![layout]],
+            &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
         );
     });
 }
@@ -356,11 +563,21 @@ fn parse_struct_declaration(parser: &mut Parser) {
 fn parse_struct_member_list(parser: &mut Parser) {
     parser.node(SyntaxKind::MemberList, |parser| {
         loop {
-            if !parser.is_at(SyntaxKind::Identifier) {
+            if !parser.is_at_any(&[SyntaxKind::Identifier, t![pub], t![location], t![interpolate]]) {
                 break;
             }
 
             parser.begin_node(SyntaxKind::Member);
+            parse_visibility(parser);
+
+            // the location and interpolation of members passed between stages
+            if parser.is_at(t![location]) {
+                parse_location(parser);
+            }
+
+            if parser.is_at(t![interpolate]) {
+                parse_interpolation(parser);
+            }
 
             // parse the member name
             parser.begin_node(SyntaxKind::Name);
@@ -375,6 +592,13 @@ fn parse_struct_member_list(parser: &mut Parser) {
                     t![fn],
                     t![struct],
                     t![layout],
+                    t![const],
+                    t![override],
+                    t![enum],
+                    t![impl],
+                    t![pub],
+                    t![type],
+                    t![stage],
                 ],
             );
             parser.end_node();
@@ -391,12 +615,19 @@ fn parse_struct_member_list(parser: &mut Parser) {
                     t![fn],
                     t![struct],
                     t![layout],
+                    t![const],
+                    t![override],
+                    t![enum],
+                    t![impl],
+                    t![pub],
+                    t![type],
+                    t![stage],
                 ],
             );
             parser.ws0();
 
             // parse the type
-            parse_item_path(parser);
+            parse_type(parser);
             parser.ws0();
 
             parser.end_node();
@@ -413,25 +644,208 @@ fn parse_struct_member_list(parser: &mut Parser) {
     });
 }
 
-/// parses an item path that targets a singular item, e.g. `foo::bar::Baz`
+/// parses the location of a struct member, e.g. `location(1)`
+fn parse_location(parser: &mut Parser) {
+    parser.node(SyntaxKind::Location, |parser| {
+        parser.consume(t![location]);
+        parser.ws0();
+
+        parser.expect(t!['('], &[t![')'], t![int], t![ident], t![:], t![,], t!['}']]);
+        parser.ws0();
+
+        parse_expression(parser);
+
+        parser.expect(t![')'], &[t![interpolate], t![ident], t![:], t![,], t!['}']]);
+    });
+    parser.ws0();
+}
+
+/// parses the interpolation of a struct member, e.g. `interpolate(flat)`
+fn parse_interpolation(parser: &mut Parser) {
+    parser.node(SyntaxKind::Interpolation, |parser| {
+        parser.consume(t![interpolate]);
+        parser.ws0();
+
+        parser.expect(t!['('], &[t![')'], t![ident], t![:], t![,], t!['}']]);
+        parser.ws0();
+        parser.expect(t![ident], &[t![')'], t![:], t![,], t!['}']]);
+        parser.ws0();
+        parser.expect(t![')'], &[t![ident], t![:], t![,], t!['}']]);
+    });
+    parser.ws0();
+}
+
+/// parses an entire enum declaration, e.g. `enum LightKind { Point, Spot = 4 }`
+fn parse_enum_declaration(parser: &mut Parser) {
+    parser.node(SyntaxKind::Enum, |parser| {
+        parse_visibility(parser);
+
+        parser.consume(t![enum]);
+        parser.ws0();
+
+        parser.begin_node(SyntaxKind::Name);
+        parser.expect(
+            t![ident],
+            &[t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+        );
+        parser.end_node();
+        parser.ws0();
+
+        parser.expect(
+            SyntaxKind::OpenBrace,
+            &[SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+        );
+        parser.ws0();
+
+        parse_enum_variant_list(parser);
+
+        parser.expect(
+            SyntaxKind::CloseBrace,
+            &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+        );
+    });
+}
+
+/// parses enum variants repeatedly, e.g. `Point, Spot = 4,`
+fn parse_enum_variant_list(parser: &mut Parser) {
+    parser.node(SyntaxKind::EnumVariantList, |parser| {
+        while parser.is_at(t![ident]) {
+            parser.begin_node(SyntaxKind::EnumVariant);
+
+            parser.begin_node(SyntaxKind::Name);
+            parser.consume(t![ident]);
+            parser.end_node();
+            parser.ws0();
+
+            if parser.is_at(t![=]) {
+                parser.begin_node(SyntaxKind::Discriminant);
+                parser.consume(t![=]);
+                parser.ws0();
+
+                parse_expression(parser);
+                parser.end_node();
+            }
+
+            parser.end_node();
+
+            if !parser.consume_if(t![,]) {
+                break;
+            }
+
+            parser.ws0();
+        }
+
+        parser.ws0();
+    });
+}
+
+/// parses an impl block, e.g. `impl Light { fn new() -> Light { ... } }`
+fn parse_impl_declaration(parser: &mut Parser) {
+    parser.node(SyntaxKind::Impl, |parser| {
+        parser.consume(t![impl]);
+        parser.ws0();
+
+        // parse the name of the implemented type
+        parser.begin_node(SyntaxKind::Name);
+        parser.expect(
+            t![ident],
+            &[t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+        );
+        parser.end_node();
+        parser.ws0();
+
+        parser.expect(
+            SyntaxKind::OpenBrace,
+            &[SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+        );
+        parser.ws0();
+
+        parse_impl_item_list(parser);
+
+        parser.expect(
+            SyntaxKind::CloseBrace,
+            &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+        );
+    });
+}
+
+/// parses the functions of an impl block repeatedly
+fn parse_impl_item_list(parser: &mut Parser) {
+    parser.node(SyntaxKind::ImplItemList, |parser| {
+        while peek_item_keyword(parser) == t![fn] {
+            parse_function_declaration(parser);
+            parser.ws0();
+        }
+    });
+}
+
+/// parses a type, e.g. `foo::Bar`, `vec3<f32>`, `[f32; 4]` or `[Particle]`
+fn parse_type(parser: &mut Parser) {
+    if parser.is_at(t!['[']) {
+        parse_array_type(parser);
+    } else {
+        parse_item_path(parser);
+    }
+}
+
+/// parses an array type, e.g. `[f32; 4]` or the runtime-sized `[f32]`
+fn parse_array_type(parser: &mut Parser) {
+    parser.begin_node(SyntaxKind::ArrayType);
+    parser.consume(t!['[']);
+    parser.ws0();
+
+    parse_type(parser);
+    parser.ws0();
+
+    if parser.consume_if(t![;]) {
+        parser.ws0();
+        parse_expression(parser);
+        parser.ws0();
+    }
+
+    parser.expect(
+        t![']'],
+        &[t![,], t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+    );
+    parser.end_node();
+}
+
+/// parses an item path that targets a singular item, e.g. `foo::bar::Baz` or `vec3<f32>`
 fn parse_item_path(parser: &mut Parser) {
     parser.begin_node(SyntaxKind::Path);
-    parse_item_path_segment(parser);
+    parse_item_path_segment(parser, true);
+    parser.end_node();
+}
+
+/// parses a path used as an expression, e.g. `foo::bar`
+///
+/// Since `a < b` would be ambiguous, generic arguments are only parsed if they are followed by call arguments,
+/// e.g. `vec3<f32>(1.0, 2.0, 3.0)`.
+fn parse_path_expression(parser: &mut Parser) {
+    parser.begin_node(SyntaxKind::PathExpression);
+    parser.begin_node(SyntaxKind::Path);
+    parse_item_path_segment(parser, false);
+    parser.end_node();
     parser.end_node();
 }
 
 /// recursively parses path segments, e.g. `foo::bar::Baz`
-fn parse_item_path_segment(parser: &mut Parser) {
+fn parse_item_path_segment(parser: &mut Parser, generics: bool) {
     parser.begin_node(SyntaxKind::PathSegment);
 
     parser.begin_node(SyntaxKind::Name);
     parser.expect(
         t![ident],
-        &[t![:], t![,], t![;], t![mod], t![use], t![fn], t![struct], t![layout]],
+        &[t![:], t![,], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
     );
     parser.end_node();
     parser.ws0();
 
+    if parser.is_at(t![<]) && (generics || is_at_generic_call(parser)) {
+        parse_generic_argument_list(parser);
+        parser.ws0();
+    }
+
     if parser.consume_if(t![:]) {
         parser.expect(
             t![:],
@@ -444,21 +858,104 @@ fn parse_item_path_segment(parser: &mut Parser) {
                 t![fn],
                 t![struct],
                 t![layout],
+                t![const],
+                t![override],
+                t![enum],
+                t![impl],
+                t![pub],
+                t![type],
+                t![stage],
             ],
         );
         parser.ws0();
 
-        parse_item_path_segment(parser);
+        parse_item_path_segment(parser, generics);
+    }
+
+    parser.end_node();
+}
+
+/// whether the current `<` starts generic arguments followed by call arguments, like the `<f32>` in `vec3<f32>(x)`.
+/// Only paths may appear between the brackets, so `a < b + c` is always a comparison
+fn is_at_generic_call(parser: &Parser) -> bool {
+    let mut depth = 0;
+    let mut n = 0;
+    while let Some(kind) = parser.nth(n) {
+        match kind {
+            t![<] => depth += 1,
+            t![>] => {
+                depth -= 1;
+                if depth == 0 {
+                    let next = match parser.nth(n + 1) {
+                        Some(SyntaxKind::Whitespace) => parser.nth(n + 2),
+                        next => next,
+                    };
+                    return next == Some(SyntaxKind::OpenParen);
+                }
+            },
+            t![ident] | t![:] | t![,] | SyntaxKind::Whitespace => {},
+            _ => return false,
+        }
+        n += 1;
+    }
+
+    false
+}
+
+/// parses a list of generic arguments, e.g. `<f32>`
+fn parse_generic_argument_list(parser: &mut Parser) {
+    parser.begin_node(SyntaxKind::GenericArgumentList);
+    parser.consume(t![<]);
+    parser.ws0();
+
+    loop {
+        if !parser.is_at(t![ident]) {
+            break;
+        }
+
+        parse_item_path(parser);
+        parser.ws0();
+
+        if !parser.consume_if(t![,]) {
+            break;
+        }
+
+        parser.ws0();
     }
 
+    parser.expect(
+        t![>],
+        &[t![,], t![;], t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+    );
     parser.end_node();
 }
 
 /// parse a function declaration, e.g. `fn my_function() {}`
+fn parse_stage(parser: &mut Parser) {
+    parser.node(SyntaxKind::Stage, |parser| {
+        parser.consume(t![stage]);
+        parser.ws0();
+
+        parser.expect(t!['('], &[t![')'], t![ident], t![fn], t!['{'], t!['}']]);
+        parser.ws0();
+        parser.expect(t![ident], &[t![')'], t![fn], t!['{'], t!['}']]);
+        parser.ws0();
+        parser.expect(t![')'], &[t![fn], t!['{'], t!['}']]);
+    });
+    parser.ws0();
+}
+
 fn parse_function_declaration(parser: &mut Parser) {
     parser.begin_node(SyntaxKind::Fn);
-    // parse fn keyword
-    parser.consume(t![fn]);
+    parse_visibility(parser);
+
+    // parse the optional shader stage of an entry point
+    if parser.is_at(t![stage]) {
+        parse_stage(parser);
+    }
+
+    // parse fn keyword, which may be missing after a stage
+    parser.expect(t![fn], &[t![ident], t!['('], t!['{'], t![mod], t![use], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.ws0();
 
     parser.begin_node(SyntaxKind::FnSignature);
@@ -479,11 +976,24 @@ fn parse_function_declaration(parser: &mut Parser) {
             t![fn],
             t![struct],
             t![layout],
+            t![const],
+            t![override],
+            t![enum],
+            t![impl],
+            t![pub],
+            t![type],
+            t![stage],
         ],
     );
     parser.end_node();
     parser.ws0();
 
+    // parse optional generic parameters
+    if parser.is_at(t![<]) {
+        parse_generic_parameter_list(parser);
+        parser.ws0();
+    }
+
     parser.expect(
         SyntaxKind::OpenParen,
         &[
@@ -498,6 +1008,13 @@ fn parse_function_declaration(parser: &mut Parser) {
             t![fn],
             t![struct],
             t![layout],
+            t![const],
+            t![override],
+            t![enum],
+            t![impl],
+            t![pub],
+            t![type],
+            t![stage],
         ],
     );
     parser.ws0();
@@ -518,6 +1035,13 @@ fn parse_function_declaration(parser: &mut Parser) {
             t![fn],
             t![struct],
             t![layout],
+            t![const],
+            t![override],
+            t![enum],
+            t![impl],
+            t![pub],
+            t![type],
+            t![stage],
         ],
     );
     parser.ws0();
@@ -538,11 +1062,18 @@ fn parse_function_declaration(parser: &mut Parser) {
                 t![fn],
                 t![struct],
                 t![layout],
+                t![const],
+                t![override],
+                t![enum],
+                t![impl],
+                t![pub],
+                t![type],
+                t![stage],
             ],
         );
         parser.ws0();
 
-        parse_item_path(parser);
+        parse_type(parser);
         parser.end_node();
         parser.ws0();
     }
@@ -554,6 +1085,67 @@ fn parse_function_declaration(parser: &mut Parser) {
     parser.end_node();
 }
 
+/// parses a list of generic parameters, e.g. `<T: float, U>`
+fn parse_generic_parameter_list(parser: &mut Parser) {
+    parser.begin_node(SyntaxKind::GenericParameterList);
+    parser.consume(t![<]);
+    parser.ws0();
+
+    loop {
+        if !parser.is_at(t![ident]) {
+            break;
+        }
+
+        parse_generic_parameter(parser);
+        parser.ws0();
+
+        if !parser.consume_if(t![,]) {
+            break;
+        }
+
+        parser.ws0();
+    }
+
+    parser.expect(
+        t![>],
+        &[t!['('], t![')'], t!['{'], t!['}'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+    );
+    parser.end_node();
+}
+
+/// parses a single generic parameter, e.g. `T: float + vec3`
+fn parse_generic_parameter(parser: &mut Parser) {
+    parser.begin_node(SyntaxKind::GenericParameter);
+    parser.begin_node(SyntaxKind::Name);
+    parser.consume(t![ident]);
+    parser.end_node();
+    parser.ws0();
+
+    if parser.consume_if(t![:]) {
+        parser.ws0();
+
+        loop {
+            parser.begin_node(SyntaxKind::TypeBound);
+            parser.begin_node(SyntaxKind::Name);
+            parser.expect(
+                t![ident],
+                &[t![+], t![,], t![>], t!['('], t!['{'], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]],
+            );
+            parser.end_node();
+            parser.end_node();
+            parser.ws0();
+
+            if !parser.consume_if(t![+]) {
+                break;
+            }
+
+            parser.ws0();
+        }
+    }
+
+    parser.end_node();
+}
+
 /// parses a list of arguments, e.g. `(foo: Bar)`
 fn parse_argument_list(parser: &mut Parser) {
     parser.begin_node(SyntaxKind::ArgumentList);
@@ -577,15 +1169,22 @@ fn parse_argument_list(parser: &mut Parser) {
     parser.end_node();
 }
 
-/// parses a single argument, e.g. `foo: bar::Bar`
+/// parses a single argument, e.g. `foo: bar::Bar` or the receiver `self` of a method
 fn parse_argument(parser: &mut Parser) {
     parser.begin_node(SyntaxKind::Argument);
+    let is_receiver = parser.current_text() == "self";
     parser.begin_node(SyntaxKind::Name);
     // consume the identifier
     parser.consume(SyntaxKind::Identifier);
     parser.end_node();
     parser.ws0();
 
+    // the receiver takes the implemented type
+    if is_receiver && !parser.is_at(t![:]) {
+        parser.end_node();
+        return;
+    }
+
     parser.expect(
         t![:],
         &[
@@ -601,11 +1200,18 @@ fn parse_argument(parser: &mut Parser) {
             t![fn],
             t![struct],
             t![layout],
+            t![const],
+            t![override],
+            t![enum],
+            t![impl],
+            t![pub],
+            t![type],
+            t![stage],
         ],
     );
     parser.ws0();
 
-    parse_item_path(parser);
+    parse_type(parser);
     parser.end_node();
 }
 
@@ -617,7 +1223,7 @@ fn parse_block(parser: &mut Parser) {
     parse_statements(parser);
     parser.ws0();
 
-    parser.expect(t!['}'], &[t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.expect(t!['}'], &[t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.end_node();
 }
 
@@ -631,8 +1237,8 @@ fn parse_statements(parser: &mut Parser) {
             },
     
             // parse an expression statement
-            // if and loop expression start
-            t![if] | t![loop] | t![while] | t![for] |
+            // if, match and loop expression start
+            t![if] | t![match] | t![loop] | t![while] | t![for] |
             // group expression start
             SyntaxKind::OpenParen |
             // primary expression start
@@ -659,7 +1265,7 @@ fn parse_let_statement(parser: &mut Parser) {
     parser.ws1();
 
     parser.begin_node(SyntaxKind::Name);
-    parser.expect(t![ident], &[t![:], t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.expect(t![ident], &[t![:], t![=], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.end_node();
     parser.ws0();
 
@@ -667,33 +1273,55 @@ fn parse_let_statement(parser: &mut Parser) {
         parser.ws0();
 
         parser.begin_node(SyntaxKind::TypeIdentifier);
-        parse_item_path(parser);
+        parse_type(parser);
         parser.end_node();
         parser.ws0();
     }
 
-    parser.expect(t![=], &[SyntaxKind::CloseBrace, t![;], t![let], t![if], t![loop], t![while], t![for], t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.expect(t![=], &[SyntaxKind::CloseBrace, t![;], t![let], t![if], t![loop], t![while], t![for], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.ws0();
 
     parse_expression(parser);
     parser.ws0();
 
-    parser.expect(t![;], &[SyntaxKind::CloseBrace, t![let], t![if], t![loop], t![while], t![for], t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.expect(t![;], &[SyntaxKind::CloseBrace, t![let], t![if], t![loop], t![while], t![for], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.end_node();
 }
 
 fn parse_expression_statement(parser: &mut Parser) -> bool {
-    parser.begin_node(SyntaxKind::ExpressionStatement);
+    let checkpoint = parser.checkpoint();
     parse_expression(parser);
     parser.ws0();
+
+    // a single `=` after the expression turns it into the place of an assignment, `==` is parsed as a binary operator
+    if parser.is_at(t![=]) {
+        parse_assign_statement(parser, checkpoint);
+        return true;
+    }
+
+    parser.begin_node_at(checkpoint, SyntaxKind::ExpressionStatement);
     let has_semicolon = parser.consume_if(t![;]);
     parser.end_node();
     has_semicolon
 }
 
+/// parses the rest of an assignment after its place, e.g. the `= particle;` in `buffer.particles[i] = particle;`
+fn parse_assign_statement(parser: &mut Parser, checkpoint: Checkpoint) {
+    parser.begin_node_at(checkpoint, SyntaxKind::AssignStatement);
+    parser.consume(t![=]);
+    parser.ws0();
+
+    parse_expression(parser);
+    parser.ws0();
+
+    parser.expect(t![;], &[SyntaxKind::CloseBrace, t![let], t![if], t![loop], t![while], t![for], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    parser.end_node();
+}
+
 fn parse_expression(parser: &mut Parser) {
     match parser.current() {
         t![if] => parse_if_expression(parser),
+        t![match] => parse_match_expression(parser),
         t![loop] => parse_unbounded_loop_expression(parser),
         t![while] => parse_predicate_loop_expression(parser),
         t![for] => parse_iterator_loop_expression(parser),
@@ -735,6 +1363,102 @@ fn parse_if_expression(parser: &mut Parser) {
     // "else" expression block
 }
 
+/// parses a match expression, e.g. `match kind { LightKind::Point => 1.0, _ => 0.0 }`
+fn parse_match_expression(parser: &mut Parser) {
+    parser.begin_node(SyntaxKind::MatchExpression);
+    parser.consume(t![match]);
+    parser.ws0();
+
+    parse_expression(parser);
+    parser.ws0();
+
+    parser.expect(t!['{'], &[t!['}'], t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    parser.ws0();
+
+    parser.begin_node(SyntaxKind::MatchArmList);
+    while is_at_pattern_start(parser) {
+        let has_block = parse_match_arm(parser);
+        parser.ws0();
+
+        // arms ending in a block don't need a comma
+        if !parser.consume_if(t![,]) && !has_block {
+            break;
+        }
+
+        parser.ws0();
+    }
+    parser.end_node();
+
+    parser.expect(t!['}'], &[t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    parser.end_node();
+}
+
+/// parses a single match arm, e.g. `1 | 2 => a`, returns true if the arm ends in a block
+fn parse_match_arm(parser: &mut Parser) -> bool {
+    parser.begin_node(SyntaxKind::MatchArm);
+
+    parse_pattern(parser);
+    parser.ws0();
+
+    while parser.consume_if(t![|]) {
+        parser.ws0();
+        parse_pattern(parser);
+        parser.ws0();
+    }
+
+    parser.expect(t![=], &[t![>], t!['{'], t!['}'], t![,], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    parser.expect(t![>], &[t!['{'], t!['}'], t![,], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    parser.ws0();
+
+    let has_block = parser.is_at(t!['{']);
+    if has_block {
+        parser.begin_node(SyntaxKind::BlockExpression);
+        parse_block(parser);
+        parser.end_node();
+    } else {
+        parse_expression(parser);
+    }
+
+    parser.end_node();
+    has_block
+}
+
+/// parses a pattern, e.g. `_`, `-1`, `true` or `LightKind::Point`
+fn parse_pattern(parser: &mut Parser) {
+    match parser.current() {
+        t![ident] if parser.current_text() == "_" => {
+            parser.begin_node(SyntaxKind::WildcardPattern);
+            parser.consume(t![ident]);
+            parser.end_node();
+        },
+        t![ident] => {
+            parser.begin_node(SyntaxKind::PathPattern);
+            parser.begin_node(SyntaxKind::Path);
+            parse_item_path_segment(parser, false);
+            parser.end_node();
+            parser.end_node();
+        },
+        t![-] | t![int] | t![true] | t![false] => {
+            parser.begin_node(SyntaxKind::LiteralPattern);
+            if parser.consume_if(t![-]) {
+                parser.ws0();
+            }
+
+            match parser.current() {
+                t![int] => parse_int_literal(parser),
+                t![true] | t![false] => parse_bool_literal(parser),
+                _ => parser.missing(&[t![int]]),
+            }
+            parser.end_node();
+        },
+        _ => parser.missing(&[t![ident], t![int], t![true], t![false]]),
+    }
+}
+
+fn is_at_pattern_start(parser: &Parser) -> bool {
+    matches!(parser.current(), t![ident] | t![-] | t![int] | t![true] | t![false])
+}
+
 fn parse_unbounded_loop_expression(_parser: &mut Parser) {
     unimplemented!();
 }
@@ -823,12 +1547,18 @@ fn parse_index_operator(parser: &mut Parser, checkpoint: Checkpoint) {
     parser.end_node();
     parser.ws0();
 
-    parser.expect(SyntaxKind::CloseBracket, &[t![;], SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.expect(SyntaxKind::CloseBracket, &[t![;], SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.end_node();
 }
 
 fn parse_call_operator(parser: &mut Parser, checkpoint: Checkpoint) {
     parser.begin_node_at(checkpoint ,SyntaxKind::CallExpression);
+    parse_call_argument_list(parser);
+    parser.end_node();
+}
+
+/// parses the parenthesized arguments of a call, e.g. `(a, b)`
+fn parse_call_argument_list(parser: &mut Parser) {
     parser.consume(SyntaxKind::OpenParen);
     parser.ws0();
 
@@ -852,16 +1582,24 @@ fn parse_call_operator(parser: &mut Parser, checkpoint: Checkpoint) {
     }
     parser.end_node();
 
-    parser.expect(SyntaxKind::CloseParen, &[t![;], SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout]]);
-    parser.end_node();
+    parser.expect(SyntaxKind::CloseParen, &[t![;], SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
 }
 
 fn parse_dot_operator(parser: &mut Parser, checkpoint: Checkpoint) {
-    parser.begin_node_at(checkpoint, SyntaxKind::FieldAccessExpression);
     parser.consume(t![.]);
     parser.ws0();
 
-    parser.expect(t![ident], &[SyntaxKind::OpenParen, SyntaxKind::CloseParen, SyntaxKind::CloseBracket, SyntaxKind::CloseBrace, t![;], t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.begin_node(SyntaxKind::Name);
+    parser.expect(t![ident], &[SyntaxKind::OpenParen, SyntaxKind::CloseParen, SyntaxKind::CloseBracket, SyntaxKind::CloseBrace, t![;], t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
+    parser.end_node();
+
+    // a name directly followed by arguments calls a method, e.g. `a.b(c)`, otherwise it accesses a field
+    if parser.is_at(SyntaxKind::OpenParen) {
+        parser.begin_node_at(checkpoint, SyntaxKind::MethodCallExpression);
+        parse_call_argument_list(parser);
+    } else {
+        parser.begin_node_at(checkpoint, SyntaxKind::FieldAccessExpression);
+    }
 
     parser.end_node();
 }
@@ -885,7 +1623,7 @@ fn parse_primary_expression(parser: &mut Parser) {
             parser.ws0();
         },
         t![ident] => {
-            parse_item_path(parser);
+            parse_path_expression(parser);
             parser.ws0();
         },
         _ => parser.missing(&[t![true], t![false], t![int], t![float], t![ident]]),
@@ -900,7 +1638,7 @@ fn parse_group_expression(parser: &mut Parser) {
     parse_expression(parser);
     parser.ws0();
 
-    parser.expect(SyntaxKind::CloseParen, &[t![;], SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout]]);
+    parser.expect(SyntaxKind::CloseParen, &[t![;], SyntaxKind::CloseBrace, t![mod], t![use], t![fn], t![struct], t![layout], t![const], t![override], t![enum], t![impl], t![pub], t![type], t![stage]]);
     parser.end_node();
 }
 
@@ -951,9 +1689,8 @@ fn peek_binary_operator(parser: &mut Parser) -> Option<BinaryOperator> {
                 Some(t![=]) => {
                     Some(BinaryOperator::Equals)
                 },
-                _ => {
-                    Some(BinaryOperator::Equals)
-                }
+                // a single `=` ends the place of an assignment
+                _ => None,
             }
         },
         t![!] => {
@@ -1021,7 +1758,7 @@ fn parse_binary_operator(parser: &mut Parser) -> Option<BinaryOperator> {
             parser.consume(t![>]);
             match parser.current() {
                 t![>] => {
-                    parser.consume(t![<]);
+                    parser.consume(t![>]);
                     Some(BinaryOperator::RightShift)
                 },
                 t![=] => {
@@ -1126,8 +1863,8 @@ fn parse_unary_operator(parser: &mut Parser) -> Option<UnaryOperator> {
 fn is_at_expression_start(parser: &Parser) -> bool {
     match parser.current() {
         // parse an expression statement
-        // if and loop expression start
-        t![if] | t![loop] | t![while] | t![for] |
+        // if, match and loop expression start
+        t![if] | t![match] | t![loop] | t![while] | t![for] |
         // group expression start
         SyntaxKind::OpenParen |
         // primary expression start
@@ -1141,7 +1878,7 @@ fn is_at_expression_start(parser: &Parser) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lexer;
+    use crate::{lexer, syn::ast::ModuleItemOwner};
 
     #[test]
     fn test_module_declaration() {
@@ -1183,6 +1920,150 @@ mod tests {
         panic!();
     }
 
+    #[test]
+    fn test_const_declaration() {
+        let inputs = [
+            "const MAX_LIGHTS: u32 = 16;",
+            "const SCALE:f32=2.0*0.5;",
+            "const LIMIT: u32 = max(MAX_LIGHTS, 4u) + other::COUNT;",
+            "override(0) SHADOW_QUALITY: u32 = 2;",
+            "override( 1 + 1 )SCALE:f32=1.0;",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics.len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_enum_declaration() {
+        let inputs = [
+            "enum Foo{}",
+            "enum Foo { A }",
+            "enum Foo {A,B,}",
+            "enum LightKind { Point, Spot = 4, Directional = MAX + 1 }",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics.len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_match_expression() {
+        let inputs = [
+            "fn f(a: u32) -> u32 { match a { 0 => 1, 1 | 2 => 2, _ => 3 } }",
+            "fn f(a: i32) -> i32 { match a { -1 => 1, _ => { let b = a; b } } }",
+            "fn f(kind: LightKind) { match kind { LightKind::Point => { a(); } LightKind::Spot => b(), } c(); }",
+            "fn f(a: bool) -> f32 { let b = match a { true => 1.0, false => 0.0 }; b }",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics, vec![], "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_generic_function() {
+        let inputs = [
+            "fn id<T>(x: T) -> T { x }",
+            "fn saturate<T: float>(x: T) -> T { clamp(x, 0.0, 1.0) }",
+            "fn lerp< T : float + vec3 , U: integer, >(a: T, b: T, t: f32) -> T { a + (b - a) * t }",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics, vec![], "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_impl_declaration() {
+        let inputs = [
+            "impl Foo {}",
+            "impl Foo { fn new() -> Foo { Foo::make(1.0) } }",
+            "impl Foo { fn luminance(self) -> f32 { dot(self.color, vec3(0.2, 0.7, 0.1)) } fn scaled(self, s: f32) -> f32 { self.luminance() * s } }",
+            "fn f(foo: Foo) -> f32 { foo.bar.luminance().max(foo . scale ( 2.0 , )) }",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics, vec![], "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_visibility() {
+        let inputs = [
+            "pub fn f() {}",
+            "pub(package) struct Foo { pub a: f32, pub ( package ) b: f32, c: f32 }",
+            "pub enum A { X } pub layout L { binding(0) uniform u: U } pub const N: u32 = 4; pub override(1) M: u32 = 2;",
+            "impl Foo { pub fn new() -> Foo { Foo::make(1.0) } fn secret(self) -> f32 { 1.0 } }",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics, vec![], "{}", input);
+        }
+
+        // only items can be public, the `pub` is skipped and the module declaration still parsed
+        let input = "pub mod foo; fn f() {}";
+        let token = lexer::tokenize(input);
+        let result = parse(&token, input);
+        assert!(matches!(result.diagnostics.as_slice(), [ParseDiagnostic::SkippedToken { skipped: SyntaxKind::PubKeyword, .. }]));
+        assert_eq!(result.tree().module_items().count(), 2);
+    }
+
+    #[test]
+    fn test_entry_point_declaration() {
+        let inputs = [
+            "stage(vertex) fn main(input: VertexIn, scene: Scene) -> vec4<f32> { input.position }",
+            "pub stage ( fragment ) fn main() {}",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics, vec![], "{}", input);
+        }
+
+        let input = "stage(vertex) main() {} fn f() {}";
+        let token = lexer::tokenize(input);
+        let result = parse(&token, input);
+        assert!(!result.diagnostics.is_empty());
+        assert_eq!(result.tree().module_items().count(), 2);
+    }
+
+    #[test]
+    fn test_type_alias_declaration() {
+        let inputs = [
+            "type Color = vec4<f32>;",
+            "type Matrices=[mat4x4<f32>;4];",
+            "pub type Particles = [foo::Particle];",
+        ];
+
+        for input in inputs {
+            let token = lexer::tokenize(input);
+            let result = parse(&token, input);
+            assert_eq!(result.diagnostics, vec![], "{}", input);
+        }
+
+        let input = "type Color vec4<f32>; fn f() {}";
+        let token = lexer::tokenize(input);
+        let result = parse(&token, input);
+        assert!(!result.diagnostics.is_empty());
+        assert_eq!(result.tree().module_items().count(), 2);
+    }
+
     #[test]
     fn test_struct_declaration() {
         let inputs = [
@@ -1196,6 +2077,10 @@ mod tests {
             "struct Foo { foo: Bar, bar: Foo }",
             "struct Foo { foo: bar::Baz, bar: Foo }",
             "struct Foo { foo: bar::Baz, bar: foo::foo::Foo }",
+            "struct Foo { position: vec3<f32>, transform: mat4x4<f32> }",
+            "struct Foo { lights: [Light; 4], matrices: [[f32; 4]; 4], particles: [Particle] }",
+            "struct Foo { location(0) color: vec3<f32>, location(1) interpolate(flat) id: u32 }",
+            "struct Foo { interpolate(linear) depth: f32, pub location(COLOR + 1) color: vec4<f32> }",
         ];
 
         for input in inputs {
@@ -1215,6 +2100,8 @@ mod tests {
             "fn foo() -> Foo {}",
             "fn foo() -> foo::Foo {}",
             "fn foo(a: A, b: B, c: C) -> foo::Foo {}",
+            "fn foo(a: vec3<f32>, b: mat4x4<f32>) -> vec4<f32> {}",
+            "fn foo(a: foo::Bar<f32, i32>) -> vec2<foo::Bar> {}",
         ];
 
         for input in inputs {
@@ -1236,6 +2123,7 @@ mod tests {
             "fn foo() { 1 * 2 + 3 }",
             "fn foo() { true; false }",
             "fn foo() { a + b; b + c; }",
+            "fn foo() { a.b[0] = 1; c = a == b; }",
         ];
 
         for input in inputs {
@@ -1253,6 +2141,9 @@ mod tests {
             "fn foo() { a(b, c); }",
             "fn foo() { a(b, c,); }",
             "fn foo() { a(1 + 2, 3 * 4); }",
+            "fn foo() { vec3(1.0, 2.0, 3.0) }",
+            "fn foo() { vec3<f32>(1.0, 2.0, 3.0) + foo::vec3 < f32 > (x) }",
+            "fn foo() { a < b; a > b }",
         ];
 
         for input in inputs {
@@ -1311,6 +2202,12 @@ mod tests {
     fn test_layout() {
         let inputs = [
             "layout Foo { binding(0) uniform bar: Bar, }",
+            "layout Foo { binding(0) uniform bar: Bar, binding(1) storage particles: [Particle] }",
+            "layout(1) Foo { binding(0) uniform bar: Bar }",
+            "layout Foo { uniform bar: Bar, binding(3) image albedo: texture_2d<f32>, sampler linear: sampler_state }",
+            "pub layout ( MATERIAL_SET + 1 ) Foo { binding(0) uniform bar: Bar }",
+            "layout Foo { push_constant draw: Draw, uniform bar: Bar }",
+            "layout Foo { storage(read) particles: [Particle], storage ( read_write ) counter: Counter, workgroup tiles: [u32; 64] }",
         ];
 
         for input in inputs {
@@ -43,12 +43,22 @@ impl<'a> Parser<'a> {
         self.token[0].kind()
     }
 
-    pub fn next(&self) -> Option<SyntaxKind> {
-        if self.token.len() < 2 {
-            return None;
+    /// The source text of the current token, e.g. to tell `_` apart from other identifiers
+    pub fn current_text(&self) -> &str {
+        if self.eof() {
+            return "";
         }
 
-        Some(self.token[1].kind())
+        &self.input[0..self.token[0].len()]
+    }
+
+    pub fn next(&self) -> Option<SyntaxKind> {
+        self.nth(1)
+    }
+
+    /// The kind of the token `n` tokens ahead, `nth(0)` is the current token
+    pub fn nth(&self, n: usize) -> Option<SyntaxKind> {
+        self.token.get(n).map(|token| token.kind())
     }
 
     /// consumes zero or one whitespace token
@@ -1,4 +1,4 @@
-use rowan::TextSize;
+pub use rowan::TextSize;
 
 pub trait Locate {
     type Item;
@@ -29,6 +29,26 @@ impl fmt::Debug for CompilerDatabase {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hir::{memory_layout::{LayoutRules, MemoryLayoutError, StructLayout}, pipeline::{EntryPointId, InterfaceError, InterfaceMismatch, PipelineError}, type_check::{StructTy, TypeCheckError, TypeCheckWarning}};
+    use camino::Utf8Path;
+    use std::sync::Arc;
+
+    fn type_check(contents: &str) -> Vec<TypeCheckError> {
+        type_check_files(&[("main.skysl", contents)])
+    }
+
+    /// Type checks the first of the given files
+    fn type_check_files(files: &[(&str, &str)]) -> Vec<TypeCheckError> {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+
+        let mut file_ids = Vec::new();
+        for (path, contents) in files {
+            file_ids.push(insert_file(&mut db, Utf8Path::new(path), Arc::new(contents.to_string())).unwrap());
+        }
+
+        db.get_typed_hir(file_ids[0]).errors
+    }
 
     #[test]
     fn it_is_send() {
@@ -38,4 +58,776 @@ mod tests {
             let _ = db;
         });
     }
+
+    #[test]
+    fn it_type_checks_vectors_and_matrices() {
+        let errors = type_check("
+            fn transform(m: mat4x4<f32>, p: vec4<f32>) -> vec4<f32> { m * p }
+            fn scale(v: vec3<f32>, s: f32) -> vec3<f32> { let c: vec3<f32> = vec3(1.0, v.xy); c * s + v }
+            fn normal(m: mat3x3<f32>, n: vec3<f32>) -> vec3<f32> { n * m }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("fn bad(m: mat4x4<f32>, v: vec3<f32>) -> vec3<f32> { m * v }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::InvalidBinaryOperands { .. }]));
+
+        let errors = type_check("fn bad() -> vec3<f32> { vec3(1.0, 2.0) }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::ConstructorComponentCount { .. }]));
+
+        // constructors with a stated element type, `<` without call arguments is still a comparison
+        let errors = type_check("
+            fn typed(x: f32) -> vec4<f32> { let half = vec2<f32>(0.5, 0.5); vec4<f32>(half, 1.0, x) }
+            fn texel() -> vec3<u32> { vec3<u32>(1, 2, 3) }
+            fn columns() -> mat2x2<f32> { mat2x2<f32>(vec2(1.0, 0.0), vec2(0.0, 1.0)) }
+            fn compare(a: f32, b: f32, c: f32) -> bool { a < b && b > (c) }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("
+            fn bad(x: u32) -> vec3<f32> { vec3<f32>(x, x, x) }
+            fn half(x: f32) -> f32 { x * 0.5 }
+            fn call() -> f32 { half<f32>(1.0) }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::MismatchedTypes { .. },
+            TypeCheckError::UnexpectedGenericArguments { .. },
+        ]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_type_checks_arrays() {
+        let errors = type_check("
+            struct Particles { count: u32, positions: [vec4<f32>] }
+            layout Simulation { binding(0) storage particles: Particles, binding(1) storage velocities: [vec4<f32>] }
+            fn first(weights: [f32; 4], m: mat4x4<f32>) -> f32 { weights[0] + m[1].x }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("struct Foo { positions: [vec4<f32>], count: u32 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::RuntimeSizedArray { .. }]));
+
+        let errors = type_check("layout Foo { binding(0) uniform positions: [vec4<f32>] }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::RuntimeSizedArray { .. }]));
+
+        let errors = type_check("fn bad(weights: [f32; 4]) -> f32 { weights[1.0] }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::InvalidIndex { .. }]));
+
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let contents = "struct Foo { values: [f32; 2 - 2], count: u32 } fn count(foo: Foo) -> u32 { foo.count }";
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new(contents.to_string())).unwrap();
+        let errors = db.get_hir(file).errors;
+        assert!(matches!(errors.as_slice(), [crate::hir::lower::LowerToHirError::InvalidArrayLength { .. }]), "{:?}", errors);
+        assert_eq!(&contents[errors[0].span().unwrap()], "2 - 2");
+        assert_eq!(db.get_typed_hir(file).errors, vec![]);
+    }
+
+    #[test]
+    fn it_type_checks_textures_and_samplers() {
+        let errors = type_check("
+            layout Material { binding(0) image albedo: texture_2d<f32>, binding(1) sampler linear: sampler_state, binding(2) image output: texture_storage_2d<rgba8unorm> }
+            fn shade(albedo: texture_2d<f32>, linear: sampler_state, uv: vec2<f32>) -> vec4<f32> { sample(albedo, linear, uv) }
+            fn blit(source: texture_2d_array<f32>, output: texture_storage_2d<rgba8unorm>, texel: vec2<i32>) { store(output, texel, load(source, texel, 0, 0)); }
+            fn size(source: texture_cube<f32>) -> vec2<u32> { dimensions(source) }
+            fn shadow(map: texture_depth_2d, shadows: sampler_comparison_state, uv: vec2<f32>, depth: f32) -> f32 { sample_compare(map, shadows, uv, depth) * sample_compare_level(map, shadows, uv, depth) }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("layout Foo { binding(0) uniform albedo: texture_2d<f32>, binding(1) image linear: sampler_state }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::InvalidBindingType { .. }, TypeCheckError::InvalidBindingType { .. }]));
+
+        let errors = type_check("layout Foo { binding(0) image output: texture_storage_2d<f32> }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::ExpectedTexelFormat { .. }]));
+
+        let errors = type_check("fn bad(albedo: texture_2d<f32>, linear: sampler_state) -> vec4<f32> { sample(albedo, linear, 1.0) }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::MismatchedTypes { .. }]));
+
+        let errors = type_check("fn bad(albedo: texture_2d<f32>, texel: vec2<i32>) { store(albedo, texel, vec4(1.0)); }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::InvalidIntrinsicArgument { .. }]));
+
+        // comparison samplers are only accepted by `sample_compare` and the other way around
+        let errors = type_check("
+            fn bad(map: texture_depth_2d, shadows: sampler_comparison_state, linear: sampler_state, uv: vec2<f32>) -> f32 {
+                sample(map, shadows, uv) + sample_compare(map, linear, uv, 0.5)
+            }
+        ");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::MismatchedTypes { .. }, TypeCheckError::MismatchedTypes { .. }]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_type_checks_math_intrinsics() {
+        let errors = type_check("
+            fn shade(normal: vec3<f32>, light: vec3<f32>, color: vec3<f32>) -> vec3<f32> {
+                let diffuse: f32 = max(dot(normalize(normal), light), 0.0);
+                let rim = pow(1.0 - abs(normal.z), 2.0);
+                mix(color * diffuse, vec3(rim), clamp(length(light), 0.0, 1.0))
+            }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("fn bad(v: vec2<f32>) -> vec2<f32> { cross(v, v) }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::NoMatchingOverload { .. }]));
+
+        let errors = type_check("fn bad(x: f32) -> f32 { sin(x, x) }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::ArgumentCount { .. }]));
+    }
+
+    #[test]
+    fn it_resolves_function_calls() {
+        let errors = type_check_files(&[
+            ("main.skysl", "
+                mod utils;
+                use utils::{lerp, half};
+                fn scale(v: f32) -> f32 { double(v) }
+                fn double(v: f32) -> f32 { v * 2.0 }
+                fn blend(a: f32, b: f32) -> f32 { lerp(a, b, half()) + utils::half() }
+            "),
+            ("utils.skysl", "
+                pub fn lerp(a: f32, b: f32, t: f32) -> f32 { a + (b - a) * t }
+                pub fn half() -> f32 { 0.5 }
+            "),
+        ]);
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("fn f(a: f32) -> f32 { a } fn g() -> f32 { f(1.0, 2.0) + f(1) }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::ArgumentCount { .. }, TypeCheckError::MismatchedTypes { .. }]));
+
+        let errors = type_check("fn a(x: f32) -> f32 { b(x) } fn b(x: f32) -> f32 { a(x) }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::RecursiveCall { .. }, TypeCheckError::RecursiveCall { .. }]));
+    }
+
+    #[test]
+    fn it_types_number_literals() {
+        let errors = type_check("
+            fn literals() -> u32 { let a: f16 = 1.5e2f16; let b: i8 = -0b1000_0000i8; let c: f32 = 1e3 + 2.0f; 0xFFu }
+            fn lengths(values: [f32; 0x10]) -> f32 { values[3u] }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("fn bad() -> u32 { 1.0 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::MismatchedTypes { .. }]));
+
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("fn a() -> f32 { 1e } fn b() -> u8 { 256u8 }".to_string())).unwrap();
+        let errors = db.get_hir(file).errors;
+        assert!(matches!(errors.as_slice(), [
+            crate::hir::lower::LowerToHirError::InvalidLiteral { error: crate::syn::cst::LiteralError::EmptyExponent },
+            crate::hir::lower::LowerToHirError::InvalidLiteral { error: crate::syn::cst::LiteralError::OutOfRange { .. } },
+        ]));
+    }
+
+    #[test]
+    fn it_coerces_unsuffixed_literals() {
+        let errors = type_check("
+            fn scale(v: vec3<f16>, s: f16) -> vec3<f16> { v * 2.0 + vec3(0.5) * s }
+            fn offset(x: u32) -> u32 { let a: u8 = 255; let b: i8 = -128; x + 1 }
+            fn clamped(x: f16) -> f16 { max(x, 0.0) + scale(vec3(x), 1.0).x }
+            fn large() -> u64 { 18446744073709551615 }
+            fn wide() -> f64 { let x: f64 = 1e300; x * 1.5e308 }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("fn bad() -> u8 { let a: i8 = 128; 256 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::LiteralOutOfRange { .. }, TypeCheckError::LiteralOutOfRange { .. }]));
+
+        let errors = type_check("fn bad() -> f16 { let a = 3000000000; 70000.0 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::LiteralOutOfRange { .. }, TypeCheckError::LiteralOutOfRange { .. }]));
+
+        let errors = type_check("fn bad() -> f32 { 1e300 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::LiteralOutOfRange { ty, .. }] if ty == "f32"));
+
+        let errors = type_check("fn bad(x: f32) -> u32 { let a: f32 = 1; x < 2 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::MismatchedTypes { .. }, TypeCheckError::InvalidBinaryOperands { .. }]));
+    }
+
+    #[test]
+    fn it_evaluates_binding_indices() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let contents = "
+            struct Particles { values: [f32; 2 * 8], rest: [vec4<f32>; u32(sqrt(16.0))] }
+            layout Material { binding(0) uniform a: f32, binding(2 * 3 + 1) uniform b: f32, binding(u32(max(2.5, 4.0))) uniform c: f32 }
+        ";
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new(contents.to_string())).unwrap();
+        assert_eq!(db.get_hir(file).errors, vec![]);
+
+        let module = db.get_typed_hir(file);
+        assert_eq!(module.errors, vec![]);
+        let indices: Vec<_> = module.items.iter()
+            .flat_map(|item| match item {
+                crate::hir::typed::ItemKind::Layout(layout) => layout.members.iter().map(|member| member.binding_index).collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        assert_eq!(indices, vec![Some(0), Some(7), Some(4)]);
+
+        let errors = type_check("layout Foo { binding(1 / 0) uniform a: f32, binding(4294967295 + 1) uniform b: f32, binding(-1i32) uniform c: f32 }");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::DivisionByZero { .. } },
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::Overflow { .. } },
+            TypeCheckError::InvalidBindingIndex { .. },
+        ]));
+
+        let errors = type_check("fn f() -> u32 { 1 } layout Foo { binding(f()) uniform a: f32 }");
+        assert!(matches!(errors.as_slice(), [TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::NotConstant { .. } }]));
+
+        let errors = type_check("const A: f32 = sqrt(-1.0); const B: f32 = 1000000000000000000000.0 * 1000000000000000000000.0;");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::NotANumber { .. } },
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::Overflow { .. } },
+        ]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_evaluates_constants() {
+        let errors = type_check_files(&[
+            ("main.skysl", "
+                mod lights;
+                use lights::MAX_LIGHTS;
+                const SCALE: f32 = 2.0 * 0.5;
+                const DOUBLE: u32 = MAX_LIGHTS * 2;
+                struct Lights { values: [vec4<f32>; MAX_LIGHTS], scales: [f32; DOUBLE] }
+                layout Scene { binding(MAX_LIGHTS + 1) uniform a: f32 }
+                fn f() -> u32 { MAX_LIGHTS * 2 }
+                fn g(x: f32) -> f32 { x * SCALE }
+            "),
+            ("lights.skysl", "const MAX_LIGHTS: u32 = 4;"),
+        ]);
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("const A: u32 = B; const B: u32 = A;");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::Cycle { .. } },
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::Cycle { .. } },
+        ]));
+
+        let errors = type_check("const X: u32 = 1 / 0; const Y: vec2<f32> = vec2(1.0, 2.0); const Z: u32 = 1.0;");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::DivisionByZero { .. } },
+            TypeCheckError::InvalidConstType { .. },
+            TypeCheckError::MismatchedTypes { .. },
+        ]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_evaluates_overrides() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let contents = "
+            const QUALITY: u32 = 2;
+            override(0) SAMPLES: u32 = QUALITY * 4;
+            override(1 + 1) EXPOSURE: f32 = 1.0;
+            fn f() -> f32 { EXPOSURE * 2.0 }
+        ";
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new(contents.to_string())).unwrap();
+        assert_eq!(db.get_hir(file).errors, vec![]);
+
+        let module = db.get_typed_hir(file);
+        assert_eq!(module.errors, vec![]);
+        let overrides: Vec<_> = module.items.iter()
+            .filter_map(|item| match item {
+                crate::hir::typed::ItemKind::Const(constant) if constant.is_override => Some((constant.override_id, constant.value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(overrides, vec![
+            (Some(0), Some(crate::hir::const_eval::ConstValue::Int { value: 8, signed: false, width: 32 })),
+            (Some(2), Some(crate::hir::const_eval::ConstValue::Float { value: 1.0, width: 32 })),
+        ]);
+
+        let errors = type_check("override(0) A: u32 = 1; override(0) B: u32 = 2; override(70000) C: u32 = 3; const D: u32 = A;");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::DuplicateOverrideId { id: 0, .. },
+            TypeCheckError::InvalidOverrideId { .. },
+            TypeCheckError::ConstEval { error: crate::hir::const_eval::ConstEvalError::Overridable { .. } },
+        ]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_type_checks_enums_and_match() {
+        let errors = type_check("
+            enum LightKind { Directional, Point = 4, Spot }
+            fn intensity(kind: LightKind, level: u32) -> f32 {
+                let scale = match level { 0 => 0.5, 1 | 2 => 1.0, _ => 2.0 };
+                match kind {
+                    LightKind::Directional => { scale }
+                    LightKind::Point | LightKind::Spot => scale * 0.5,
+                }
+            }
+            fn is_point(kind: LightKind) -> bool { kind == LightKind::Point }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("
+            enum A { X = 1, Y = 1, X }
+            fn f(a: A) -> u32 { match a { A::X => 1, A::X => 2, A::Z => 3 } }
+            fn g(v: f32) -> u32 { match v { _ => 0 } }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::DuplicateDiscriminant { value: 1, .. },
+            TypeCheckError::DuplicateVariant { .. },
+            TypeCheckError::UnreachablePattern { .. },
+            TypeCheckError::UnknownVariant { .. },
+            TypeCheckError::NonExhaustiveMatch { .. },
+            TypeCheckError::InvalidMatchScrutinee { .. },
+        ]), "{:?}", errors);
+
+        // a wildcard after every variant is only a warning, a repeated variant stays an error
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            enum A { X, Y }
+            fn f(a: A) -> u32 { match a { A::X => 1, A::Y => 2, _ => 3 } }
+            fn g(a: bool) -> u32 { match a { true => 1, false | true => 2 } }
+        ".to_string())).unwrap();
+
+        let module = db.get_typed_hir(file);
+        assert!(matches!(module.errors.as_slice(), [TypeCheckError::UnreachablePattern { .. }]), "{:?}", module.errors);
+        assert!(matches!(module.warnings.as_slice(), [TypeCheckWarning::UnreachableWildcard { .. }]), "{:?}", module.warnings);
+    }
+
+    #[test]
+    fn it_type_checks_generic_functions() {
+        let errors = type_check("
+            fn saturate<T: float>(x: T) -> T { clamp(x, 0.0, 1.0) }
+            fn lerp<T: float>(a: T, b: T, t: T) -> T { a + (b - a) * t }
+            fn brightness<T: float + vec3>(color: T) -> T { color * dot(color, saturate(color)) }
+            fn f(color: vec3<f32>) -> vec3<f32> { lerp(color, brightness(color), vec3(0.5)) * saturate(2.0) }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("
+            fn saturate<T: float>(x: T) -> T { clamp(x, 0.0, 1.0) }
+            fn scale<T: float>(x: T) -> T { x * 100000.0 }
+            fn pick<T: float>(a: f32) -> f32 { a }
+            fn id<T: number>(x: T) -> T { x }
+            fn f() -> f16 { saturate(1); pick(1.0); scale(1.0); scale(1.0f16) }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::UnknownTypeBound { .. },
+            TypeCheckError::UnsatisfiedBound { .. },
+            TypeCheckError::CannotInferGeneric { .. },
+            TypeCheckError::InstantiationFailed { .. },
+        ]), "{:?}", errors);
+
+        assert_eq!(errors[3].to_string(), "In `scale<f16>`: Literal out of range for `f16`");
+    }
+
+    #[test]
+    fn it_checks_visibility() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let contents = "
+            mod lights;
+            use lights::{Light, attenuation, falloff};
+            fn f(light: Light) -> f32 { light.intensity * light.power() * attenuation(2.0) }
+            fn g(light: Light) -> f32 { light.range + light.scale() + lights::falloff() }
+        ";
+        let main = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new(contents.to_string())).unwrap();
+        insert_file(&mut db, Utf8Path::new("lights.skysl"), Arc::new("
+            pub struct Light { pub intensity: f32, range: f32 }
+            impl Light { pub fn power(self) -> f32 { self.intensity * self.range } fn scale(self) -> f32 { self.range } }
+            pub(package) fn attenuation(d: f32) -> f32 { 1.0 / (d * d) }
+            fn falloff() -> f32 { 0.5 }
+        ".to_string())).unwrap();
+
+        let errors = db.get_hir(main).errors;
+        assert!(matches!(errors.as_slice(), [
+            crate::hir::lower::LowerToHirError::PrivateItem { scope: "module", .. },
+            crate::hir::lower::LowerToHirError::PrivateItem { scope: "module", .. },
+        ]), "{:?}", errors);
+        assert_eq!(errors[0].to_string(), "`falloff` is private to its module, consider declaring it `pub`");
+        let spans: Vec<_> = errors.iter().filter_map(|error| error.span()).map(|span| &contents[span]).collect();
+        assert_eq!(spans, vec!["falloff", "falloff"]);
+        assert!(errors[0].span().unwrap().start() < errors[1].span().unwrap().start());
+
+        let errors = db.get_typed_hir(main).errors;
+        assert!(matches!(errors.as_slice(), [TypeCheckError::PrivateMember { .. }, TypeCheckError::PrivateMember { .. }]), "{:?}", errors);
+        assert_eq!(errors[1].to_string(), "`Light::scale` is private to its module, consider declaring it `pub`");
+    }
+
+    #[test]
+    fn it_type_checks_methods() {
+        let errors = type_check("
+            struct Light { color: vec3<f32>, intensity: f32 }
+            impl Light {
+                fn luminance(self) -> f32 { dot(self.color, vec3(0.2126, 0.7152, 0.0722)) * self.intensity }
+                fn scaled(self, factor: f32) -> f32 { self.luminance() * factor }
+                fn ambient() -> f32 { 0.1 }
+            }
+            fn f(light: Light) -> f32 { light.scaled(2.0) + light.intensity + Light::ambient() }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("
+            struct Light { intensity: f32 }
+            impl Light { fn ambient() -> f32 { 0.1 } fn half(self) -> f32 { 0.5 } }
+            impl Light { fn half(self) -> f32 { 0.5 } }
+            fn f(light: Light) -> f32 { light.ambient() + light.brightness() + light.half(1.0) }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::DuplicateMethod { .. },
+            TypeCheckError::NotAMethod { .. },
+            TypeCheckError::UnknownMethod { .. },
+            TypeCheckError::ArgumentCount { .. },
+        ]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_resolves_type_aliases() {
+        let errors = type_check("
+            type Color = vec4<f32>;
+            type Palette = [Color; 4];
+            type Lights = [Light];
+            struct Light { color: Color, palette: Palette }
+            struct Scene { count: u32, lights: Lights }
+            layout Bindings { binding(0) storage scene: Scene, binding(1) uniform tint: Color }
+            fn f(color: Color, palette: Palette) -> f32 { color.x + palette[2].y }
+        ");
+        assert_eq!(errors, vec![]);
+
+        let errors = type_check("
+            type A = B;
+            type B = [A; 2];
+            struct Foo { a: A }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::RecursiveTypeAlias { .. },
+            TypeCheckError::RecursiveTypeAlias { .. },
+        ]), "{:?}", errors);
+        assert_eq!(errors[0].to_string(), "Type alias `A` expands to itself");
+    }
+
+    #[test]
+    fn it_computes_struct_layouts() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let main = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            struct Light { position: vec3<f32>, intensity: f32, weights: [f32; 2], transform: mat3x3<f32> }
+            struct Scene { time: f32, light: Light, enabled: bool }
+            layout Bindings { binding(0) uniform scene: Scene, binding(1) storage light: Light }
+        ".to_string())).unwrap();
+
+        let struct_ty = |name: &str| StructTy { file: main, name: db.intern_name(name.to_string()) };
+        let offsets = |layout: &StructLayout| layout.members.iter().map(|member| member.offset).collect::<Vec<_>>();
+
+        let layout = db.struct_layout(struct_ty("Light"), LayoutRules::Std140);
+        assert_eq!((offsets(&layout), layout.size, layout.alignment), (vec![0, 12, 16, 48], 96, 16));
+        let layout = db.struct_layout(struct_ty("Light"), LayoutRules::Std430);
+        assert_eq!((offsets(&layout), layout.size, layout.alignment), (vec![0, 12, 16, 32], 80, 16));
+        let layout = db.struct_layout(struct_ty("Light"), LayoutRules::Scalar);
+        assert_eq!((offsets(&layout), layout.size, layout.alignment), (vec![0, 12, 16, 24], 60, 4));
+
+        let layout = db.struct_layout(struct_ty("Scene"), LayoutRules::Std140);
+        assert_eq!(offsets(&layout), vec![0, 16, 112]);
+        assert!(matches!(layout.errors.as_slice(), [MemoryLayoutError::InvalidMemberType { .. }]), "{:?}", layout.errors);
+        assert_eq!(layout.errors[0].to_string(), "`Scene::enabled` of type `bool` has no memory layout and can't be shared with the host");
+
+        let errors = db.get_typed_hir(main).errors;
+        assert!(matches!(errors.as_slice(), [TypeCheckError::MemoryLayout { rules: "std140", .. }]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_type_checks_entry_points() {
+        let errors = type_check("
+            struct Camera { view: mat4x4<f32> }
+            layout Scene { binding(0) uniform camera: Camera }
+            stage(vertex) fn main(position: vec4<f32>, scene: Scene) -> vec4<f32> { scene.camera.view * position }
+            stage(fragment) fn shade<T: float>(scene: Scene) {}
+            fn project(scene: Scene) {}
+            struct Foo { scene: Scene }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::GenericEntryPoint { .. },
+            TypeCheckError::LayoutArgument { .. },
+            TypeCheckError::OpaqueType { .. },
+        ]), "{:?}", errors);
+    }
+
+    #[test]
+    fn it_checks_set_and_binding_indices() {
+        let errors = type_check("
+            const MATERIAL_SET: u32 = 1;
+            layout(MATERIAL_SET + 1) Material { binding(0) uniform a: f32, binding(0) uniform b: f32 }
+            layout(2) Shadows { binding(0) uniform c: f32 }
+            layout(-1i32) Invalid { binding(0) uniform d: f32 }
+            stage(fragment) fn main(material: Material, shadows: Shadows) {}
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::DuplicateBindingIndex { index: 0, .. },
+            TypeCheckError::InvalidSetIndex { .. },
+            TypeCheckError::DuplicateSetIndex { set: 2, .. },
+        ]), "{:?}", errors);
+
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            layout Scene { binding(0) uniform a: f32 }
+            layout(0) Material { binding(0) uniform b: f32 }
+            layout Lights { binding(0) uniform c: f32 }
+            stage(fragment) fn main(scene: Scene, material: Material, lights: Lights) {}
+        ".to_string())).unwrap();
+        assert_eq!(db.get_typed_hir(file).errors, vec![]);
+
+        let reflection = crate::workspace::reflect_package(&db, file);
+        let sets: Vec<_> = reflection.entry_points[0].layouts.iter().map(|layout| (layout.name.as_str(), layout.set)).collect();
+        assert_eq!(sets, vec![("Scene", 1), ("Material", 0), ("Lights", 2)]);
+    }
+
+    #[test]
+    fn it_assigns_binding_indices() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            layout Material { uniform a: f32, binding(1) uniform b: f32, uniform c: f32, binding(5) uniform d: f32, uniform e: f32 }
+            layout Shadows { binding(2) uniform f: f32, uniform g: f32 }
+        ".to_string())).unwrap();
+
+        let module = db.get_typed_hir(file);
+        assert_eq!(module.errors, vec![]);
+        let indices: Vec<_> = module.items.iter()
+            .flat_map(|item| match item {
+                crate::hir::typed::ItemKind::Layout(layout) => layout.members.iter().map(|member| (member.binding_index, member.automatic_binding)).collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        assert_eq!(indices, vec![
+            (Some(0), true), (Some(1), false), (Some(2), true), (Some(5), false), (Some(3), true),
+            (Some(2), false), (Some(0), true),
+        ]);
+
+        let names: Vec<_> = module.warnings.iter()
+            .filter_map(|warning| match warning {
+                TypeCheckWarning::MixedBindingIndices { name, previous, .. } => Some((name.as_str(), previous.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, vec![("e", "d"), ("g", "f")]);
+    }
+
+    #[test]
+    fn it_checks_pipeline_layouts() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            struct Camera { view: mat4x4<f32>, position: vec3<f32> }
+            struct VertexCamera { transform: mat4x4<f32>, origin: vec3<f32> }
+            struct Light { color: vec3<f32> }
+            layout(0) Scene { binding(0) uniform camera: Camera, binding(1) image albedo: texture_2d<f32> }
+            layout(0) VertexScene { binding(0) uniform camera: VertexCamera, binding(1) sampler albedo: sampler_state }
+            layout(0) LightScene { binding(0) uniform light: Light }
+            stage(vertex) fn vertex_main(scene: VertexScene) {}
+            stage(fragment) fn fragment_main(scene: Scene) {}
+            stage(fragment) fn light_main(scene: LightScene) {}
+        ".to_string())).unwrap();
+        assert_eq!(db.get_typed_hir(file).errors, vec![]);
+
+        let entry_point = |name: &str| EntryPointId { file, name: db.intern_name(name.to_string()) };
+
+        let pipeline = db.pipeline_layout(vec![entry_point("vertex_main"), entry_point("fragment_main")]);
+        assert_eq!(pipeline.bindings.len(), 2);
+        assert!(matches!(pipeline.errors.as_slice(), [
+            PipelineError::KindMismatch { set: 0, binding: 1, .. },
+        ]), "{:?}", pipeline.errors);
+
+        let pipeline = db.pipeline_layout(vec![entry_point("fragment_main"), entry_point("light_main")]);
+        assert!(matches!(pipeline.errors.as_slice(), [
+            PipelineError::IncompatibleLayout { set: 0, binding: 0, rules: "std140", .. },
+        ]), "{:?}", pipeline.errors);
+    }
+
+    #[test]
+    fn it_matches_stage_interfaces() {
+        let errors = type_check("
+            struct Varyings { location(1) a: f32, location(1) b: f32, interpolate(linear) c: u32, location(-1i32) d: f32 }
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::DuplicateLocation { location: 1, .. },
+            TypeCheckError::InvalidInterpolation { .. },
+            TypeCheckError::InvalidLocation { .. },
+        ]), "{:?}", errors);
+
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            struct VertexOutput { location(0) color: vec3<f32>, uv: vec2<f32>, location(4) material: u32, normal: vec3<f32>, tangent: vec3<f32> }
+            struct FragmentInput { colour: vec3<f32>, uv: vec2<f32>, location(4) material: u32, interpolate(linear) normal: vec3<f32>, location(3) tangent: vec4<f32> }
+            stage(vertex) fn vertex_main(output: VertexOutput) -> VertexOutput { output }
+            stage(fragment) fn fragment_main(input: FragmentInput) {}
+        ".to_string())).unwrap();
+
+        let entry_point = |name: &str| EntryPointId { file, name: db.intern_name(name.to_string()) };
+        let interface = db.stage_interface(entry_point("vertex_main"), entry_point("fragment_main"));
+
+        let locations: Vec<_> = interface.outputs.iter().map(|output| (output.location, output.automatic_location)).collect();
+        assert_eq!(locations, vec![(0, false), (1, true), (4, false), (2, true), (3, true)]);
+
+        let errors: Vec<_> = interface.errors.iter().map(|mismatch| &mismatch.error).collect();
+        assert!(matches!(errors.as_slice(), [
+            InterfaceError::RenamedOutput { location: 0, .. },
+            InterfaceError::InterpolationMismatch { input: "linear", output: "perspective", .. },
+            InterfaceError::TypeMismatch { .. },
+        ]), "{:?}", errors);
+
+        // arguments that are not structs are matched like members without a location
+        let file = insert_file(&mut db, Utf8Path::new("arguments.skysl"), Arc::new("
+            struct VertexOutput { color: vec3<f32>, uv: vec2<f32> }
+            layout Scene { binding(0) uniform exposure: f32 }
+            stage(vertex) fn vertex_main(output: VertexOutput) -> VertexOutput { output }
+            stage(fragment) fn fragment_main(color: vec4<f32>, scene: Scene, uv: vec2<f32>) {}
+            stage(fragment) fn renamed_main(c: vec3<f32>) {}
+        ".to_string())).unwrap();
+
+        let entry_point = |name: &str| EntryPointId { file, name: db.intern_name(name.to_string()) };
+        let interface = db.stage_interface(entry_point("vertex_main"), entry_point("fragment_main"));
+
+        let inputs: Vec<_> = interface.inputs.iter().map(|input| (input.location, input.structure)).collect();
+        assert_eq!(inputs, vec![(0, None), (1, None)]);
+        assert!(matches!(interface.errors.as_slice(), [InterfaceMismatch { error: InterfaceError::TypeMismatch { .. }, .. }]), "{:?}", interface.errors);
+
+        let interface = db.stage_interface(entry_point("vertex_main"), entry_point("renamed_main"));
+        assert!(matches!(interface.errors.as_slice(), [InterfaceMismatch { error: InterfaceError::RenamedOutput { location: 0, .. }, .. }]), "{:?}", interface.errors);
+    }
+
+    #[test]
+    fn it_checks_push_constants() {
+        let errors = type_check("
+            struct Draw { transform: mat4x4<f32>, color: vec4<f32> }
+            struct Large { transforms: [mat4x4<f32>; 2], color: vec4<f32> }
+            layout Material { push_constant draw: Draw, binding(0) push_constant large: Large, uniform tint: vec4<f32> }
+            stage(fragment) fn main(material: Material) {}
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::PushConstantTooLarge { size: 144, limit: 128, .. },
+            TypeCheckError::UnexpectedBindingIndex { binding: "push_constant", .. },
+            TypeCheckError::MultiplePushConstants { .. },
+        ]), "{:?}", errors);
+
+        let manifest = "[package]\nname = \"shaders\"\n\n[limits]\nmax_push_constant_size = 256\n";
+        let errors = type_check_files(&[
+            ("src/lib.skysl", "struct Large { transforms: [mat4x4<f32>; 2], color: vec4<f32> } layout Draw { push_constant large: Large }"),
+            ("skysl.toml", manifest),
+        ]);
+        assert_eq!(errors, vec![]);
+
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let file = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            layout Draw { push_constant color: vec3<f32>, uniform tint: vec4<f32> }
+            stage(fragment) fn main(draw: Draw) {}
+        ".to_string())).unwrap();
+        assert_eq!(db.get_typed_hir(file).errors, vec![]);
+
+        let reflection = crate::workspace::reflect_package(&db, file);
+        let bindings: Vec<_> = reflection.layouts[0].bindings.iter().map(|binding| (binding.kind, binding.binding)).collect();
+        assert_eq!(bindings, vec![("push_constant", None), ("uniform", Some(0))]);
+
+        let push_constant = reflection.entry_points[0].push_constant.as_ref().unwrap();
+        assert_eq!((push_constant.member.as_str(), push_constant.size), ("color", 12));
+    }
+
+    #[test]
+    fn it_checks_storage_access_and_atomics() {
+        let errors = type_check("
+            struct Counter { count: atomic<u32> }
+            layout Particles { storage(read) positions: [vec4<f32>], storage(read) counter: Counter, uniform total: atomic<u32> }
+            layout Tiles { workgroup tiles: [u32; 64] }
+            stage(compute) fn simulate(particles: Particles) {
+                particles.positions[0] = vec4(0.0);
+                atomic_add(particles.counter.count, 1);
+                let count = particles.counter.count;
+            }
+            stage(fragment) fn main(tiles: Tiles) {}
+        ");
+        assert!(matches!(errors.as_slice(), [
+            TypeCheckError::AtomicOutsideSharedMemory { .. },
+            TypeCheckError::ReadOnlyBinding { .. },
+            TypeCheckError::ReadOnlyBinding { .. },
+            TypeCheckError::AtomicOutsideSharedMemory { .. },
+            TypeCheckError::WorkgroupOutsideCompute { .. },
+        ]), "{:?}", errors);
+        assert_eq!(errors[1].to_string(), "`Particles::positions` is a read-only `storage(read)` binding and can't be written");
+
+        let errors = type_check("
+            struct Counter { count: atomic<u32>, max: atomic<i32> }
+            layout Particles { storage(read_write) positions: [vec4<f32>], storage counter: Counter }
+            layout Tiles { workgroup tiles: [u32; 64] }
+            stage(compute) fn main(particles: Particles, tiles: Tiles) {
+                tiles.tiles[0] = atomic_add(particles.counter.count, 1);
+                particles.positions[1] = vec4(1.0);
+                let previous = atomic_compare_exchange(particles.counter.max, 0, 1);
+            }
+        ");
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn it_reflects_layouts_and_entry_points() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let main = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            mod lights;
+            use lights::Lighting;
+            struct Camera { view: mat4x4<f32>, exposure: f32 }
+            layout Scene { binding(0) uniform camera: Camera, binding(1) image albedo: texture_2d<f32> }
+            stage(fragment) fn main(uv: vec2<f32>, scene: Scene, lighting: Lighting) -> vec4<f32> { vec4(uv, scene.camera.exposure, lighting.intensity) }
+        ".to_string())).unwrap();
+        insert_file(&mut db, Utf8Path::new("lights.skysl"), Arc::new("
+            pub layout Lighting { binding(2) storage intensity: f32 }
+        ".to_string())).unwrap();
+
+        let reflection = crate::workspace::reflect_package(&db, main);
+        let bindings = reflection.layouts.iter()
+            .flat_map(|layout| layout.bindings.iter().map(move |binding| (layout.module.as_str(), binding.name.as_str(), binding.binding, binding.kind)))
+            .collect::<Vec<_>>();
+        assert_eq!(bindings, vec![
+            ("package", "camera", Some(0), "uniform"),
+            ("package", "albedo", Some(1), "image"),
+            ("package::lights", "intensity", Some(2), "storage"),
+        ]);
+
+        let camera = reflection.layouts[0].bindings[0].memory.as_ref().unwrap();
+        assert_eq!((camera.rules, camera.size, camera.members[1].offset), ("std140", 80, 64));
+        assert_eq!(reflection.layouts[0].bindings[1].memory, None);
+
+        let entry_point = &reflection.entry_points[0];
+        assert_eq!((entry_point.name.as_str(), entry_point.stage), ("main", "fragment"));
+        assert_eq!(entry_point.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), vec!["uv"]);
+        assert_eq!(entry_point.output.as_ref().map(|output| output.name.as_str()), Some("vec4<f32>"));
+        assert_eq!(entry_point.layouts.iter().map(|layout| layout.module.as_str()).collect::<Vec<_>>(), vec!["package", "package::lights"]);
+    }
+
+    #[test]
+    fn it_generates_rust_types() {
+        let mut db = CompilerDatabase::default();
+        initialize_fs(&mut db);
+        let main = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            mod lights;
+            struct Camera { position: vec3<f32>, exposure: f32, weights: [f32; 2], light: lights::Light }
+            struct Flags { enabled: bool }
+            layout MyScene { binding(0) uniform camera: Camera, binding(3) storage point_lights: [lights::Light] }
+        ".to_string())).unwrap();
+        insert_file(&mut db, Utf8Path::new("lights.skysl"), Arc::new("
+            pub struct Light { color: vec3<f32> }
+        ".to_string())).unwrap();
+
+        let rust = crate::workspace::generate_rust_types(&db, main, &[LayoutRules::Std140]);
+        assert!(rust.contains("pub const MY_SCENE_CAMERA_BINDING: u32 = 0;"));
+        assert!(rust.contains("pub const MY_SCENE_POINT_LIGHTS_BINDING: u32 = 3;"));
+        assert!(rust.contains("    pub weights: [[f32; 4]; 2],\n"));
+        assert!(rust.contains("        pub light: lights::Light,\n"));
+        assert!(rust.contains("const _: () = assert!(std::mem::size_of::<Camera>() == 64);"));
+        assert!(rust.contains("#[repr(C, align(16))]\n        #[derive(Debug, Clone, Copy, PartialEq)]\n        pub struct Light {\n            pub color: [f32; 3],\n            pub _pad0: [u8; 4],\n"));
+        assert!(rust.contains("// `Flags` is skipped, it has no std140 layout"));
+
+        let main = insert_file(&mut db, Utf8Path::new("main.skysl"), Arc::new("
+            struct Node { self: f32, super: u32, move: f32 }
+            layout Graph { binding(0) uniform crate: Node }
+        ".to_string())).unwrap();
+        let rust = crate::workspace::generate_rust_types(&db, main, &[LayoutRules::Std430]);
+        assert!(rust.contains("pub self_: f32,"), "{}", rust);
+        assert!(rust.contains("pub r#move: f32,"), "{}", rust);
+        syn::parse_file(&rust).unwrap_or_else(|error| panic!("{}\n{}", error, rust));
+    }
 }
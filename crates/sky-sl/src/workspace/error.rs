@@ -1,8 +1,18 @@
 use crate::fs::FileSystemError;
+use camino::Utf8PathBuf;
 use thiserror::*;
 
 #[derive(Debug, Error)]
 pub enum CompileError {
     #[error("File system error: {0}")]
     FileSystemError(#[from] FileSystemError),
+
+    #[error("Could not read `{path}`: {error}")]
+    Io { path: Utf8PathBuf, error: std::io::Error },
+
+    #[error("`{path}` is not an entry point")]
+    UnknownEntryPoint { path: String },
+
+    #[error("`{path}` is a {found} entry point, expected a {expected} entry point")]
+    UnexpectedStage { path: String, expected: &'static str, found: &'static str },
 }
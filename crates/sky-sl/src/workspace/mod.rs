@@ -1,6 +1,10 @@
 mod db;
 mod error;
+mod reflection;
+mod rust_types;
 mod workspace;
 
 pub use error::*;
+pub use reflection::*;
+pub use rust_types::*;
 pub use workspace::*;
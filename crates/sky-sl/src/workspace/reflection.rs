@@ -0,0 +1,501 @@
+use crate::fs::FileId;
+use crate::intern::Name;
+use crate::hir::{const_eval::ConstValue, memory_layout::{type_layout, LayoutRules}, pipeline::{EntryPointId, InterfaceVariable, PipelineError}, symbol::SymbolKind, type_check::{assign_set_indices, ScalarTy, StructTy, Ty}, typed, untyped::{AccessMode, BindingKind}, HirDatabase};
+use crate::syn::cst::{parse_number_literal, Literal, LiteralValue};
+use serde::Serialize;
+use std::collections::HashMap;
+use thiserror::*;
+
+/// A pipeline overridable constant, declared with `override(id) NAME: ty = default;`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OverrideConstant {
+    pub id: u32,
+    pub name: String,
+    pub ty: ScalarTy,
+
+    /// The default value, `None` if it could not be evaluated
+    pub default: Option<ConstValue>,
+}
+
+impl OverrideConstant {
+    /// Parses a value for this constant, e.g. `2`, `-0.5`, `true` or `4u`
+    pub fn parse_value(&self, text: &str) -> Result<ConstValue, OverrideError> {
+        let (negated, literal) = match text.strip_prefix('-') {
+            Some(literal) => (true, literal),
+            None => (false, text),
+        };
+
+        let literal = match literal {
+            "true" => Ok(Literal { value: LiteralValue::Boolean(true), suffix: None }),
+            "false" => Ok(Literal { value: LiteralValue::Boolean(false), suffix: None }),
+            literal => parse_number_literal(literal),
+        };
+
+        literal.ok()
+            .and_then(|literal| ConstValue::from_literal(literal, negated, self.ty))
+            .ok_or_else(|| OverrideError::InvalidValue {
+                name: self.name.clone(),
+                value: text.to_string(),
+                ty: self.ty.display(),
+            })
+    }
+}
+
+/// Finds an overridable constant by its name or id
+pub fn find_override<'a>(overrides: &'a [OverrideConstant], key: &str) -> Result<&'a OverrideConstant, OverrideError> {
+    let id = key.parse::<u32>().ok();
+    overrides.iter()
+        .find(|constant| constant.name == key || Some(constant.id) == id)
+        .ok_or_else(|| OverrideError::UnknownOverride { key: key.to_string() })
+}
+
+#[derive(Debug, Error, Eq, PartialEq, Clone)]
+pub enum OverrideError {
+    #[error("No overridable constant is named or has the id `{key}`")]
+    UnknownOverride { key: String },
+
+    #[error("`{value}` is not a valid `{ty}` value for `{name}`")]
+    InvalidValue { name: String, value: String, ty: String },
+}
+
+/// The layouts and entry points of a package, for tools that need to know the bindings without parsing shader source
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Reflection {
+    pub layouts: Vec<LayoutReflection>,
+    pub entry_points: Vec<EntryPointReflection>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct LayoutReflection {
+    /// The path of the declaring module, e.g. `package::lights`
+    pub module: String,
+    pub name: String,
+
+    /// The explicit set index in `layout(n)`, entry points assign one to layouts without it
+    pub set: Option<u32>,
+    pub bindings: Vec<BindingReflection>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct BindingReflection {
+    pub name: String,
+
+    /// The evaluated `binding(n)` or the automatically assigned index, `None` if it could not be evaluated
+    pub binding: Option<u32>,
+
+    /// Whether the binding index was assigned automatically
+    pub automatic: bool,
+
+    /// The binding kind keyword, e.g. `uniform`
+    pub kind: &'static str,
+
+    /// `read` or `read_write` for storage bindings
+    pub access: Option<&'static str>,
+    pub ty: String,
+
+    /// The memory layout of uniform and storage bindings, computed with their default rules
+    pub memory: Option<MemoryReflection>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct MemoryReflection {
+    /// `std140`, `std430` or `scalar`
+    pub rules: &'static str,
+    pub size: u32,
+    pub alignment: u32,
+    pub stride: Option<u32>,
+
+    /// The members of a struct or an array of structs
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<MemberReflection>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct MemberReflection {
+    pub name: String,
+    pub ty: String,
+    pub offset: u32,
+    pub size: u32,
+    pub alignment: u32,
+    pub stride: Option<u32>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<MemberReflection>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct EntryPointReflection {
+    pub module: String,
+    pub name: String,
+
+    /// `vertex`, `fragment` or `compute`
+    pub stage: &'static str,
+
+    /// The arguments of the entry point, except for layouts
+    pub inputs: Vec<InterfaceReflection>,
+
+    /// The return type, `None` if the entry point returns nothing
+    pub output: Option<TypeReflection>,
+
+    /// The layouts passed to the entry point
+    pub layouts: Vec<LayoutUsage>,
+
+    /// The push constant of one of the layouts, an entry point can only use one
+    pub push_constant: Option<PushConstantUsage>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct InterfaceReflection {
+    pub name: String,
+    pub ty: TypeReflection,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct TypeReflection {
+    pub name: String,
+
+    /// The members of a struct
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<InterfaceReflection>,
+}
+
+/// Refers to a layout of the reflection by its module and name
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct LayoutUsage {
+    pub module: String,
+    pub name: String,
+
+    /// The set index the layout is bound to in this entry point
+    pub set: u32,
+}
+
+/// Refers to the push constant member of a layout in the reflection
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct PushConstantUsage {
+    pub module: String,
+    pub layout: String,
+    pub member: String,
+
+    /// The size in bytes, push constants are laid out with the std430 rules
+    pub size: u32,
+}
+
+/// The merged bindings of entry points sharing a pipeline
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct PipelineReflection {
+    /// Ordered by set and binding index
+    pub bindings: Vec<PipelineBindingReflection>,
+
+    /// Bindings the entry points disagree on
+    pub errors: Vec<PipelineError>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct PipelineBindingReflection {
+    pub set: u32,
+    pub binding: u32,
+
+    /// The binding kind keyword, e.g. `uniform`
+    pub kind: &'static str,
+
+    /// `read_write` for storage bindings any of the entry points may write, otherwise `read`
+    pub access: Option<&'static str>,
+    pub ty: String,
+
+    /// The layout declaring the binding, the first one if the entry points use different layouts
+    pub layout: LayoutUsage,
+    pub member: String,
+}
+
+/// The values passed from a vertex to a fragment entry point
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct StageInterfaceReflection {
+    /// The members of the struct returned by the vertex entry point
+    pub outputs: Vec<InterfaceVariableReflection>,
+
+    /// The members of the struct arguments and the other non-layout arguments of the fragment entry point
+    pub inputs: Vec<InterfaceVariableReflection>,
+
+    /// Fragment inputs that don't match the vertex outputs
+    pub errors: Vec<InterfaceMismatchReflection>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct InterfaceVariableReflection {
+    pub name: String,
+    pub location: u32,
+
+    /// Whether the location was assigned automatically
+    pub automatic: bool,
+    pub ty: String,
+
+    /// `perspective`, `linear` or `flat`
+    pub interpolation: &'static str,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct InterfaceMismatchReflection {
+    /// The path of the module declaring the input, e.g. `package::lights`
+    pub module: String,
+
+    /// The struct declaring the input, `None` for arguments of the fragment entry point
+    pub structure: Option<String>,
+    pub member: String,
+
+    /// The one-based line and column of the member
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+}
+
+/// Reflects the module in `root` and all modules declared below it
+pub(crate) fn reflect_package(db: &dyn HirDatabase, root: FileId) -> Reflection {
+    let mut modules = Vec::new();
+    collect_modules(db, root, "package".to_string(), &mut modules);
+    let module_paths: HashMap<FileId, String> = modules.iter().cloned().collect();
+
+    let mut reflection = Reflection {
+        layouts: Vec::new(),
+        entry_points: Vec::new(),
+    };
+
+    for (file, module) in &modules {
+        for item in db.get_typed_hir(*file).items {
+            match item {
+                typed::ItemKind::Layout(layout) => reflection.layouts.push(reflect_layout(db, &layout, module)),
+                typed::ItemKind::Function(function) => {
+                    if let Some(entry_point) = reflect_entry_point(db, &function, module, &module_paths) {
+                        reflection.entry_points.push(entry_point);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+
+    reflection
+}
+
+pub(crate) fn reflect_pipeline_layout(db: &dyn HirDatabase, root: FileId, entry_points: Vec<EntryPointId>) -> PipelineReflection {
+    let mut modules = Vec::new();
+    collect_modules(db, root, "package".to_string(), &mut modules);
+    let module_paths: HashMap<FileId, String> = modules.into_iter().collect();
+
+    let pipeline = db.pipeline_layout(entry_points);
+    let bindings = pipeline.bindings.iter()
+        .map(|binding| PipelineBindingReflection {
+            set: binding.set,
+            binding: binding.binding,
+            kind: binding.kind.keyword(),
+            access: binding.access.map(AccessMode::name),
+            ty: binding.ty.display(db),
+            layout: LayoutUsage {
+                module: module_paths.get(&binding.layout.file).cloned().unwrap_or_default(),
+                name: db.lookup_intern_name(binding.layout.name),
+                set: binding.set,
+            },
+            member: db.lookup_intern_name(binding.member),
+        })
+        .collect();
+
+    PipelineReflection {
+        bindings,
+        errors: pipeline.errors,
+    }
+}
+
+pub(crate) fn reflect_stage_interface(db: &dyn HirDatabase, root: FileId, vertex: EntryPointId, fragment: EntryPointId) -> StageInterfaceReflection {
+    let mut modules = Vec::new();
+    collect_modules(db, root, "package".to_string(), &mut modules);
+    let module_paths: HashMap<FileId, String> = modules.into_iter().collect();
+
+    let interface = db.stage_interface(vertex, fragment);
+    let errors = interface.errors.iter()
+        .map(|mismatch| {
+            let file = mismatch.input.file;
+            let position = db.get_line_index(file).find_position(mismatch.input.span.start());
+
+            InterfaceMismatchReflection {
+                module: module_paths.get(&file).cloned().unwrap_or_default(),
+                structure: mismatch.input.structure.map(|structure| db.lookup_intern_name(structure.name)),
+                member: db.lookup_intern_name(mismatch.input.name),
+                line: position.line + 1,
+                column: position.column + 1,
+                message: mismatch.error.to_string(),
+            }
+        })
+        .collect();
+
+    StageInterfaceReflection {
+        outputs: interface.outputs.iter().map(|variable| reflect_interface_variable(db, variable)).collect(),
+        inputs: interface.inputs.iter().map(|variable| reflect_interface_variable(db, variable)).collect(),
+        errors,
+    }
+}
+
+fn reflect_interface_variable(db: &dyn HirDatabase, variable: &InterfaceVariable) -> InterfaceVariableReflection {
+    InterfaceVariableReflection {
+        name: db.lookup_intern_name(variable.name),
+        location: variable.location,
+        automatic: variable.automatic_location,
+        ty: variable.ty.display(db),
+        interpolation: variable.interpolation.name(),
+    }
+}
+
+/// Lists the module in `file` and all modules declared below it with their paths, e.g. `package::lights`
+pub(crate) fn collect_modules(db: &dyn HirDatabase, file: FileId, path: String, modules: &mut Vec<(FileId, String)>) {
+    modules.push((file, path.clone()));
+
+    for symbol in db.get_symbols(file).iter().filter(|symbol| symbol.kind == SymbolKind::Module) {
+        if let Some(child) = db.child_module(file, symbol.name) {
+            collect_modules(db, child, format!("{}::{}", path, db.lookup_intern_name(symbol.name)), modules);
+        }
+    }
+}
+
+fn reflect_layout(db: &dyn HirDatabase, layout: &typed::LayoutKind, module: &str) -> LayoutReflection {
+    let bindings = layout.members.iter()
+        .map(|member| {
+            let name = db.lookup_intern_name(member.name);
+
+            // errors are discarded, they are reported when type checking the layout
+            let memory = LayoutRules::for_binding(member.binding_kind).map(|rules| {
+                let layout = type_layout(db, member.ty, rules, &name, &mut Vec::new());
+                MemoryReflection {
+                    rules: rules.name(),
+                    size: layout.size,
+                    alignment: layout.alignment,
+                    stride: layout.stride,
+                    members: reflect_members(db, member.ty, rules),
+                }
+            });
+
+            BindingReflection {
+                name,
+                binding: member.binding_index,
+                automatic: member.automatic_binding,
+                kind: member.binding_kind.keyword(),
+                access: member.access.map(AccessMode::name),
+                ty: member.ty.display(db),
+                memory,
+            }
+        })
+        .collect();
+
+    LayoutReflection {
+        module: module.to_string(),
+        name: db.lookup_intern_name(layout.name),
+        set: layout.set_index,
+        bindings,
+    }
+}
+
+/// The members of a struct, or of the struct elements of an array
+fn reflect_members(db: &dyn HirDatabase, ty: Ty, rules: LayoutRules) -> Vec<MemberReflection> {
+    let struct_ty = match ty {
+        Ty::Struct(struct_ty) => struct_ty,
+        Ty::Array(array_ty) => return reflect_members(db, db.lookup_intern_array_ty(array_ty).element, rules),
+        _ => return Vec::new(),
+    };
+
+    db.struct_layout(struct_ty, rules).members.iter()
+        .map(|member| MemberReflection {
+            name: db.lookup_intern_name(member.name),
+            ty: member.ty.display(db),
+            offset: member.offset,
+            size: member.layout.size,
+            alignment: member.layout.alignment,
+            stride: member.layout.stride,
+            members: reflect_members(db, member.ty, rules),
+        })
+        .collect()
+}
+
+fn reflect_entry_point(db: &dyn HirDatabase, function: &typed::FunctionKind, module: &str, module_paths: &HashMap<FileId, String>) -> Option<EntryPointReflection> {
+    let stage = function.stage?;
+
+    let mut inputs = Vec::new();
+    let mut layout_tys = Vec::new();
+    for argument in &function.signature.arguments {
+        match argument.ty {
+            Ty::Layout(layout_ty) => layout_tys.push(layout_ty),
+            ty => inputs.push(InterfaceReflection {
+                name: db.lookup_intern_name(argument.name),
+                ty: reflect_type(db, ty),
+            }),
+        }
+    }
+
+    let layouts = layout_tys.iter()
+        .zip(assign_set_indices(db, &layout_tys))
+        .map(|(layout_ty, set)| LayoutUsage {
+            module: module_paths.get(&layout_ty.file).cloned().unwrap_or_default(),
+            name: db.lookup_intern_name(layout_ty.name),
+            set,
+        })
+        .collect();
+
+    let output = match function.signature.return_type {
+        Ty::Unit => None,
+        ty => Some(reflect_type(db, ty)),
+    };
+
+    let push_constant = layout_tys.iter().find_map(|layout_ty| {
+        let member = db.get_typed_hir(layout_ty.file).items.into_iter()
+            .find_map(|item| match item {
+                typed::ItemKind::Layout(layout) if layout.name == layout_ty.name => Some(layout.members),
+                _ => None,
+            })?
+            .into_iter()
+            .find(|member| member.binding_kind == BindingKind::PushConstant)?;
+
+        let name = db.lookup_intern_name(member.name);
+        let layout = type_layout(db, member.ty, LayoutRules::Std430, &name, &mut Vec::new());
+
+        Some(PushConstantUsage {
+            module: module_paths.get(&layout_ty.file).cloned().unwrap_or_default(),
+            layout: db.lookup_intern_name(layout_ty.name),
+            member: name,
+            size: layout.size,
+        })
+    });
+
+    Some(EntryPointReflection {
+        module: module.to_string(),
+        name: db.lookup_intern_name(function.signature.name),
+        stage: stage.name(),
+        inputs,
+        output,
+        layouts,
+        push_constant,
+    })
+}
+
+fn reflect_type(db: &dyn HirDatabase, ty: Ty) -> TypeReflection {
+    let members = match ty {
+        Ty::Struct(struct_ty) => struct_members(db, struct_ty).into_iter()
+            .map(|(name, ty)| InterfaceReflection {
+                name: db.lookup_intern_name(name),
+                ty: reflect_type(db, ty),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    TypeReflection {
+        name: ty.display(db),
+        members,
+    }
+}
+
+fn struct_members(db: &dyn HirDatabase, struct_ty: StructTy) -> Vec<(Name, Ty)> {
+    db.get_typed_hir(struct_ty.file).items.iter()
+        .find_map(|item| match item {
+            typed::ItemKind::Struct(structure) if structure.name == struct_ty.name => {
+                Some(structure.members.iter().map(|member| (member.name, member.ty)).collect())
+            },
+            _ => None,
+        })
+        .unwrap_or_default()
+}
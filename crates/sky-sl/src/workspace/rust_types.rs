@@ -0,0 +1,346 @@
+use super::collect_modules;
+use crate::fs::FileId;
+use crate::hir::{memory_layout::{type_layout, LayoutRules, MemoryLayoutError, StructLayout, TypeLayout}, type_check::{ScalarTy, StructTy, Ty}, typed, HirDatabase};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Generates Rust source mirroring the structs and layouts of the module in `root` and all modules declared below it.
+///
+/// For every rule set a module like `std140` holds a `#[repr(C)]` struct for each sky-sl struct, with explicit
+/// padding fields so the Rust layout matches the shader layout and a compile time check of the size.
+/// The binding index of every layout member is a constant like `SCENE_CAMERA_BINDING`, an explicit set index one like `SCENE_SET`.
+/// Structs without a memory layout, e.g. containing a `bool`, are skipped.
+pub(crate) fn generate_rust_types(db: &dyn HirDatabase, root: FileId, rules: &[LayoutRules]) -> String {
+    let modules = module_paths(db, root);
+    let module_paths: HashMap<FileId, Vec<String>> = modules.iter().cloned().collect();
+
+    let mut output = String::new();
+    writeln!(output, "// Generated by sky-sl, do not edit").unwrap();
+
+    let mut bindings = ModuleWriter::new(&mut output, 0);
+    for (file, path) in &modules {
+        bindings.enter(path);
+        for item in db.get_typed_hir(*file).items {
+            if let typed::ItemKind::Layout(layout) = item {
+                write_binding_constants(db, &layout, &mut bindings);
+            }
+        }
+    }
+    bindings.finish();
+
+    for rules in rules {
+        writeln!(output, "\npub mod {} {{", rules.name()).unwrap();
+
+        let mut structs = ModuleWriter::new(&mut output, 1);
+        for (file, path) in &modules {
+            structs.enter(path);
+            for item in db.get_typed_hir(*file).items {
+                if let typed::ItemKind::Struct(structure) = item {
+                    let struct_ty = StructTy { file: *file, name: structure.name };
+                    write_struct(db, struct_ty, *rules, path.len(), &module_paths, &mut structs);
+                }
+            }
+        }
+        structs.finish();
+
+        writeln!(output, "}}").unwrap();
+    }
+
+    output
+}
+
+/// The Rust type `generate_rust_types` generates for a sky-sl struct, used to verify hand-written Rust types
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RustStruct {
+    pub name: String,
+    pub size: u32,
+    pub alignment: u32,
+    pub fields: Vec<RustField>,
+
+    /// Members that can't be laid out, a struct with errors has no Rust type
+    pub errors: Vec<MemoryLayoutError>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RustField {
+    pub name: String,
+    pub offset: u32,
+    pub size: u32,
+
+    /// The type as written in sky-sl, e.g. `vec3<f32>`
+    pub sky_sl_ty: String,
+
+    /// The type as written in Rust, e.g. `[f32; 3]`, with struct paths relative to the package.
+    /// `None` for runtime-sized arrays, which are not part of the Rust type
+    pub rust_ty: Option<String>,
+}
+
+/// Describes the Rust type of a struct in the module in `root` or any module declared below it
+pub(crate) fn rust_struct(db: &dyn HirDatabase, root: FileId, struct_ty: StructTy, rules: LayoutRules) -> RustStruct {
+    let module_paths: HashMap<FileId, Vec<String>> = module_paths(db, root).into_iter().collect();
+    let layout = db.struct_layout(struct_ty, rules);
+
+    let fields = layout.members.iter()
+        .map(|member| RustField {
+            name: db.lookup_intern_name(member.name),
+            offset: member.offset,
+            size: member.layout.size,
+            sky_sl_ty: member.ty.display(db),
+            rust_ty: rust_type(db, member.ty, member.layout, rules, 0, &module_paths),
+        })
+        .collect();
+
+    RustStruct {
+        name: db.lookup_intern_name(struct_ty.name),
+        size: layout.size,
+        alignment: layout.alignment,
+        fields,
+        errors: layout.errors,
+    }
+}
+
+/// Lists the modules below `root` with their path below the package, e.g. `["lights"]` for `package::lights`
+fn module_paths(db: &dyn HirDatabase, root: FileId) -> Vec<(FileId, Vec<String>)> {
+    let mut modules = Vec::new();
+    collect_modules(db, root, "package".to_string(), &mut modules);
+
+    modules.into_iter()
+        .map(|(file, path)| (file, path.split("::").skip(1).map(str::to_string).collect()))
+        .collect()
+}
+
+/// Writes lines into nested `pub mod` blocks, modules are only opened once a line is written into them
+struct ModuleWriter<'a> {
+    output: &'a mut String,
+    base_indent: usize,
+    path: Vec<String>,
+    target: Vec<String>,
+}
+
+impl<'a> ModuleWriter<'a> {
+    fn new(output: &'a mut String, base_indent: usize) -> Self {
+        Self {
+            output,
+            base_indent,
+            path: Vec::new(),
+            target: Vec::new(),
+        }
+    }
+
+    fn enter(&mut self, path: &[String]) {
+        self.target = path.to_vec();
+    }
+
+    fn line(&mut self, line: &str) {
+        let common = self.path.iter().zip(&self.target).take_while(|(current, next)| current == next).count();
+
+        while self.path.len() > common {
+            self.path.pop();
+            self.write("}");
+        }
+
+        while self.path.len() < self.target.len() {
+            let segment = self.target[self.path.len()].clone();
+            self.write(&format!("pub mod {} {{", rust_identifier(&segment)));
+            self.path.push(segment);
+        }
+
+        self.write(line);
+    }
+
+    /// Closes all open modules
+    fn finish(mut self) {
+        while self.path.pop().is_some() {
+            self.write("}");
+        }
+    }
+
+    fn write(&mut self, line: &str) {
+        match line.is_empty() {
+            true => writeln!(self.output).unwrap(),
+            false => writeln!(self.output, "{}{}", "    ".repeat(self.base_indent + self.path.len()), line).unwrap(),
+        }
+    }
+}
+
+fn write_binding_constants(db: &dyn HirDatabase, layout: &typed::LayoutKind, writer: &mut ModuleWriter) {
+    let layout_name = screaming_snake_case(&db.lookup_intern_name(layout.name));
+
+    if let Some(set_index) = layout.set_index {
+        writer.line(&format!("pub const {}_SET: u32 = {};", layout_name, set_index));
+    }
+
+    for member in &layout.members {
+        // the binding index is reported when type checking the layout if it could not be evaluated
+        if let Some(binding_index) = member.binding_index {
+            let member_name = screaming_snake_case(&db.lookup_intern_name(member.name));
+            writer.line(&format!("pub const {}_{}_BINDING: u32 = {};", layout_name, member_name, binding_index));
+        }
+    }
+}
+
+fn write_struct(db: &dyn HirDatabase, struct_ty: StructTy, rules: LayoutRules, depth: usize, module_paths: &HashMap<FileId, Vec<String>>, writer: &mut ModuleWriter) {
+    let name = db.lookup_intern_name(struct_ty.name);
+    let layout = db.struct_layout(struct_ty, rules);
+
+    if !layout.errors.is_empty() {
+        writer.line(&format!("// `{}` is skipped, it has no {} layout", name, rules.name()));
+        return;
+    }
+
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    let mut padding = 0;
+    let mut pad = |fields: &mut Vec<String>, size: u32| {
+        fields.push(format!("pub _pad{}: [u8; {}],", padding, size));
+        padding += 1;
+    };
+
+    for member in &layout.members {
+        if member.offset > offset {
+            pad(&mut fields, member.offset - offset);
+        }
+
+        let member_name = rust_identifier(&db.lookup_intern_name(member.name));
+        match rust_type(db, member.ty, member.layout, rules, depth, module_paths) {
+            Some(ty) => fields.push(format!("pub {}: {},", member_name, ty)),
+            // a runtime-sized array has no size, it is only known when binding the buffer
+            None => fields.push(format!("// followed by `{}: {}` with a stride of {} bytes", member_name, member.ty.display(db), member.layout.stride.unwrap_or(0))),
+        }
+
+        offset = member.offset + member.layout.size;
+    }
+
+    if layout.size > offset {
+        pad(&mut fields, layout.size - offset);
+    }
+
+    let repr = match rust_alignment(db, &layout, rules) < layout.alignment {
+        true => format!("#[repr(C, align({}))]", layout.alignment),
+        false => "#[repr(C)]".to_string(),
+    };
+
+    writer.line(&repr);
+    writer.line("#[derive(Debug, Clone, Copy, PartialEq)]");
+    writer.line(&format!("pub struct {} {{", name));
+    for field in fields {
+        writer.line(&format!("    {}", field));
+    }
+    writer.line("}");
+    writer.line(&format!("const _: () = assert!(std::mem::size_of::<{}>() == {});", name, layout.size));
+    writer.line("");
+}
+
+/// The Rust type of a struct member, `None` for runtime-sized arrays.
+/// Vectors and matrix columns are arrays, padded with extra components if their stride requires it.
+fn rust_type(db: &dyn HirDatabase, ty: Ty, layout: TypeLayout, rules: LayoutRules, depth: usize, module_paths: &HashMap<FileId, Vec<String>>) -> Option<String> {
+    let padded = |scalar: ScalarTy, stride: u32, count: u32| {
+        format!("[[{}; {}]; {}]", rust_scalar(scalar), stride / scalar_size(scalar), count)
+    };
+
+    let ty = match ty {
+        // the host initializes and reads atomics as plain integers
+        Ty::Scalar(scalar) | Ty::Atomic(scalar) => rust_scalar(scalar).to_string(),
+        Ty::Enum(_) => "u32".to_string(),
+        Ty::Vector(scalar, components) => format!("[{}; {}]", rust_scalar(scalar), components),
+        Ty::Matrix(scalar, columns, _) => padded(scalar, layout.stride?, columns as u32),
+        Ty::Struct(struct_ty) => {
+            let mut path = vec!["super".to_string(); depth];
+            path.extend(module_paths.get(&struct_ty.file)?.iter().map(|segment| rust_identifier(segment)));
+            path.push(db.lookup_intern_name(struct_ty.name));
+            path.join("::")
+        },
+        Ty::Array(array_ty) => {
+            let array = db.lookup_intern_array_ty(array_ty);
+            let length = array.length?;
+            let stride = layout.stride?;
+            let element = type_layout(db, array.element, rules, "", &mut Vec::new());
+
+            match array.element {
+                // e.g. the elements of a `[f32; 4]` are 16 bytes apart in std140
+                Ty::Scalar(scalar) | Ty::Vector(scalar, _) if stride != element.size => padded(scalar, stride, length),
+                _ => format!("[{}; {}]", rust_type(db, array.element, element, rules, depth, module_paths)?, length),
+            }
+        },
+        Ty::Unit | Ty::Texture(_) | Ty::Sampler { .. } | Ty::Layout(_) | Ty::Generic(_) | Ty::Error => return None,
+    };
+
+    Some(ty)
+}
+
+/// The alignment Rust gives the generated struct without an `align` attribute
+fn rust_alignment(db: &dyn HirDatabase, layout: &StructLayout, rules: LayoutRules) -> u32 {
+    fn alignment(db: &dyn HirDatabase, ty: Ty, rules: LayoutRules) -> u32 {
+        match ty {
+            Ty::Scalar(scalar) | Ty::Vector(scalar, _) | Ty::Matrix(scalar, _, _) | Ty::Atomic(scalar) => scalar_size(scalar),
+            Ty::Enum(_) => 4,
+            Ty::Struct(struct_ty) => db.struct_layout(struct_ty, rules).alignment,
+            Ty::Array(array_ty) => match db.lookup_intern_array_ty(array_ty) {
+                array if array.length.is_some() => alignment(db, array.element, rules),
+                _ => 1,
+            },
+            _ => 1,
+        }
+    }
+
+    layout.members.iter().map(|member| alignment(db, member.ty, rules)).max().unwrap_or(1)
+}
+
+/// Half precision floats have no Rust type, their bits are stored as `u16`
+fn rust_scalar(scalar: ScalarTy) -> &'static str {
+    match scalar {
+        ScalarTy::Int { signed: true, width: 8 } => "i8",
+        ScalarTy::Int { signed: true, width: 16 } => "i16",
+        ScalarTy::Int { signed: true, width: 64 } => "i64",
+        ScalarTy::Int { signed: true, .. } => "i32",
+        ScalarTy::Int { signed: false, width: 8 } => "u8",
+        ScalarTy::Int { signed: false, width: 16 } | ScalarTy::Float { width: 16 } => "u16",
+        ScalarTy::Int { signed: false, width: 64 } => "u64",
+        ScalarTy::Int { signed: false, .. } => "u32",
+        ScalarTy::Float { width: 64 } => "f64",
+        ScalarTy::Float { .. } => "f32",
+        ScalarTy::Bool => "bool",
+    }
+}
+
+fn scalar_size(scalar: ScalarTy) -> u32 {
+    match scalar {
+        ScalarTy::Int { width, .. } | ScalarTy::Float { width } => width as u32 / 8,
+        ScalarTy::Bool => 1,
+    }
+}
+
+/// Escapes names that are keywords in Rust but not in sky-sl, e.g. `r#move`. Path keywords can't be raw identifiers,
+/// they get a trailing underscore instead, e.g. `self_`
+fn rust_identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "continue", "do", "dyn", "extern", "final", "in", "macro",
+        "move", "priv", "ref", "return", "static", "trait", "try", "unsafe", "unsized", "virtual", "where", "yield",
+    ];
+    const PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+    if PATH_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Converts names like `MyBindgroup` or `point_lights` to `MY_BINDGROUP` and `POINT_LIGHTS`
+fn screaming_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous_lowercase = false;
+
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lowercase {
+            result.push('_');
+        }
+
+        previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        result.extend(c.to_uppercase());
+    }
+
+    result
+}
@@ -1,4 +1,4 @@
-use super::{db::CompilerDatabase, CompileError};
+use super::{collect_modules, db::CompilerDatabase, generate_rust_types, reflect_package, reflect_pipeline_layout, reflect_stage_interface, rust_struct, CompileError, PipelineReflection, RustStruct, OverrideConstant, Reflection, StageInterfaceReflection};
 use crate::fs::{initialize_fs, insert_file, lookup_file, remove_file, FileId, FileSystemError};
 use crate::intern::{InternDatabase, Name};
 use crate::package::{Package, PackageDatabase};
@@ -24,6 +24,40 @@ impl Workspace {
         Self { root_path, db }
     }
 
+    /// Creates a workspace containing all shader files below `root_path`, e.g. to generate Rust types in a build script
+    pub fn open(root_path: Utf8PathBuf) -> Result<Self, CompileError> {
+        let mut workspace = Self::create(root_path.clone());
+        workspace.insert_directory(&root_path)?;
+        Ok(workspace)
+    }
+
+    /// Adds all `.skysl` files in a directory and its subdirectories, returns their paths
+    pub fn insert_directory(&mut self, directory: &Utf8Path) -> Result<Vec<Utf8PathBuf>, CompileError> {
+        let mut files = Vec::new();
+        self.insert_directory_recursive(directory, &mut files)?;
+        Ok(files)
+    }
+
+    fn insert_directory_recursive(&mut self, directory: &Utf8Path, files: &mut Vec<Utf8PathBuf>) -> Result<(), CompileError> {
+        let io_error = |error| CompileError::Io { path: directory.to_owned(), error };
+
+        for entry in std::fs::read_dir(directory).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            let path = Utf8PathBuf::from_path_buf(path)
+                .map_err(|path| io_error(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{} is not valid UTF-8", path.display()))))?;
+
+            if path.is_dir() {
+                self.insert_directory_recursive(&path, files)?;
+            } else if path.extension() == Some("skysl") {
+                let contents = std::fs::read_to_string(&path).map_err(|error| CompileError::Io { path: path.clone(), error })?;
+                self.insert_file(&path, Arc::new(contents))?;
+                files.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn insert_file(
         &mut self,
         path: &Utf8Path,
@@ -89,6 +123,115 @@ impl Workspace {
         Ok(typed_hir)
     }
 
+    /// Lists the pipeline overridable constants declared in a file, ordered like their declarations
+    pub fn get_overrides(&mut self, path: &Utf8Path) -> Result<Vec<OverrideConstant>, CompileError> {
+        let typed_hir = self.get_typed_hir(path)?;
+        let overrides = typed_hir.items.iter()
+            .filter_map(|item| match item {
+                hir::typed::ItemKind::Const(constant) => match (constant.override_id, constant.ty) {
+                    (Some(id), hir::type_check::Ty::Scalar(ty)) => Some(OverrideConstant {
+                        id,
+                        name: self.db.lookup_intern_name(constant.name),
+                        ty,
+                        default: constant.value,
+                    }),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        Ok(overrides)
+    }
+
+    /// Computes the memory layout of a struct declared in a file, `None` if there is no such struct
+    pub fn get_struct_layout(&mut self, path: &Utf8Path, name: &str, rules: hir::memory_layout::LayoutRules) -> Result<Option<hir::memory_layout::StructLayout>, CompileError> {
+        let path = path.strip_prefix(&self.root_path).unwrap();
+        let file_id = lookup_file(&self.db, path).ok_or_else(|| {
+            CompileError::FileSystemError(FileSystemError::FileDoesNotExist(path.to_owned()))
+        })?;
+
+        let name = self.db.intern_name(name.to_string());
+        let layout = self.db.get_symbols(file_id)
+            .find_by_name_and_kind(name, hir::symbol::SymbolKind::Struct)
+            .map(|_| self.db.struct_layout(hir::type_check::StructTy { file: file_id, name }, rules));
+
+        Ok(layout)
+    }
+
+    /// Reflects the layouts and entry points of the module in a file and all modules declared below it,
+    /// e.g. of a whole package given its `main.skysl`
+    pub fn get_reflection(&mut self, path: &Utf8Path) -> Result<Reflection, CompileError> {
+        let path = path.strip_prefix(&self.root_path).unwrap();
+        let file_id = lookup_file(&self.db, path).ok_or_else(|| {
+            CompileError::FileSystemError(FileSystemError::FileDoesNotExist(path.to_owned()))
+        })?;
+
+        Ok(reflect_package(&self.db, file_id))
+    }
+
+    /// Generates Rust types for the structs and layout bindings of the module in a file and all modules declared below it,
+    /// with one module of `#[repr(C)]` structs per rule set
+    pub fn get_rust_types(&mut self, path: &Utf8Path, rules: &[hir::memory_layout::LayoutRules]) -> Result<String, CompileError> {
+        let path = path.strip_prefix(&self.root_path).unwrap();
+        let file_id = lookup_file(&self.db, path).ok_or_else(|| {
+            CompileError::FileSystemError(FileSystemError::FileDoesNotExist(path.to_owned()))
+        })?;
+
+        Ok(generate_rust_types(&self.db, file_id, rules))
+    }
+
+    /// Describes the Rust type generated for a struct given its path below the module in `root`, e.g. `lights::Light`,
+    /// `None` if there is no such struct
+    pub fn get_rust_struct(&mut self, root: &Utf8Path, path: &str, rules: hir::memory_layout::LayoutRules) -> Result<Option<RustStruct>, CompileError> {
+        let root = root.strip_prefix(&self.root_path).unwrap();
+        let root = lookup_file(&self.db, root).ok_or_else(|| {
+            CompileError::FileSystemError(FileSystemError::FileDoesNotExist(root.to_owned()))
+        })?;
+
+        let rust_struct = self.find_item(root, path, hir::symbol::SymbolKind::Struct)
+            .map(|(file, name)| rust_struct(&self.db, root, hir::type_check::StructTy { file, name }, rules));
+
+        Ok(rust_struct)
+    }
+
+    /// Merges the bindings of entry points sharing a pipeline, given their paths below the module in `root`,
+    /// e.g. `["vertex_main", "lights::shade"]`, and reports the bindings they disagree on
+    pub fn get_pipeline_layout(&mut self, root: &Utf8Path, entry_points: &[&str]) -> Result<PipelineReflection, CompileError> {
+        let root = root.strip_prefix(&self.root_path).unwrap();
+        let root = lookup_file(&self.db, root).ok_or_else(|| {
+            CompileError::FileSystemError(FileSystemError::FileDoesNotExist(root.to_owned()))
+        })?;
+
+        let entry_points = entry_points.iter()
+            .map(|path| self.find_entry_point(root, path, None))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(reflect_pipeline_layout(&self.db, root, entry_points))
+    }
+
+    /// Matches the inputs of a fragment entry point with the outputs of a vertex entry point, given their paths
+    /// below the module in `root`, and reports inputs that don't match by their member
+    pub fn get_stage_interface(&mut self, root: &Utf8Path, vertex: &str, fragment: &str) -> Result<StageInterfaceReflection, CompileError> {
+        let root = root.strip_prefix(&self.root_path).unwrap();
+        let root = lookup_file(&self.db, root).ok_or_else(|| {
+            CompileError::FileSystemError(FileSystemError::FileDoesNotExist(root.to_owned()))
+        })?;
+
+        let vertex = self.find_entry_point(root, vertex, Some(hir::untyped::ShaderStage::Vertex))?;
+        let fragment = self.find_entry_point(root, fragment, Some(hir::untyped::ShaderStage::Fragment))?;
+
+        Ok(reflect_stage_interface(&self.db, root, vertex, fragment))
+    }
+
+    pub fn get_primitives(&self) -> hir::primitive::PrimitiveList {
+        self.db.get_primitives()
+    }
+
+    pub fn get_intrinsics(&self) -> hir::intrinsic::IntrinsicList {
+        self.db.get_intrinsics()
+    }
+
     pub fn get_item_at(&mut self, path: &Utf8Path, _line: u32, _character: u32) -> Result<(), CompileError> {
         let path = path.strip_prefix(&self.root_path).unwrap();
 
@@ -101,7 +244,54 @@ impl Workspace {
         Ok(())
     }
 
+    /// Finds an item by its path below the module in `root`, e.g. `lights::Light`
+    fn find_item(&self, root: FileId, path: &str, kind: hir::symbol::SymbolKind) -> Option<(FileId, Name)> {
+        let (module, name) = match path.rsplit_once("::") {
+            Some((module, name)) => (format!("package::{}", module), name),
+            None => ("package".to_string(), path),
+        };
+
+        let mut modules = Vec::new();
+        collect_modules(&self.db, root, "package".to_string(), &mut modules);
+        let (file, _) = modules.into_iter().find(|(_, path)| *path == module)?;
+
+        let name = self.db.intern_name(name.to_string());
+        self.db.get_symbols(file).find_by_name_and_kind(name, kind)?;
+        Some((file, name))
+    }
+
+    /// Finds an entry point by its path below the module in `root`, which has to run in `expected` if given
+    fn find_entry_point(&self, root: FileId, path: &str, expected: Option<hir::untyped::ShaderStage>) -> Result<hir::pipeline::EntryPointId, CompileError> {
+        let (file, name) = self.find_item(root, path, hir::symbol::SymbolKind::Function)
+            .ok_or_else(|| CompileError::UnknownEntryPoint { path: path.to_string() })?;
+
+        let stage = self.db.get_hir(file).items.iter().find_map(|item| match item {
+            hir::untyped::ItemKind::Function(function) if function.signature.name == name => function.stage,
+            _ => None,
+        });
+
+        match (stage, expected) {
+            (None, _) => Err(CompileError::UnknownEntryPoint { path: path.to_string() }),
+            (Some(stage), Some(expected)) if stage != expected => Err(CompileError::UnexpectedStage {
+                path: path.to_string(),
+                expected: expected.name(),
+                found: stage.name(),
+            }),
+            _ => Ok(hir::pipeline::EntryPointId { file, name }),
+        }
+    }
+
     pub fn interned_name(&self, name: Name) -> String {
         self.db.lookup_intern_name(name)
     }
+
+    /// Displays the type a type alias expands to, `None` if the symbol is not a type alias or the alias is recursive
+    pub fn display_type_alias(&self, symbol: &hir::symbol::Symbol) -> Option<String> {
+        if symbol.kind != hir::symbol::SymbolKind::TypeAlias {
+            return None;
+        }
+
+        let ty = self.db.resolve_type_alias(symbol.file, symbol.name).ok()?;
+        Some(ty.display(&self.db))
+    }
 }